@@ -38,4 +38,14 @@ pub enum AmmError {
     ConstantProductInvariantFailed,
     #[msg("Casting has caused an overflow")]
     CastingOverflow,
+    #[msg("StableSwap `amplification` must be between `MIN_AMPLIFICATION` and `MAX_AMPLIFICATION`")]
+    InvalidAmplification,
+    #[msg("StableSwap pools need their base and quote mints to have the same decimals")]
+    StableSwapDecimalsMismatch,
+    #[msg("StableSwap math overflowed or failed to converge")]
+    StableSwapCalculationError,
+    #[msg("D should always be increasing")]
+    StableSwapInvariantFailed,
+    #[msg("This AMM is already in the current layout")]
+    AmmAlreadyMigrated,
 }
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::{state::SwapType, Amm};
+use crate::{state::{AmmCurve, SwapType}, Amm};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CommonFields {
@@ -33,12 +33,27 @@ impl CommonFields {
     }
 }
 
+/// Bumped whenever fields are appended to `SwapEvent`.
+pub const SWAP_EVENT_VERSION: u8 = 1;
+
 #[event]
 pub struct SwapEvent {
     pub common: CommonFields,
     pub input_amount: u64,
     pub output_amount: u64,
     pub swap_type: SwapType,
+    /// Everything below was added in version 1. New fields only ever get
+    /// appended, so parsers built against an older version can still decode
+    /// the prefix that they know about.
+    pub version: u8,
+    /// The fee kept by the pool, denominated in the input token.
+    pub fee_amount: u64,
+    pub pre_base_reserves: u64,
+    pub pre_quote_reserves: u64,
+    pub pre_spot_price: u128,
+    pub post_spot_price: u128,
+    /// Whether the swap recorded a new TWAP observation.
+    pub twap_observation_recorded: bool,
 }
 
 #[event]
@@ -71,6 +86,7 @@ pub struct CreateAmmEvent {
     pub quote_mint: Pubkey,
     pub vault_ata_base: Pubkey,
     pub vault_ata_quote: Pubkey,
+    pub curve: AmmCurve,
 }
 
 #[event]
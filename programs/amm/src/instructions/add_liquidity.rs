@@ -9,11 +9,11 @@ use crate::events::{AddLiquidityEvent, CommonFields};
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct AddLiquidityArgs {
     /// How much quote token you will deposit to the pool
-    quote_amount: u64,
+    pub quote_amount: u64,
     /// The maximum base token you will deposit to the pool
-    max_base_amount: u64,
+    pub max_base_amount: u64,
     /// The minimum LP token you will get back
-    min_lp_tokens: u64,
+    pub min_lp_tokens: u64,
 }
 
 impl AddOrRemoveLiquidity<'_> {
@@ -62,18 +62,8 @@ impl AddOrRemoveLiquidity<'_> {
         let (lp_tokens_to_mint, base_amount) = if total_lp_supply > 0 {
             require!(min_lp_tokens > 0, AmmError::ZeroMinLpTokens);
 
-            let quote_reserve = amm.quote_amount as u128;
-            let base_reserve = amm.base_amount as u128;
-
-            // this should only panic in an extreme scenario: when (quote_amount * base_reserve) / quote_reserve > u64::MAX
-            let base_amount: u64 = (((quote_amount as u128 * base_reserve) / quote_reserve) + 1)
-                .try_into()
-                .map_err(|_| AmmError::CastingOverflow)?;
-
-            let lp_tokens_to_mint: u64 = ((quote_amount as u128 * total_lp_supply as u128)
-                / quote_reserve)
-                .try_into()
-                .map_err(|_| AmmError::CastingOverflow)?;
+            let (lp_tokens_to_mint, base_amount) =
+                amm.get_liquidity_to_add(quote_amount, total_lp_supply)?;
 
             require_gte!(
                 max_base_amount,
@@ -98,8 +88,14 @@ impl AddOrRemoveLiquidity<'_> {
             (initial_lp_tokens, base_amount)
         };
 
-        amm.base_amount += base_amount;
-        amm.quote_amount += quote_amount;
+        amm.base_amount = amm
+            .base_amount
+            .checked_add(base_amount)
+            .ok_or(error!(AmmError::AddLiquidityCalculationError))?;
+        amm.quote_amount = amm
+            .quote_amount
+            .checked_add(quote_amount)
+            .ok_or(error!(AmmError::AddLiquidityCalculationError))?;
 
         let seeds = generate_amm_seeds!(amm);
         let signer = &[&seeds[..]];
@@ -11,6 +11,7 @@ use crate::events::{CreateAmmEvent, CommonFields};
 pub struct CreateAmmArgs {
     pub twap_initial_observation: u128,
     pub twap_max_observation_change_per_update: u128,
+    pub curve: AmmCurve,
 }
 
 #[event_cpi]
@@ -94,8 +95,21 @@ impl CreateAmm<'_> {
         let CreateAmmArgs {
             twap_initial_observation,
             twap_max_observation_change_per_update,
+            curve,
         } = args;
 
+        if let AmmCurve::StableSwap { amplification } = curve {
+            require!(
+                (MIN_AMPLIFICATION..=MAX_AMPLIFICATION).contains(&amplification),
+                AmmError::InvalidAmplification
+            );
+            require_eq!(
+                base_mint.decimals,
+                quote_mint.decimals,
+                AmmError::StableSwapDecimalsMismatch
+            );
+        }
+
         amm.set_inner(Amm {
             bump: ctx.bumps.amm,
 
@@ -118,6 +132,14 @@ impl CreateAmm<'_> {
             ),
 
             seq_num: 0,
+
+            curve,
+
+            cumulative_base_fees_per_lp: 0,
+            cumulative_quote_fees_per_lp: 0,
+            quote_volume: 0,
+            quote_liquidity_aggregator: 0,
+            quote_liquidity_updated_slot: current_slot,
         });
 
         let clock = Clock::get()?;
@@ -130,6 +152,7 @@ impl CreateAmm<'_> {
             quote_mint: quote_mint.key(),
             vault_ata_base: vault_ata_base.key(),
             vault_ata_quote: vault_ata_quote.key(),
+            curve,
         });
 
         Ok(())
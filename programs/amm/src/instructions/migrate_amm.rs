@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+
+use crate::error::AmmError;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct MigrateAmm<'info> {
+    /// CHECK: deserialized in `handle`, since it may be too small for the current layout
+    #[account(mut, owner = crate::ID)]
+    pub amm: UncheckedAccount<'info>,
+    /// Pays for the AMM's account to grow to the current layout.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateAmm<'_> {
+    /// Grows an AMM created before its newer fields were added. Those fields
+    /// are all at the end of `Amm`, and zeroes deserialize into their defaults:
+    /// a constant-product curve, no fees or volume yet, and no liquidity
+    /// history. Liquidity is tracked from the migration onwards. Anyone can
+    /// call this, since it doesn't change how the AMM trades.
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let MigrateAmm {
+            amm,
+            payer,
+            system_program,
+        } = ctx.accounts;
+
+        {
+            let data = amm.try_borrow_data()?;
+
+            require!(
+                data.len() >= 8 && data[..8] == Amm::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+        }
+
+        let space = 8 + std::mem::size_of::<Amm>();
+
+        require_gt!(space, amm.data_len(), AmmError::AmmAlreadyMigrated);
+
+        let rent_exempt_lamports = Rent::get()?.minimum_balance(space);
+
+        if rent_exempt_lamports > amm.lamports() {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    Transfer {
+                        from: payer.to_account_info(),
+                        to: amm.to_account_info(),
+                    },
+                ),
+                rent_exempt_lamports - amm.lamports(),
+            )?;
+        }
+
+        amm.realloc(space, true)?;
+
+        // this also makes sure that it can be loaded now
+        let mut migrated_amm = Amm::try_deserialize(&mut &amm.try_borrow_data()?[..])?;
+        migrated_amm.quote_liquidity_updated_slot = Clock::get()?.slot;
+        migrated_amm.try_serialize(&mut &mut amm.try_borrow_mut_data()?[..])?;
+
+        Ok(())
+    }
+}
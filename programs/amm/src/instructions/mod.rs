@@ -2,6 +2,7 @@ pub use add_liquidity::*;
 pub use common::*;
 pub use crank_that_twap::*;
 pub use create_amm::*;
+pub use migrate_amm::*;
 pub use remove_liquidity::*;
 pub use swap::*;
 
@@ -9,5 +10,6 @@ pub mod add_liquidity;
 pub mod common;
 pub mod crank_that_twap;
 pub mod create_amm;
+pub mod migrate_amm;
 pub mod remove_liquidity;
 pub mod swap;
@@ -54,7 +54,7 @@ impl AddOrRemoveLiquidity<'_> {
         require_gt!(total_liquidity, 0, AmmError::AssertFailed);
 
         let (base_to_withdraw, quote_to_withdraw) =
-            amm.get_base_and_quote_withdrawable(lp_tokens_to_burn, total_liquidity);
+            amm.get_base_and_quote_withdrawable(lp_tokens_to_burn, total_liquidity)?;
 
         require_gte!(
             base_to_withdraw,
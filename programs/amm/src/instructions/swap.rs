@@ -5,7 +5,7 @@ use crate::error::AmmError;
 use crate::generate_amm_seeds;
 use crate::state::*;
 // use crate::SwapEvent;
-use crate::events::{SwapEvent, CommonFields};
+use crate::events::{SwapEvent, CommonFields, SWAP_EVENT_VERSION};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct SwapArgs {
@@ -19,8 +19,9 @@ pub struct SwapArgs {
 pub struct Swap<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
-    #[account(mut)]
+    #[account(mut, has_one = lp_mint)]
     pub amm: Account<'info, Amm>,
+    pub lp_mint: Account<'info, Mint>,
     #[account(
         mut,
         token::mint = amm.base_mint,
@@ -53,6 +54,7 @@ impl Swap<'_> {
         let Swap {
             user,
             amm,
+            lp_mint,
             user_base_account,
             user_quote_account,
             vault_ata_base,
@@ -85,10 +87,17 @@ impl Swap<'_> {
 
         require!(input_amount > 0, AmmError::ZeroSwapAmount);
 
-        amm.update_twap(clock.slot)?;
+        let twap_observation = amm.update_twap(clock.slot)?;
+
+        let pre_base_reserves = amm.base_amount;
+        let pre_quote_reserves = amm.quote_amount;
+        let pre_spot_price = amm.get_spot_price()?;
 
         let output_amount = amm.swap(input_amount, swap_type)?;
 
+        let fee_amount = Amm::get_swap_fee(input_amount);
+        amm.accrue_swap_fee(fee_amount, swap_type, lp_mint.supply);
+
         let seeds = generate_amm_seeds!(amm);
 
         let (user_from, vault_to, vault_from, user_to) = match swap_type {
@@ -144,6 +153,13 @@ impl Swap<'_> {
             input_amount,
             output_amount,
             swap_type,
+            version: SWAP_EVENT_VERSION,
+            fee_amount,
+            pre_base_reserves,
+            pre_quote_reserves,
+            pre_spot_price,
+            post_spot_price: amm.get_spot_price()?,
+            twap_observation_recorded: twap_observation.is_some(),
         });
 
         Ok(())
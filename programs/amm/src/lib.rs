@@ -55,4 +55,8 @@ pub mod amm {
     pub fn crank_that_twap(ctx: Context<CrankThatTwap>) -> Result<()> {
         CrankThatTwap::handle(ctx)
     }
+
+    pub fn migrate_amm(ctx: Context<MigrateAmm>) -> Result<()> {
+        MigrateAmm::handle(ctx)
+    }
 }
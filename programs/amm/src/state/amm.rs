@@ -2,7 +2,8 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::clock::Slot;
 
 use crate::error::AmmError;
-use crate::{MAX_PRICE, ONE_MINUTE_IN_SLOTS, PRICE_SCALE};
+use crate::state::curve::*;
+use crate::{FEES_PER_LP_SCALE_BITS, MAX_PRICE, ONE_MINUTE_IN_SLOTS, PRICE_SCALE};
 use std::cmp::{max, min, Ordering};
 
 #[derive(Clone, Copy, Debug, AnchorSerialize, AnchorDeserialize)]
@@ -84,6 +85,67 @@ pub struct Amm {
     pub oracle: TwapOracle,
 
     pub seq_num: u64,
+
+    pub curve: AmmCurve,
+
+    /// Running sums of the fees that each LP token has earned, in Q64.64 form.
+    /// Like the TWAP aggregator, these wrap on overflow, so compare two
+    /// snapshots with `FeeSnapshot::get_fees_earned_since` rather than by
+    /// subtracting them directly.
+    pub cumulative_base_fees_per_lp: u128,
+    pub cumulative_quote_fees_per_lp: u128,
+
+    /// The total amount of quote tokens that have been swapped in or out of
+    /// this AMM, so other programs can tell whether a market was actually traded.
+    pub quote_volume: u128,
+
+    /// Running sum of quote_amount over every slot up to
+    /// `quote_liquidity_updated_slot`. Each change to the reserves first credits
+    /// the reserve from before the change for the slots since the last one, so
+    /// liquidity only counts for the slots that it was actually in the pool.
+    /// Lets clients check that a market actually had liquidity over some
+    /// period, rather than only at the moments they happened to look at it.
+    ///
+    /// Like the TWAP aggregator, this wraps back to 0 on overflow, so compare
+    /// two readings with `get_average_quote_liquidity_since`.
+    pub quote_liquidity_aggregator: u128,
+    pub quote_liquidity_updated_slot: u64,
+}
+
+/// The state of an AMM's fee accumulators at a point in time.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct FeeSnapshot {
+    pub cumulative_base_fees_per_lp: u128,
+    pub cumulative_quote_fees_per_lp: u128,
+}
+
+impl FeeSnapshot {
+    /// The `(base, quote)` fees earned by `lp_tokens` that were held from
+    /// `start` until this snapshot. Rounds down.
+    pub fn get_fees_earned_since(&self, start: &FeeSnapshot, lp_tokens: u64) -> (u64, u64) {
+        let fees_earned = |end: u128, start: u128| -> u64 {
+            let fees_per_lp = U256::from(end.wrapping_sub(start));
+            let fees = (fees_per_lp * U256::from(lp_tokens)) >> FEES_PER_LP_SCALE_BITS;
+
+            // can only exceed u64::MAX if the accumulator wrapped more than once
+            if fees > U256::from(u64::MAX) {
+                u64::MAX
+            } else {
+                fees.as_u64()
+            }
+        };
+
+        (
+            fees_earned(
+                self.cumulative_base_fees_per_lp,
+                start.cumulative_base_fees_per_lp,
+            ),
+            fees_earned(
+                self.cumulative_quote_fees_per_lp,
+                start.cumulative_quote_fees_per_lp,
+            ),
+        )
+    }
 }
 
 impl Amm {
@@ -91,6 +153,54 @@ impl Amm {
         self.base_amount as u128 * self.quote_amount as u128
     }
 
+    /// The current price of the pool, in the same units as `TwapOracle::last_price`.
+    /// Returns 0 if the pool has no reserves.
+    pub fn get_spot_price(&self) -> Result<u128> {
+        if self.base_amount == 0 || self.quote_amount == 0 {
+            return Ok(0);
+        }
+
+        match self.curve {
+            AmmCurve::ConstantProduct => {
+                Ok((self.quote_amount as u128 * PRICE_SCALE) / self.base_amount as u128)
+            }
+            AmmCurve::StableSwap { amplification } => {
+                get_stable_swap_price(self.base_amount, self.quote_amount, amplification)
+            }
+        }
+    }
+
+    pub fn get_fee_snapshot(&self) -> FeeSnapshot {
+        FeeSnapshot {
+            cumulative_base_fees_per_lp: self.cumulative_base_fees_per_lp,
+            cumulative_quote_fees_per_lp: self.cumulative_quote_fees_per_lp,
+        }
+    }
+
+    /// Credits the fee from a swap to the pool's LPs. `fee_amount` is
+    /// denominated in the swap's input token.
+    pub fn accrue_swap_fee(&mut self, fee_amount: u64, swap_type: SwapType, lp_total_supply: u64) {
+        if lp_total_supply == 0 {
+            return;
+        }
+
+        let fees_per_lp =
+            ((fee_amount as u128) << FEES_PER_LP_SCALE_BITS) / lp_total_supply as u128;
+
+        let accumulator = match swap_type {
+            SwapType::Buy => &mut self.cumulative_quote_fees_per_lp,
+            SwapType::Sell => &mut self.cumulative_base_fees_per_lp,
+        };
+
+        *accumulator = accumulator.wrapping_add(fees_per_lp);
+    }
+
+    /// The part of `input_amount` that stays in the pool as a fee. `swap` prices
+    /// 99% of the input against the reserves, so this is 1% rounded up.
+    pub fn get_swap_fee(input_amount: u64) -> u64 {
+        input_amount - ((input_amount as u128 * 99) / 100) as u64
+    }
+
     /// Does the internal accounting to swap `input_amount` into the returned
     /// output amount so that output amount can be transferred to the user.
     pub fn swap(&mut self, input_amount: u64, swap_type: SwapType) -> Result<u64> {
@@ -110,62 +220,143 @@ impl Amm {
         require!(input_reserve != 0, AmmError::NoReserves);
         require!(output_reserve != 0, AmmError::NoReserves);
 
-        let input_amount_with_fee = input_amount as u128 * 99;
+        let output_amount = match self.curve {
+            AmmCurve::ConstantProduct => {
+                let input_amount_with_fee = input_amount as u128 * 99;
 
-        let numerator = input_amount_with_fee
-            .checked_mul(output_reserve)
-            .ok_or(error!(AmmError::InputAmountOverflow))?;
+                let numerator = input_amount_with_fee
+                    .checked_mul(output_reserve)
+                    .ok_or(error!(AmmError::InputAmountOverflow))?;
 
-        let denominator = (input_reserve * 100) + input_amount_with_fee;
+                let denominator = (input_reserve * 100) + input_amount_with_fee;
 
-        let output_amount = (numerator / denominator)
-            .try_into()
-            .map_err(|_| AmmError::CastingOverflow)?;
+                (numerator / denominator)
+                    .try_into()
+                    .map_err(|_| AmmError::CastingOverflow)?
+            }
+            AmmCurve::StableSwap { amplification } => get_stable_swap_output_amount(
+                input_reserve as u64,
+                output_reserve as u64,
+                input_amount - Self::get_swap_fee(input_amount),
+                amplification,
+            )?,
+        };
 
+        // `output_amount` is always less than `output_reserve`, so only the
+        // additions can overflow
         match swap_type {
             SwapType::Buy => {
-                self.quote_amount += input_amount;
+                self.quote_amount = self
+                    .quote_amount
+                    .checked_add(input_amount)
+                    .ok_or(error!(AmmError::InputAmountOverflow))?;
                 self.base_amount -= output_amount;
             }
             SwapType::Sell => {
-                self.base_amount += input_amount;
+                self.base_amount = self
+                    .base_amount
+                    .checked_add(input_amount)
+                    .ok_or(error!(AmmError::InputAmountOverflow))?;
                 self.quote_amount -= output_amount;
             }
         }
 
-        let new_k = self.k();
+        let quote_amount_swapped = match swap_type {
+            SwapType::Buy => input_amount,
+            SwapType::Sell => output_amount,
+        };
+        self.quote_volume = self
+            .quote_volume
+            .saturating_add(quote_amount_swapped as u128);
 
-        require_gte!(new_k, k, AmmError::ConstantProductInvariantFailed);
+        match self.curve {
+            AmmCurve::ConstantProduct => {
+                let new_k = self.k();
+
+                require_gte!(new_k, k, AmmError::ConstantProductInvariantFailed);
+            }
+            AmmCurve::StableSwap { amplification } => {
+                // k can decrease on a StableSwap curve, but D can't
+                let d = compute_stable_swap_d(
+                    base_amount_start as u64,
+                    quote_amount_start as u64,
+                    amplification,
+                )?;
+                let new_d =
+                    compute_stable_swap_d(self.base_amount, self.quote_amount, amplification)?;
+
+                require_gte!(new_d, d, AmmError::StableSwapInvariantFailed);
+            }
+        }
 
         Ok(output_amount)
     }
 
+    /// Get the number of LP tokens to mint and the number of base tokens to
+    /// deposit when adding `quote_amount` to a pool that already has liquidity.
+    /// Both round in the pool's favor.
+    ///
+    /// Returns `(lp_tokens_to_mint, base_amount)`.
+    pub fn get_liquidity_to_add(
+        &self,
+        quote_amount: u64,
+        lp_total_supply: u64,
+    ) -> Result<(u64, u64)> {
+        require_gt!(lp_total_supply, 0, AmmError::AddLiquidityCalculationError);
+        require_gt!(self.quote_amount, 0, AmmError::AddLiquidityCalculationError);
+
+        let quote_reserve = self.quote_amount as u128;
+        let base_reserve = self.base_amount as u128;
+
+        // this should only fail in an extreme scenario: when (quote_amount * base_reserve) / quote_reserve > u64::MAX
+        let base_amount: u64 = (((quote_amount as u128 * base_reserve) / quote_reserve) + 1)
+            .try_into()
+            .map_err(|_| AmmError::CastingOverflow)?;
+
+        let lp_tokens_to_mint: u64 = ((quote_amount as u128 * lp_total_supply as u128)
+            / quote_reserve)
+            .try_into()
+            .map_err(|_| AmmError::CastingOverflow)?;
+
+        Ok((lp_tokens_to_mint, base_amount))
+    }
+
     /// Get the number of base and quote tokens withdrawable from a position
     pub fn get_base_and_quote_withdrawable(
         &self,
         lp_tokens: u64,
         lp_total_supply: u64,
-    ) -> (u64, u64) {
-        (
-            self.get_base_withdrawable(lp_tokens, lp_total_supply),
-            self.get_quote_withdrawable(lp_tokens, lp_total_supply),
-        )
+    ) -> Result<(u64, u64)> {
+        Ok((
+            self.get_base_withdrawable(lp_tokens, lp_total_supply)?,
+            self.get_quote_withdrawable(lp_tokens, lp_total_supply)?,
+        ))
     }
 
     /// Get the number of base tokens withdrawable from a position
-    pub fn get_base_withdrawable(&self, lp_tokens: u64, lp_total_supply: u64) -> u64 {
+    pub fn get_base_withdrawable(&self, lp_tokens: u64, lp_total_supply: u64) -> Result<u64> {
+        require_gte!(lp_total_supply, lp_tokens, AmmError::InsufficientBalance);
+        require_gt!(lp_total_supply, 0, AmmError::InsufficientBalance);
+
         // must fit back into u64 since `lp_tokens` <= `lp_total_supply`
-        ((lp_tokens as u128 * self.base_amount as u128) / lp_total_supply as u128) as u64
+        Ok(((lp_tokens as u128 * self.base_amount as u128) / lp_total_supply as u128) as u64)
     }
 
     /// Get the number of quote tokens withdrawable from a position
-    pub fn get_quote_withdrawable(&self, lp_tokens: u64, lp_total_supply: u64) -> u64 {
-        ((lp_tokens as u128 * self.quote_amount as u128) / lp_total_supply as u128) as u64
+    pub fn get_quote_withdrawable(&self, lp_tokens: u64, lp_total_supply: u64) -> Result<u64> {
+        require_gte!(lp_total_supply, lp_tokens, AmmError::InsufficientBalance);
+        require_gt!(lp_total_supply, 0, AmmError::InsufficientBalance);
+
+        Ok(((lp_tokens as u128 * self.quote_amount as u128) / lp_total_supply as u128) as u64)
     }
 
     /// Returns the time-weighted average price since market creation in UQ64x32 form.
     pub fn get_twap(&self) -> Result<u128> {
-        let slots_passed = (self.oracle.last_updated_slot - self.created_at_slot) as u128;
+        let slots_passed = self
+            .oracle
+            .last_updated_slot
+            .checked_sub(self.created_at_slot)
+            .ok_or(error!(AmmError::AssertFailed))? as u128;
 
         require_neq!(slots_passed, 0, AmmError::NoSlotsPassed);
         require!(self.oracle.aggregator != 0, AmmError::AssertFailed);
@@ -173,12 +364,57 @@ impl Amm {
         Ok(self.oracle.aggregator / slots_passed)
     }
 
+    /// Returns what `quote_liquidity_aggregator` would be if it were updated at
+    /// `current_slot`. The reserves can't have changed since it was last
+    /// updated, so this is exact.
+    pub fn get_quote_liquidity_aggregator(&self, current_slot: Slot) -> u128 {
+        let slots_passed = current_slot.saturating_sub(self.quote_liquidity_updated_slot) as u128;
+
+        // can't saturate since both are less than 2^64
+        self.quote_liquidity_aggregator
+            .wrapping_add(self.quote_amount as u128 * slots_passed)
+    }
+
+    /// Returns the time-weighted average quote reserve between `start_slot`,
+    /// when `get_quote_liquidity_aggregator` returned `start_aggregator`, and
+    /// `current_slot`.
+    pub fn get_average_quote_liquidity_since(
+        &self,
+        start_slot: Slot,
+        start_aggregator: u128,
+        current_slot: Slot,
+    ) -> Result<u64> {
+        let slots_passed = current_slot
+            .checked_sub(start_slot)
+            .ok_or(error!(AmmError::AssertFailed))? as u128;
+
+        require_neq!(slots_passed, 0, AmmError::NoSlotsPassed);
+
+        let aggregated = self
+            .get_quote_liquidity_aggregator(current_slot)
+            .wrapping_sub(start_aggregator);
+
+        // can't exceed u64::MAX unless the aggregator has wrapped more than
+        // once, in which case the result is meaningless anyways
+        Ok((aggregated / slots_passed).min(u64::MAX as u128) as u64)
+    }
+
+    /// Credits the current quote reserve for the slots since the last update.
+    fn update_quote_liquidity_aggregator(&mut self, current_slot: Slot) {
+        self.quote_liquidity_aggregator = self.get_quote_liquidity_aggregator(current_slot);
+        self.quote_liquidity_updated_slot =
+            max(self.quote_liquidity_updated_slot, current_slot);
+    }
+
     /// Updates the TWAP. Should be called before any changes to the AMM's state
     /// have been made.
     ///
     /// Returns an observation if one was recorded.
     pub fn update_twap(&mut self, current_slot: Slot) -> Result<Option<u128>> {
-        let oracle = &mut self.oracle;
+        // unlike observations, liquidity can't be manipulated by updating
+        // often, so this is updated on every change to the reserves
+        self.update_quote_liquidity_aggregator(current_slot);
+
         // a manipulator is likely to be "bursty" with their usage, such as a
         // validator who abuses their slots to manipulate the TWAP.
         // meanwhile, regular trading is less likely to happen in each slot.
@@ -197,7 +433,7 @@ impl Amm {
         // we allow updates once a minute as a happy medium. if you have an asset
         // that trades near $1500 and you allow $25 updates per minute, it can double
         // over an hour.
-        if current_slot < oracle.last_updated_slot + ONE_MINUTE_IN_SLOTS {
+        if current_slot < self.oracle.last_updated_slot.saturating_add(ONE_MINUTE_IN_SLOTS) {
             return Ok(None);
         }
 
@@ -212,7 +448,11 @@ impl Amm {
         // - 4 * 1,000,000,000 = 4,000,000,000 META units (hansons)
         // so there's (400,000,000 / 4,000,000,000) or 0.1 USDC units per hanson,
         // which is 100,000,000,000 when scaled by 1e12.
-        let price = (self.quote_amount as u128 * PRICE_SCALE) / self.base_amount as u128;
+        //
+        // StableSwap pools use their marginal price rather than the ratio of reserves.
+        let price = self.get_spot_price()?;
+
+        let oracle = &mut self.oracle;
 
         let last_observation = oracle.last_observation;
 
@@ -331,6 +571,99 @@ mod simple_amm_tests {
         assert_eq!(amm_clone.k(), 30); // 2 x 15
     }
 
+    #[test]
+    pub fn swap_fee_and_spot_price() {
+        assert_eq!(Amm::get_swap_fee(0), 0);
+        assert_eq!(Amm::get_swap_fee(1), 1);
+        assert_eq!(Amm::get_swap_fee(100), 1);
+        assert_eq!(Amm::get_swap_fee(101), 2);
+
+        let amm = Amm {
+            base_amount: 4_000_000_000,
+            quote_amount: 400_000_000,
+            ..Amm::default()
+        };
+
+        assert_eq!(amm.get_spot_price().unwrap(), 100_000_000_000);
+        assert_eq!(Amm::default().get_spot_price().unwrap(), 0);
+    }
+
+    #[test]
+    pub fn stable_swap_amm() {
+        let mut amm = Amm {
+            base_amount: 1_000_000,
+            quote_amount: 1_000_000,
+            curve: AmmCurve::StableSwap { amplification: 100 },
+            ..Amm::default()
+        };
+
+        assert_eq!(amm.get_spot_price().unwrap(), PRICE_SCALE);
+
+        // near the peg, you get back almost everything after the 1% fee
+        assert_eq!(amm.swap(10_000, Buy).unwrap(), 9_899);
+        assert_eq!(amm.base_amount, 990_101);
+        assert_eq!(amm.quote_amount, 1_010_000);
+
+        // constant product would only have given back 9_802
+        let mut constant_product_amm = Amm {
+            base_amount: 1_000_000,
+            quote_amount: 1_000_000,
+            ..Amm::default()
+        };
+        assert_eq!(constant_product_amm.swap(10_000, Buy).unwrap(), 9_802);
+
+        let price = amm.get_spot_price().unwrap();
+        assert!(price > PRICE_SCALE);
+        assert!(price < PRICE_SCALE * 101 / 100);
+    }
+
+    #[test]
+    pub fn fee_accumulators() {
+        let mut amm = Amm {
+            base_amount: 1_000_000,
+            quote_amount: 1_000_000,
+            ..Amm::default()
+        };
+        // a power of two, so that there's no rounding
+        let lp_total_supply = 1_024;
+
+        let start = amm.get_fee_snapshot();
+
+        let fee = Amm::get_swap_fee(10_000);
+        amm.swap(10_000, Buy).unwrap();
+        amm.accrue_swap_fee(fee, Buy, lp_total_supply);
+
+        let fee = Amm::get_swap_fee(5_000);
+        amm.swap(5_000, Sell).unwrap();
+        amm.accrue_swap_fee(fee, Sell, lp_total_supply);
+
+        let end = amm.get_fee_snapshot();
+
+        assert_eq!(end.get_fees_earned_since(&start, lp_total_supply), (50, 100));
+        assert_eq!(end.get_fees_earned_since(&start, 256), (12, 25));
+        assert_eq!(end.get_fees_earned_since(&end, lp_total_supply), (0, 0));
+
+        // 10_000 quote in, then however much quote came out of the sell
+        assert_eq!(amm.quote_volume, 10_000 + 1_000_000 + 10_000 - amm.quote_amount as u128);
+
+        // accumulators wrap, but the difference is still right
+        let start = FeeSnapshot {
+            cumulative_base_fees_per_lp: u128::MAX,
+            cumulative_quote_fees_per_lp: u128::MAX,
+        };
+        let mut amm = Amm {
+            cumulative_base_fees_per_lp: u128::MAX,
+            cumulative_quote_fees_per_lp: u128::MAX,
+            ..Amm::default()
+        };
+        amm.accrue_swap_fee(100, Buy, lp_total_supply);
+
+        assert_eq!(
+            amm.get_fee_snapshot().get_fees_earned_since(&start, lp_total_supply),
+            (0, 100)
+        );
+    }
+
     #[test]
     pub fn simple_twap_math_amm() {
         let mut amm = Amm {
@@ -341,10 +674,74 @@ mod simple_amm_tests {
         };
 
         // minute hasn't passed since last slot
-        assert_eq!(amm.update_twap(1), None);
+        assert_eq!(amm.update_twap(1), Ok(None));
         assert_eq!(amm.oracle.last_updated_slot, 0);
 
-        assert_eq!(amm.update_twap(ONE_MINUTE_IN_SLOTS), Some(10 * PRICE_SCALE));
+        assert_eq!(amm.update_twap(ONE_MINUTE_IN_SLOTS), Ok(Some(10 * PRICE_SCALE)));
+    }
+
+    #[test]
+    pub fn average_quote_liquidity() {
+        let mut amm = Amm {
+            base_amount: 5,
+            quote_amount: 50,
+            oracle: TwapOracle::new(0, 1_000_000, MAX_PRICE),
+            ..Amm::default()
+        };
+
+        assert_eq!(
+            amm.get_average_quote_liquidity_since(0, 0, 0).unwrap_err(),
+            AmmError::NoSlotsPassed.into()
+        );
+
+        // doesn't need a crank to count
+        assert_eq!(amm.get_average_quote_liquidity_since(0, 0, 100).unwrap(), 50);
+
+        amm.update_twap(ONE_MINUTE_IN_SLOTS).unwrap();
+        assert_eq!(
+            amm.get_average_quote_liquidity_since(0, 0, ONE_MINUTE_IN_SLOTS).unwrap(),
+            50
+        );
+
+        // the pool was drained for the next 3 minutes
+        amm.quote_amount = 10;
+        let start_aggregator = amm.get_quote_liquidity_aggregator(ONE_MINUTE_IN_SLOTS);
+        assert_eq!(
+            amm.get_average_quote_liquidity_since(0, 0, ONE_MINUTE_IN_SLOTS * 4).unwrap(),
+            20
+        );
+        assert_eq!(
+            amm.get_average_quote_liquidity_since(
+                ONE_MINUTE_IN_SLOTS,
+                start_aggregator,
+                ONE_MINUTE_IN_SLOTS * 4
+            )
+            .unwrap(),
+            10
+        );
+    }
+
+    #[test]
+    pub fn quote_liquidity_only_counts_while_its_in_the_pool() {
+        let mut amm = Amm {
+            base_amount: 5,
+            quote_amount: 50,
+            oracle: TwapOracle::new(0, 1_000_000, MAX_PRICE),
+            ..Amm::default()
+        };
+
+        // someone adds a lot of liquidity right before a crank, which is still
+        // within the same minute as the last update...
+        amm.update_twap(ONE_MINUTE_IN_SLOTS).unwrap();
+        amm.update_twap(ONE_MINUTE_IN_SLOTS * 2 - 1).unwrap();
+        amm.quote_amount = 1_000_050;
+
+        // ...and pulls it right after
+        amm.update_twap(ONE_MINUTE_IN_SLOTS * 2).unwrap();
+        amm.quote_amount = 50;
+
+        let average = amm.get_average_quote_liquidity_since(0, 0, ONE_MINUTE_IN_SLOTS * 2).unwrap();
+        assert_eq!(average, 50 + 1_000_000 / (ONE_MINUTE_IN_SLOTS * 2));
     }
 
     #[test]
@@ -360,18 +757,271 @@ mod simple_amm_tests {
 
         let slots_until_overflow = u128::MAX / (u64::MAX as u128 * PRICE_SCALE);
 
-        amm.update_twap(slots_until_overflow as u64);
-        require!(amm.oracle.aggregator > MAX_PRICE * 18_400_000);
+        amm.update_twap(slots_until_overflow as u64).unwrap();
+        assert!(amm.oracle.aggregator > MAX_PRICE * 18_400_000);
         assert_ne!(amm.oracle.aggregator, u128::MAX);
 
-        amm_clone.update_twap(slots_until_overflow as u64 + 1);
+        amm_clone.update_twap(slots_until_overflow as u64 + 1).unwrap();
         assert_eq!(amm_clone.oracle.aggregator, u128::MAX);
 
         // check that it wraps over
-        amm_clone.update_twap(slots_until_overflow as u64 + 1 + ONE_MINUTE_IN_SLOTS);
+        amm_clone
+            .update_twap(slots_until_overflow as u64 + 1 + ONE_MINUTE_IN_SLOTS)
+            .unwrap();
         assert_eq!(
             amm_clone.oracle.aggregator,
             ONE_MINUTE_IN_SLOTS as u128 * MAX_PRICE - 1
         ); // sub 1 cuz wrap
     }
+
+    #[test]
+    pub fn amm_created_before_newer_fields_loads_once_grown() {
+        use anchor_lang::prelude::*;
+        use anchor_lang::Discriminator;
+
+        // the layout that AMMs were created with before `curve` and everything
+        // after it were added
+        #[derive(AnchorSerialize)]
+        struct TwapOracleV0 {
+            last_updated_slot: u64,
+            last_price: u128,
+            last_observation: u128,
+            aggregator: u128,
+            max_observation_change_per_update: u128,
+            initial_observation: u128,
+        }
+
+        #[derive(AnchorSerialize)]
+        struct AmmV0 {
+            bump: u8,
+            created_at_slot: u64,
+            lp_mint: Pubkey,
+            base_mint: Pubkey,
+            quote_mint: Pubkey,
+            base_mint_decimals: u8,
+            quote_mint_decimals: u8,
+            base_amount: u64,
+            quote_amount: u64,
+            oracle: TwapOracleV0,
+            seq_num: u64,
+        }
+
+        let old_amm = AmmV0 {
+            bump: 254,
+            created_at_slot: 100,
+            lp_mint: Pubkey::new_unique(),
+            base_mint: Pubkey::new_unique(),
+            quote_mint: Pubkey::new_unique(),
+            base_mint_decimals: 9,
+            quote_mint_decimals: 6,
+            base_amount: 1_000,
+            quote_amount: 2_000,
+            oracle: TwapOracleV0 {
+                last_updated_slot: 500,
+                last_price: 3,
+                last_observation: 4,
+                aggregator: 5,
+                max_observation_change_per_update: 6,
+                initial_observation: 7,
+            },
+            seq_num: 8,
+        };
+
+        let mut data = Amm::DISCRIMINATOR.to_vec();
+        old_amm.serialize(&mut data).unwrap();
+        // what `migrate_amm` leaves the account as
+        data.resize(8 + std::mem::size_of::<Amm>(), 0);
+
+        let amm = Amm::try_deserialize(&mut &data[..]).unwrap();
+
+        assert_eq!(amm.lp_mint, old_amm.lp_mint);
+        assert_eq!(amm.quote_amount, old_amm.quote_amount);
+        assert_eq!(amm.oracle.aggregator, old_amm.oracle.aggregator);
+        assert_eq!(amm.oracle.initial_observation, old_amm.oracle.initial_observation);
+        assert_eq!(amm.seq_num, old_amm.seq_num);
+        assert_eq!(amm.curve, AmmCurve::ConstantProduct);
+        assert_eq!(amm.cumulative_quote_fees_per_lp, 0);
+        assert_eq!(amm.quote_volume, 0);
+        assert_eq!(amm.quote_liquidity_aggregator, 0);
+        assert_eq!(amm.quote_liquidity_updated_slot, 0);
+    }
+}
+
+#[cfg(test)]
+mod amm_property_tests {
+    use crate::state::*;
+    use proptest::prelude::*;
+
+    /// Token amounts, biased towards the magnitudes that pools actually see.
+    fn amount() -> impl Strategy<Value = u64> {
+        prop_oneof![1..1_000u64, 1..1_000_000_000_000u64, 1..=u64::MAX]
+    }
+
+    fn swap_type() -> impl Strategy<Value = SwapType> {
+        prop_oneof![Just(SwapType::Buy), Just(SwapType::Sell)]
+    }
+
+    fn curve() -> impl Strategy<Value = AmmCurve> {
+        prop_oneof![
+            Just(AmmCurve::ConstantProduct),
+            (MIN_AMPLIFICATION..=MAX_AMPLIFICATION)
+                .prop_map(|amplification| AmmCurve::StableSwap { amplification }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn swap_never_decreases_invariant(
+            base_amount in amount(),
+            quote_amount in amount(),
+            input_amount in amount(),
+            swap_type in swap_type(),
+            curve in curve(),
+        ) {
+            let mut amm = Amm {
+                base_amount,
+                quote_amount,
+                curve,
+                ..Amm::default()
+            };
+
+            // errors are fine, panics are not
+            if let Ok(output_amount) = amm.swap(input_amount, swap_type) {
+                match curve {
+                    AmmCurve::ConstantProduct => {
+                        prop_assert!(amm.k() >= base_amount as u128 * quote_amount as u128);
+                    }
+                    AmmCurve::StableSwap { amplification } => {
+                        prop_assert!(
+                            compute_stable_swap_d(amm.base_amount, amm.quote_amount, amplification)?
+                                >= compute_stable_swap_d(base_amount, quote_amount, amplification)?
+                        );
+                    }
+                }
+
+                let output_reserve = match swap_type {
+                    SwapType::Buy => base_amount,
+                    SwapType::Sell => quote_amount,
+                };
+                prop_assert!(output_amount < output_reserve);
+            }
+        }
+
+        #[test]
+        fn add_then_remove_never_profits(
+            base_amount in amount(),
+            quote_amount in amount(),
+            lp_total_supply in amount(),
+            quote_to_add in amount(),
+        ) {
+            let mut amm = Amm {
+                base_amount,
+                quote_amount,
+                ..Amm::default()
+            };
+
+            let Ok((lp_tokens_minted, base_to_add)) =
+                amm.get_liquidity_to_add(quote_to_add, lp_total_supply)
+            else {
+                return Ok(());
+            };
+
+            let (Some(new_base_amount), Some(new_quote_amount), Some(new_lp_total_supply)) = (
+                amm.base_amount.checked_add(base_to_add),
+                amm.quote_amount.checked_add(quote_to_add),
+                lp_total_supply.checked_add(lp_tokens_minted),
+            ) else {
+                return Ok(());
+            };
+
+            amm.base_amount = new_base_amount;
+            amm.quote_amount = new_quote_amount;
+
+            let (base_withdrawable, quote_withdrawable) =
+                amm.get_base_and_quote_withdrawable(lp_tokens_minted, new_lp_total_supply)?;
+
+            prop_assert!(base_withdrawable <= base_to_add);
+            prop_assert!(quote_withdrawable <= quote_to_add);
+        }
+
+        #[test]
+        fn withdrawable_never_exceeds_reserves(
+            base_amount in 0..=u64::MAX,
+            quote_amount in 0..=u64::MAX,
+            lp_tokens in 0..=u64::MAX,
+            lp_total_supply in 0..=u64::MAX,
+        ) {
+            let amm = Amm {
+                base_amount,
+                quote_amount,
+                ..Amm::default()
+            };
+
+            if let Ok((base_withdrawable, quote_withdrawable)) =
+                amm.get_base_and_quote_withdrawable(lp_tokens, lp_total_supply)
+            {
+                prop_assert!(base_withdrawable <= base_amount);
+                prop_assert!(quote_withdrawable <= quote_amount);
+            }
+        }
+
+        #[test]
+        fn twap_observations_stay_between_price_and_last_observation(
+            base_amount in amount(),
+            quote_amount in amount(),
+            last_updated_slot in 0..=u64::MAX,
+            slots_passed in 0..=u64::MAX,
+            last_observation in 0..=MAX_PRICE,
+            max_observation_change_per_update in 0..=MAX_PRICE,
+            aggregator in 0..=u128::MAX,
+            curve in curve(),
+        ) {
+            let mut amm = Amm {
+                base_amount,
+                quote_amount,
+                oracle: TwapOracle {
+                    last_observation,
+                    aggregator,
+                    ..TwapOracle::new(last_updated_slot, 0, max_observation_change_per_update)
+                },
+                curve,
+                ..Amm::default()
+            };
+
+            let current_slot = last_updated_slot.saturating_add(slots_passed);
+
+            if let Ok(Some(observation)) = amm.update_twap(current_slot) {
+                let price = amm.oracle.last_price;
+
+                prop_assert!(observation >= price.min(last_observation));
+                prop_assert!(observation <= price.max(last_observation));
+                prop_assert!(
+                    observation.abs_diff(last_observation) <= max_observation_change_per_update
+                );
+                prop_assert_eq!(amm.oracle.last_observation, observation);
+                prop_assert_eq!(amm.oracle.last_updated_slot, current_slot);
+            }
+        }
+
+        #[test]
+        fn get_twap_never_panics(
+            created_at_slot in 0..=u64::MAX,
+            last_updated_slot in 0..=u64::MAX,
+            aggregator in 0..=u128::MAX,
+        ) {
+            let amm = Amm {
+                created_at_slot,
+                oracle: TwapOracle {
+                    last_updated_slot,
+                    aggregator,
+                    ..TwapOracle::default()
+                },
+                ..Amm::default()
+            };
+
+            if let Ok(twap) = amm.get_twap() {
+                prop_assert!(twap <= aggregator);
+            }
+        }
+    }
 }
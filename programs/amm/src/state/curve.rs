@@ -0,0 +1,274 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AmmError;
+use crate::PRICE_SCALE;
+
+#[allow(clippy::all)]
+mod uint {
+    uint::construct_uint! {
+        pub struct U256(4);
+    }
+}
+
+pub(crate) use self::uint::U256;
+
+pub const MIN_AMPLIFICATION: u64 = 1;
+pub const MAX_AMPLIFICATION: u64 = 10_000;
+
+/// Newton's method usually converges in fewer than 10 iterations, this is
+/// just a backstop.
+const MAX_ITERATIONS: usize = 256;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum AmmCurve {
+    /// The uniswap v1 invariant, x * y = k.
+    #[default]
+    ConstantProduct,
+    /// Curve's StableSwap invariant for two assets that should trade near 1:1,
+    /// such as pass and fail markets of stablecoin-denominated questions.
+    ///
+    /// As `amplification` goes to 0, this behaves like a constant-product curve.
+    /// As it goes to infinity, this behaves like a constant-sum curve. Prices
+    /// are raw quote units per raw base unit, so both mints need the same
+    /// decimals for 1:1 to mean the same thing to users.
+    StableSwap { amplification: u64 },
+}
+
+/// Computes the StableSwap invariant `D` for the given reserves. `D` is the
+/// total amount of tokens that the pool would hold if the reserves were
+/// balanced, so it has the same units as the reserves.
+///
+/// Derived from `get_D` in https://github.com/curvefi/curve-contract/blob/b0bbf77f8f93c9c5f4e415bce9cd71f0cdee960e/contracts/pool-templates/base/SwapTemplateBase.vy#L216-L241
+pub fn compute_stable_swap_d(amount_a: u64, amount_b: u64, amplification: u64) -> Result<u128> {
+    require!(amount_a != 0, AmmError::NoReserves);
+    require!(amount_b != 0, AmmError::NoReserves);
+
+    let amount_a = U256::from(amount_a);
+    let amount_b = U256::from(amount_b);
+    let sum = amount_a + amount_b;
+    let ann = U256::from(amplification) * 2u64;
+
+    let mut d = sum;
+    for _ in 0..MAX_ITERATIONS {
+        let d_p = checked_mul_div(d, d, amount_a * 2u64)?;
+        let d_p = checked_mul_div(d_p, d, amount_b * 2u64)?;
+
+        let d_prev = d;
+
+        let numerator = ann
+            .checked_mul(sum)
+            .and_then(|n| n.checked_add(d_p * 2u64))
+            .and_then(|n| n.checked_mul(d))
+            .ok_or(error!(AmmError::StableSwapCalculationError))?;
+        let denominator = (ann - 1u64)
+            .checked_mul(d)
+            .and_then(|n| n.checked_add(d_p * 3u64))
+            .ok_or(error!(AmmError::StableSwapCalculationError))?;
+
+        d = numerator / denominator;
+
+        if abs_diff(d, d_prev) <= U256::one() {
+            return to_u128(d);
+        }
+    }
+
+    err!(AmmError::StableSwapCalculationError)
+}
+
+/// Given the reserve of one side of the pool and the invariant `D`, computes
+/// the reserve of the other side of the pool.
+///
+/// Derived from `get_y` in https://github.com/curvefi/curve-contract/blob/b0bbf77f8f93c9c5f4e415bce9cd71f0cdee960e/contracts/pool-templates/base/SwapTemplateBase.vy#L381-L423
+pub fn compute_stable_swap_y(amount: u128, d: u128, amplification: u64) -> Result<u128> {
+    require!(amount != 0, AmmError::NoReserves);
+
+    let amount = U256::from(amount);
+    let d = U256::from(d);
+    let ann = U256::from(amplification) * 2u64;
+
+    let c = checked_mul_div(d, d, amount * 2u64)?;
+    let c = checked_mul_div(c, d, ann * 2u64)?;
+    let b = amount + d / ann;
+
+    let mut y = d;
+    for _ in 0..MAX_ITERATIONS {
+        let y_prev = y;
+
+        let numerator = y
+            .checked_mul(y)
+            .and_then(|n| n.checked_add(c))
+            .ok_or(error!(AmmError::StableSwapCalculationError))?;
+        let denominator = (y * 2u64 + b)
+            .checked_sub(d)
+            .filter(|denominator| !denominator.is_zero())
+            .ok_or(error!(AmmError::StableSwapCalculationError))?;
+
+        y = numerator / denominator;
+
+        if abs_diff(y, y_prev) <= U256::one() {
+            return to_u128(y);
+        }
+    }
+
+    err!(AmmError::StableSwapCalculationError)
+}
+
+/// How much of the output token a StableSwap pool pays out for `input_amount`
+/// of the input token. Fees should already have been taken out of `input_amount`.
+///
+/// Rounds down by an extra unit so that imprecision in Newton's method can
+/// only ever benefit the pool.
+pub fn get_stable_swap_output_amount(
+    input_reserve: u64,
+    output_reserve: u64,
+    input_amount: u64,
+    amplification: u64,
+) -> Result<u64> {
+    let d = compute_stable_swap_d(input_reserve, output_reserve, amplification)?;
+
+    let new_input_reserve = input_reserve as u128 + input_amount as u128;
+    let new_output_reserve = compute_stable_swap_y(new_input_reserve, d, amplification)?;
+
+    let output_amount = (output_reserve as u128)
+        .saturating_sub(new_output_reserve)
+        .saturating_sub(1);
+
+    // can't underflow or overflow since the subtractions saturate
+    Ok(output_amount as u64)
+}
+
+/// The marginal price of a StableSwap pool, in quote units per base unit
+/// scaled by `PRICE_SCALE`.
+///
+/// With the invariant F(x, y) = Ann(x + y) + D - Ann * D - D^3 / 4xy, the
+/// price of x in terms of y is F_x / F_y, which simplifies to:
+///
+/// y * (4 * Ann * x^2 * y + D^3) / (x * (4 * Ann * x * y^2 + D^3))
+pub fn get_stable_swap_price(
+    base_reserve: u64,
+    quote_reserve: u64,
+    amplification: u64,
+) -> Result<u128> {
+    let d = U256::from(compute_stable_swap_d(
+        base_reserve,
+        quote_reserve,
+        amplification,
+    )?);
+
+    let x = U256::from(base_reserve);
+    let y = U256::from(quote_reserve);
+    let ann = U256::from(amplification) * 2u64;
+
+    let d_cubed = d * d * d;
+
+    let numerator = ann * 4u64 * x * x * y + d_cubed;
+    let denominator = ann * 4u64 * x * y * y + d_cubed;
+
+    // (numerator / denominator) is roughly x / y, so scaling it up first
+    // keeps most of the precision
+    let scaled_ratio = checked_mul_div(numerator, U256::from(PRICE_SCALE), denominator)?;
+
+    to_u128(checked_mul_div(scaled_ratio, y, x)?)
+}
+
+fn checked_mul_div(a: U256, b: U256, c: U256) -> Result<U256> {
+    a.checked_mul(b)
+        .and_then(|product| product.checked_div(c))
+        .ok_or(error!(AmmError::StableSwapCalculationError))
+}
+
+fn abs_diff(a: U256, b: U256) -> U256 {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+fn to_u128(value: U256) -> Result<u128> {
+    require!(value <= U256::from(u128::MAX), AmmError::CastingOverflow);
+
+    Ok(value.as_u128())
+}
+
+#[cfg(test)]
+mod stable_swap_tests {
+    use super::*;
+
+    const AMP: u64 = 100;
+
+    #[test]
+    pub fn balanced_pool_d_is_sum() {
+        assert_eq!(compute_stable_swap_d(1_000, 1_000, AMP).unwrap(), 2_000);
+        assert_eq!(
+            compute_stable_swap_d(u64::MAX, u64::MAX, MAX_AMPLIFICATION).unwrap(),
+            u64::MAX as u128 * 2
+        );
+    }
+
+    #[test]
+    pub fn y_inverts_d() {
+        let d = compute_stable_swap_d(1_000_000, 3_000_000, AMP).unwrap();
+        let y = compute_stable_swap_y(1_000_000, d, AMP).unwrap();
+
+        assert!(y.abs_diff(3_000_000) <= 1);
+    }
+
+    #[test]
+    pub fn balanced_pool_price_is_one() {
+        let price = get_stable_swap_price(1_000_000_000, 1_000_000_000, AMP).unwrap();
+
+        assert_eq!(price, PRICE_SCALE);
+    }
+
+    #[test]
+    pub fn better_execution_than_constant_product() {
+        let reserve = 1_000_000_000;
+        let input = 100_000_000;
+
+        let stable_output = get_stable_swap_output_amount(reserve, reserve, input, AMP).unwrap();
+        let constant_product_output =
+            (input as u128 * reserve as u128 / (reserve as u128 + input as u128)) as u64;
+
+        assert!(stable_output > constant_product_output);
+        // but you never get more than you put in
+        assert!(stable_output < input);
+    }
+
+    #[test]
+    pub fn swaps_never_decrease_d() {
+        let (mut base, mut quote) = (5_000_000_000u64, 2_000_000_000u64);
+
+        for input in [1, 10, 1_000, 1_000_000, 3_000_000_000] {
+            let d = compute_stable_swap_d(base, quote, AMP).unwrap();
+
+            let output = get_stable_swap_output_amount(base, quote, input, AMP).unwrap();
+            base += input;
+            quote -= output;
+
+            assert!(compute_stable_swap_d(base, quote, AMP).unwrap() >= d);
+        }
+    }
+
+    #[test]
+    pub fn imbalanced_pool_prices() {
+        // more base than quote, so base should be cheaper than quote
+        let price = get_stable_swap_price(3_000_000_000, 1_000_000_000, AMP).unwrap();
+        assert!(price < PRICE_SCALE);
+
+        let price = get_stable_swap_price(1_000_000_000, 3_000_000_000, AMP).unwrap();
+        assert!(price > PRICE_SCALE);
+    }
+
+    #[test]
+    pub fn empty_reserves() {
+        assert_eq!(
+            compute_stable_swap_d(0, 1, AMP).unwrap_err(),
+            AmmError::NoReserves.into()
+        );
+        assert_eq!(
+            compute_stable_swap_d(1, 0, AMP).unwrap_err(),
+            AmmError::NoReserves.into()
+        );
+    }
+}
@@ -1,11 +1,15 @@
 pub use amm::*;
+pub use curve::*;
 
 pub mod amm;
+pub mod curve;
 
 pub const TEN_SECONDS_IN_SLOTS: u64 = 25;
 pub const ONE_MINUTE_IN_SLOTS: u64 = TEN_SECONDS_IN_SLOTS * 6;
 pub const PRICE_SCALE: u128 = 1_000_000_000_000;
 pub const MAX_PRICE: u128 = u64::MAX as u128 * PRICE_SCALE;
+/// Per-LP fee accumulators are stored as Q64.64 fixed-point numbers.
+pub const FEES_PER_LP_SCALE_BITS: u32 = 64;
 
 pub const AMM_SEED_PREFIX: &[u8] = b"amm__";
 pub const AMM_LP_MINT_SEED_PREFIX: &[u8] = b"amm_lp_mint";
@@ -24,6 +24,94 @@ pub enum AutocratError {
     ProposalNotPassed,
     #[msg("The proposer has fewer pass or fail LP tokens than they requested to lock")]
     InsufficientLpTokenBalance,
-    #[msg("The LP tokens passed in have less liquidity than the DAO's `min_quote_futarchic_liquidity` or `min_base_futachic_liquidity`")]
+    #[msg("The LP tokens passed in have less liquidity than the proposal's `min_quote_futarchic_liquidity` or `min_base_futachic_liquidity`")]
     InsufficientLpTokenLock,
+    #[msg("This `ProposalInstructions` account has already been used by a proposal and can't be changed")]
+    ProposalInstructionsFrozen,
+    #[msg("Proposals can only be cancelled within `PROPOSAL_CANCELLATION_WINDOW_SLOTS` of being initialized")]
+    CancellationWindowElapsed,
+    #[msg("This proposal can't be executed until `execution_delay_slots` after it was finalized")]
+    ExecutionDelayNotElapsed,
+    #[msg("The signer isn't this DAO's `veto_authority`")]
+    InvalidVetoAuthority,
+    #[msg("This proposal can't be executed because its `execution_window_slots` have passed")]
+    ExecutionWindowElapsed,
+    #[msg("This proposal can still be executed, so it can't be marked as expired")]
+    ExecutionWindowNotElapsed,
+    #[msg("Multi-option proposals need between 2 and `MAX_PROPOSAL_OPTIONS` options, one for each of the question's outcomes")]
+    InvalidNumOptions,
+    #[msg("The remaining accounts don't match the proposal's options")]
+    InvalidOptionAccounts,
+    #[msg("This DAO already has `MAX_PROPOSAL_CATEGORIES` proposal categories")]
+    TooManyProposalCategories,
+    #[msg("A proposal category's `data_prefix_len` can be at most 8")]
+    InvalidDataPrefixLen,
+    #[msg("A proposal bond has to be in the DAO's token or USDC")]
+    InvalidBondMint,
+    #[msg("This DAO has a proposal bond, so the bond accounts need to be passed in")]
+    MissingBondAccounts,
+    #[msg("This proposal has no bond, or its bond has already been settled")]
+    NoBondToSettle,
+    #[msg("A proposal's bond can only be settled once the proposal is no longer pending")]
+    ProposalNotFinalized,
+    #[msg("The DAO or this proposer already has the maximum number of active proposals")]
+    TooManyActiveProposals,
+    #[msg("This proposal setup has already created both markets")]
+    ProposalSetupComplete,
+    #[msg("A market's mints don't match the conditional tokens of the proposal's vaults")]
+    InvalidSetupMint,
+    #[msg("A proposal's question must use the proposal's address as its ID and have the canonical number of outcomes")]
+    NonCanonicalQuestion,
+    #[msg("This DAO has a spot market, which needs to be passed in")]
+    MissingSpotAmm,
+    #[msg("This isn't the DAO's spot market")]
+    InvalidSpotAmm,
+    #[msg("This DAO doesn't seed proposal markets from its treasury")]
+    TreasuryLiquidityDisabled,
+    #[msg("The treasury has already seeded this market")]
+    MarketAlreadySeeded,
+    #[msg("This market isn't one of the proposal's")]
+    InvalidSeedMarket,
+    #[msg("The market's price is too far from its TWAP oracle's last observation to seed it")]
+    MarketPriceOutOfRange,
+    #[msg("The treasury has no liquidity in this market to settle")]
+    NoTreasuryPositionToSettle,
+    #[msg("This proposal is already in the current layout")]
+    ProposalAlreadyMigrated,
+    #[msg("Thresholds in basis points can't be more than 10,000")]
+    InvalidThresholdBps,
+    #[msg("Proposals need to last at least a day's worth of slots")]
+    InvalidSlotsPerProposal,
+    #[msg("The TWAP's initial observation can't be 0")]
+    InvalidTwapInitialObservation,
+    #[msg("The TWAP's max observation change per update can't be 0, or the TWAP could never move")]
+    InvalidMaxObservationChangePerUpdate,
+    #[msg("The execution window can't be 0, or passed proposals could never be executed")]
+    InvalidExecutionWindow,
+    #[msg("A cap on active proposals can't be 0, or no proposals could be created")]
+    InvalidMaxActiveProposals,
+    #[msg("The treasury needs to add a non-zero amount of both tokens")]
+    InvalidTreasuryLiquidity,
+    #[msg("A spending limit needs a non-zero window")]
+    InvalidSpendingLimit,
+    #[msg("This DAO already has the maximum number of spending limits")]
+    TooManySpendingLimits,
+    #[msg(
+        "This instruction would send more out of the treasury than the DAO's spending limits allow"
+    )]
+    SpendingLimitExceeded,
+    #[msg("Proposals can't change the owner or delegate of the treasury's limited token accounts")]
+    TreasuryTokenAccountAuthorityChanged,
+    #[msg("This DAO is already in the current layout")]
+    DaoAlreadyMigrated,
+    #[msg("Multi-option proposals don't support proposal bonds or treasury liquidity")]
+    UnsupportedByMultiOptionProposals,
+    #[msg("A proposal can only be initialized once its setup has created both markets")]
+    ProposalSetupIncomplete,
+    #[msg("An absolute threshold can be at most the DAO's initial TWAP observation")]
+    InvalidAbsoluteThreshold,
+    #[msg("RelativeAboveSpot needs the DAO to have a spot market")]
+    SpotAmmRequired,
+    #[msg("Only proposals that have already been finalized can be migrated")]
+    CannotMigratePendingProposal,
 }
@@ -0,0 +1,221 @@
+use super::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CommonFields {
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+impl CommonFields {
+    pub fn new(clock: &Clock) -> Self {
+        Self {
+            slot: clock.slot,
+            unix_timestamp: clock.unix_timestamp,
+        }
+    }
+}
+
+/// The parts of a DAO's config that decide how its proposals are traded and
+/// finalized.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DaoConfig {
+    pub pass_threshold_bps: u16,
+    pub slots_per_proposal: u64,
+    pub twap_initial_observation: u128,
+    pub twap_max_observation_change_per_update: u128,
+    pub min_quote_futarchic_liquidity: u64,
+    pub min_base_futarchic_liquidity: u64,
+    pub min_average_quote_liquidity: u64,
+    pub execution_delay_slots: u64,
+    pub veto_authority: Option<Pubkey>,
+    pub execution_window_slots: u64,
+    pub decision_rule: DecisionRule,
+    pub proposal_bond: Option<ProposalBond>,
+    pub max_active_proposals: Option<u32>,
+    pub max_active_proposals_per_proposer: Option<u32>,
+    pub spot_amm: Option<Pubkey>,
+    pub treasury_liquidity: Option<TreasuryLiquidity>,
+}
+
+impl DaoConfig {
+    pub fn new(dao: &Dao) -> Self {
+        Self {
+            pass_threshold_bps: dao.pass_threshold_bps,
+            slots_per_proposal: dao.slots_per_proposal,
+            twap_initial_observation: dao.twap_initial_observation,
+            twap_max_observation_change_per_update: dao.twap_max_observation_change_per_update,
+            min_quote_futarchic_liquidity: dao.min_quote_futarchic_liquidity,
+            min_base_futarchic_liquidity: dao.min_base_futarchic_liquidity,
+            min_average_quote_liquidity: dao.min_average_quote_liquidity,
+            execution_delay_slots: dao.execution_delay_slots,
+            veto_authority: dao.veto_authority,
+            execution_window_slots: dao.execution_window_slots,
+            decision_rule: dao.decision_rule,
+            proposal_bond: dao.proposal_bond,
+            max_active_proposals: dao.max_active_proposals,
+            max_active_proposals_per_proposer: dao.max_active_proposals_per_proposer,
+            spot_amm: dao.spot_amm,
+            treasury_liquidity: dao.treasury_liquidity,
+        }
+    }
+}
+
+#[event]
+pub struct InitializeDaoEvent {
+    pub common: CommonFields,
+    pub dao: Pubkey,
+    pub token_mint: Pubkey,
+    pub usdc_mint: Pubkey,
+    pub treasury: Pubkey,
+    pub config: DaoConfig,
+}
+
+#[event]
+pub struct UpdateDaoEvent {
+    pub common: CommonFields,
+    pub dao: Pubkey,
+    /// The DAO's config after the update.
+    pub config: DaoConfig,
+}
+
+#[event]
+pub struct InitializeProposalEvent {
+    pub common: CommonFields,
+    pub proposal: Pubkey,
+    pub dao: Pubkey,
+    pub proposer: Pubkey,
+    pub number: u32,
+    pub description_url: String,
+    pub instructions: Pubkey,
+    pub question: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub pass_amm: Pubkey,
+    pub fail_amm: Pubkey,
+    pub pass_lp_tokens_locked: u64,
+    pub fail_lp_tokens_locked: u64,
+    pub pass_threshold_bps: u16,
+    pub slots_per_proposal: u64,
+    pub nonce: u64,
+}
+
+#[event]
+pub struct FinalizeProposalEvent {
+    pub common: CommonFields,
+    pub proposal: Pubkey,
+    pub dao: Pubkey,
+    pub pass_market_twap: u128,
+    pub fail_market_twap: u128,
+    /// The proposal's threshold, which the DAO's decision rule applies to the
+    /// TWAPs.
+    pub pass_threshold_bps: u16,
+    pub decision_rule: DecisionRule,
+    pub spot_twap: Option<u128>,
+    pub markets_had_liquidity: bool,
+    /// Either `Passed` or `Failed`.
+    pub state: ProposalState,
+}
+
+#[event]
+pub struct InitializeMultiOptionProposalEvent {
+    pub common: CommonFields,
+    pub proposal: Pubkey,
+    pub dao: Pubkey,
+    pub proposer: Pubkey,
+    pub number: u32,
+    pub description_url: String,
+    pub question: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    /// In outcome order, starting with the status quo.
+    pub options: Vec<ProposalOption>,
+    pub pass_threshold_bps: u16,
+    pub slots_per_proposal: u64,
+    pub nonce: u64,
+}
+
+#[event]
+pub struct FinalizeMultiOptionProposalEvent {
+    pub common: CommonFields,
+    pub proposal: Pubkey,
+    pub dao: Pubkey,
+    /// In the same order as the proposal's options.
+    pub option_twaps: Vec<u128>,
+    pub pass_threshold_bps: u16,
+    pub decision_rule: DecisionRule,
+    pub spot_twap: Option<u128>,
+    pub markets_had_liquidity: bool,
+    pub winning_option: u8,
+    /// Either `Passed` or `Failed`.
+    pub state: ProposalState,
+}
+
+#[event]
+pub struct SeedProposalMarketEvent {
+    pub common: CommonFields,
+    pub proposal: Pubkey,
+    pub dao: Pubkey,
+    pub amm: Pubkey,
+    /// The LP tokens that the treasury got for its liquidity.
+    pub lp_tokens: u64,
+}
+
+#[event]
+pub struct SettleTreasuryPositionsEvent {
+    pub common: CommonFields,
+    pub proposal: Pubkey,
+    pub dao: Pubkey,
+    pub amm: Pubkey,
+    /// The treasury's LP tokens that were burned to remove its liquidity.
+    pub lp_tokens_burned: u64,
+}
+
+#[event]
+pub struct SettleProposalBondEvent {
+    pub common: CommonFields,
+    pub proposal: Pubkey,
+    pub dao: Pubkey,
+    pub proposer: Pubkey,
+    pub bond_mint: Pubkey,
+    pub amount: u64,
+    /// Whether the bond went back to the proposer rather than to the treasury.
+    pub refunded: bool,
+}
+
+/// Emitted for both regular and multi-option proposals, as are the cancel,
+/// veto and expire events below.
+#[event]
+pub struct ExecuteProposalEvent {
+    pub common: CommonFields,
+    pub proposal: Pubkey,
+    pub dao: Pubkey,
+    /// The index of the instruction that was just executed, if there was one.
+    pub instruction_index: Option<u16>,
+    /// `Executed` once every instruction has been executed.
+    pub state: ProposalState,
+}
+
+#[event]
+pub struct CancelProposalEvent {
+    pub common: CommonFields,
+    pub proposal: Pubkey,
+    pub dao: Pubkey,
+    pub proposer: Pubkey,
+}
+
+#[event]
+pub struct VetoProposalEvent {
+    pub common: CommonFields,
+    pub proposal: Pubkey,
+    pub dao: Pubkey,
+    pub veto_authority: Pubkey,
+}
+
+#[event]
+pub struct ExpireProposalEvent {
+    pub common: CommonFields,
+    pub proposal: Pubkey,
+    pub dao: Pubkey,
+    /// How many of the proposal's instructions were executed before it expired.
+    pub num_instructions_executed: u16,
+}
@@ -0,0 +1,50 @@
+use super::*;
+
+#[derive(Accounts)]
+pub struct AddProposalCategory<'info> {
+    #[account(mut, has_one = treasury)]
+    pub dao: Account<'info, Dao>,
+    pub treasury: Signer<'info>,
+}
+
+impl AddProposalCategory<'_> {
+    pub fn validate(&self, category: &ProposalCategory) -> Result<()> {
+        require_gte!(
+            8,
+            category.data_prefix_len,
+            AutocratError::InvalidDataPrefixLen
+        );
+
+        category.requirements.validate()?;
+
+        Ok(())
+    }
+
+    /// Adds a proposal category, or replaces the category that matches the same
+    /// instructions.
+    pub fn handle(ctx: Context<Self>, category: ProposalCategory) -> Result<()> {
+        let dao = &mut ctx.accounts.dao;
+
+        let existing_category = dao.proposal_categories.iter_mut().find(|existing| {
+            existing.program_id == category.program_id
+                && existing.data_prefix_len == category.data_prefix_len
+                && existing.data_prefix[..existing.data_prefix_len as usize]
+                    == category.data_prefix[..category.data_prefix_len as usize]
+        });
+
+        match existing_category {
+            Some(existing_category) => *existing_category = category,
+            None => {
+                require_gt!(
+                    MAX_PROPOSAL_CATEGORIES,
+                    dao.proposal_categories.len(),
+                    AutocratError::TooManyProposalCategories
+                );
+
+                dao.proposal_categories.push(category);
+            }
+        }
+
+        Ok(())
+    }
+}
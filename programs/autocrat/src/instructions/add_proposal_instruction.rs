@@ -0,0 +1,14 @@
+use super::*;
+
+#[derive(Accounts)]
+pub struct AddProposalInstruction<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut, has_one = authority)]
+    pub proposal_instructions: Box<Account<'info, ProposalInstructions>>,
+}
+
+impl AddProposalInstruction<'_> {
+    pub fn handle(ctx: Context<Self>, instruction: ProposalInstruction) -> Result<()> {
+        ctx.accounts.proposal_instructions.push(instruction)
+    }
+}
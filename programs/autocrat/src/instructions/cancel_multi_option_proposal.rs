@@ -0,0 +1,106 @@
+use super::*;
+
+/// Every option's market is passed in through the remaining accounts, the same
+/// way as for `finalize_multi_option_proposal`.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelMultiOptionProposal<'info> {
+    #[account(mut,
+        has_one = question,
+        has_one = dao,
+        has_one = proposer,
+    )]
+    pub proposal: Box<Account<'info, MultiOptionProposal>>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    #[account(mut, has_one = treasury)]
+    pub dao: Box<Account<'info, Dao>>,
+    /// Created here for proposals that were made before proposer records
+    /// existed.
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        space = 8 + std::mem::size_of::<ProposerRecord>(),
+        seeds = [b"proposer_record", dao.key().as_ref(), proposal.proposer.as_ref()],
+        bump,
+    )]
+    pub proposer_record: Box<Account<'info, ProposerRecord>>,
+    #[account(mut)]
+    pub question: Account<'info, Question>,
+    /// CHECK: it's okay
+    pub treasury: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub vault_program: Program<'info, ConditionalVaultProgram>,
+    /// CHECK: checked by vault program
+    pub vault_event_authority: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info, 'c: 'info> CancelMultiOptionProposal<'info> {
+    pub fn validate(&self) -> Result<()> {
+        self.proposal.validate_cancel(Clock::get()?.slot)
+    }
+
+    /// Same as `cancel_proposal`, except that every option pays out equally.
+    pub fn handle(ctx: Context<'_, '_, 'c, 'info, Self>) -> Result<()> {
+        let CancelMultiOptionProposal {
+            proposal,
+            proposer: _,
+            dao,
+            proposer_record,
+            question,
+            treasury,
+            token_program,
+            vault_program,
+            vault_event_authority,
+            system_program: _,
+            event_authority: _,
+            program: _,
+        } = ctx.accounts;
+
+        let proposer_key = proposal.proposer;
+        let nonce = proposal.nonce;
+        let proposal_seeds = &[
+            b"multi_option_proposal",
+            proposer_key.as_ref(),
+            &nonce.to_le_bytes(),
+            &[proposal.pda_bump],
+        ];
+        let proposal_signer = &[&proposal_seeds[..]];
+
+        let dao_key = dao.key();
+        let treasury_seeds = &[dao_key.as_ref(), &[dao.treasury_pda_bump]];
+        let treasury_signer = &[&treasury_seeds[..]];
+
+        unlock_option_lp_tokens(
+            proposal,
+            ctx.remaining_accounts,
+            &treasury.to_account_info(),
+            treasury_signer,
+            token_program,
+        )?;
+
+        proposal.state = ProposalState::Cancelled;
+        proposer_record.initialize_if_new(dao.key(), proposal.proposer, ctx.bumps.proposer_record);
+        proposer_record.close_proposal(dao);
+
+        resolve_proposal_question(
+            question,
+            proposal.to_account_info(),
+            proposal_signer,
+            vault_program,
+            vault_event_authority,
+            vec![1; proposal.options.len()],
+        )?;
+
+        let clock = Clock::get()?;
+        emit_cpi!(CancelProposalEvent {
+            common: CommonFields::new(&clock),
+            proposal: proposal.key(),
+            dao: proposal.dao,
+            proposer: proposal.proposer,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use super::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    #[account(mut,
+        has_one = question,
+        has_one = pass_amm,
+        has_one = fail_amm,
+        has_one = dao,
+        has_one = proposer,
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub pass_amm: Account<'info, Amm>,
+    pub fail_amm: Account<'info, Amm>,
+    #[account(mut, has_one = treasury)]
+    pub dao: Box<Account<'info, Dao>>,
+    /// Created here for proposals that were made before proposer records
+    /// existed.
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        space = 8 + std::mem::size_of::<ProposerRecord>(),
+        seeds = [b"proposer_record", dao.key().as_ref(), proposal.proposer.as_ref()],
+        bump,
+    )]
+    pub proposer_record: Box<Account<'info, ProposerRecord>>,
+    #[account(mut)]
+    pub question: Account<'info, Question>,
+    /// CHECK: it's okay
+    pub treasury: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = pass_amm.lp_mint,
+        associated_token::authority = proposer,
+    )]
+    pub pass_lp_user_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = fail_amm.lp_mint,
+        associated_token::authority = proposer,
+    )]
+    pub fail_lp_user_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = pass_amm.lp_mint,
+        associated_token::authority = dao.treasury,
+    )]
+    pub pass_lp_vault_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = fail_amm.lp_mint,
+        associated_token::authority = dao.treasury,
+    )]
+    pub fail_lp_vault_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub vault_program: Program<'info, ConditionalVaultProgram>,
+    /// CHECK: checked by vault program
+    pub vault_event_authority: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl CancelProposal<'_> {
+    pub fn validate(&self) -> Result<()> {
+        self.proposal.validate_cancel(Clock::get()?.slot)
+    }
+
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let CancelProposal {
+            proposal,
+            proposer: _,
+            pass_amm,
+            fail_amm,
+            dao,
+            proposer_record,
+            question,
+            treasury,
+            pass_lp_user_account,
+            fail_lp_user_account,
+            pass_lp_vault_account,
+            fail_lp_vault_account,
+            vault_program,
+            token_program,
+            vault_event_authority,
+            system_program: _,
+            event_authority: _,
+            program: _,
+        } = ctx.accounts;
+
+        let proposer_key = proposal.proposer;
+        let nonce = proposal.nonce;
+        let proposal_seeds = &[
+            b"proposal",
+            proposer_key.as_ref(),
+            &nonce.to_le_bytes(),
+            &[proposal.pda_bump],
+        ];
+        let proposal_signer = &[&proposal_seeds[..]];
+
+        let dao_key = dao.key();
+        let treasury_seeds = &[dao_key.as_ref(), &[dao.treasury_pda_bump]];
+        let treasury_signer = &[&treasury_seeds[..]];
+
+        for (lp_tokens_locked, lp_vault_account, lp_user_account) in [
+            (
+                proposal.pass_lp_tokens_locked,
+                pass_lp_vault_account,
+                pass_lp_user_account,
+            ),
+            (
+                proposal.fail_lp_tokens_locked,
+                fail_lp_vault_account,
+                fail_lp_user_account,
+            ),
+        ] {
+            unlock_lp_tokens(
+                lp_tokens_locked,
+                lp_vault_account,
+                lp_user_account,
+                &treasury.to_account_info(),
+                treasury_signer,
+                token_program,
+            )?;
+        }
+
+        proposal.state = ProposalState::Cancelled;
+        proposal.record_quote_volumes(pass_amm, fail_amm);
+        proposer_record.initialize_if_new(dao.key(), proposal.proposer, ctx.bumps.proposer_record);
+        proposer_record.close_proposal(dao);
+
+        // the markets never got to say anything, so split the conditional
+        // tokens' value evenly and let traders unwind at roughly what they paid
+        resolve_proposal_question(
+            question,
+            proposal.to_account_info(),
+            proposal_signer,
+            vault_program,
+            vault_event_authority,
+            vec![1, 1],
+        )?;
+
+        let clock = Clock::get()?;
+        emit_cpi!(CancelProposalEvent {
+            common: CommonFields::new(&clock),
+            proposal: proposal.key(),
+            dao: proposal.dao,
+            proposer: proposal.proposer,
+        });
+
+        Ok(())
+    }
+}
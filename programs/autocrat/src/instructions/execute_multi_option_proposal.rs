@@ -0,0 +1,46 @@
+use super::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteMultiOptionProposal<'info> {
+    #[account(mut, has_one = dao)]
+    pub proposal: Box<Account<'info, MultiOptionProposal>>,
+    #[account(
+        constraint = proposal.winning_instructions() == Some(instructions.key()),
+    )]
+    pub instructions: Box<Account<'info, ProposalInstructions>>,
+    #[account(mut)]
+    pub dao: Box<Account<'info, Dao>>,
+}
+
+impl<'info, 'c: 'info> ExecuteMultiOptionProposal<'info> {
+    pub fn validate(&self) -> Result<()> {
+        self.proposal.validate_execute(Clock::get()?.slot)
+    }
+
+    /// Executes the next of the winning option's instructions, the same way as
+    /// `execute_proposal`.
+    pub fn handle(ctx: Context<'_, '_, 'c, 'info, Self>) -> Result<()> {
+        let ExecuteMultiOptionProposal {
+            proposal,
+            instructions,
+            dao,
+            event_authority: _,
+            program: _,
+        } = ctx.accounts;
+
+        let instruction_index =
+            execute_next_instruction(&mut ***proposal, instructions, ctx.remaining_accounts, dao)?;
+
+        let clock = Clock::get()?;
+        emit_cpi!(ExecuteProposalEvent {
+            common: CommonFields::new(&clock),
+            proposal: proposal.key(),
+            dao: proposal.dao,
+            instruction_index,
+            state: proposal.state,
+        });
+
+        Ok(())
+    }
+}
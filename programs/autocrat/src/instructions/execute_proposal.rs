@@ -1,40 +1,343 @@
 use super::*;
 
+use anchor_spl::token_interface::TokenAccount as InterfaceTokenAccount;
+
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ExecuteProposal<'info> {
-    #[account(mut, has_one = dao)]
+    #[account(mut, has_one = dao, has_one = instructions)]
     pub proposal: Account<'info, Proposal>,
+    pub instructions: Box<Account<'info, ProposalInstructions>>,
+    #[account(mut)]
     pub dao: Box<Account<'info, Dao>>,
 }
 
-impl ExecuteProposal<'_> {
-    pub fn validate(&self) -> Result<()> {
-        require!(
-            self.proposal.state == ProposalState::Passed,
-            AutocratError::ProposalNotPassed
-        );
+/// The state of one of the treasury's token accounts for a limited mint,
+/// before a proposal's instruction is executed.
+struct LimitedTreasuryAccount {
+    index: usize,
+    spending_limit: SpendingLimit,
+    amount: u64,
+    delegate: Option<Pubkey>,
+}
 
-        Ok(())
-    }
+impl LimitedTreasuryAccount {
+    /// Finds the treasury's token accounts for mints in `spending_limits`.
+    /// The instruction can only touch token accounts that are passed in, so
+    /// these are the only balances that it can lower.
+    fn collect<'info>(
+        accounts: &'info [AccountInfo<'info>],
+        treasury: Pubkey,
+        spending_limits: &[SpendingLimit],
+    ) -> Vec<Self> {
+        let mut limited_accounts: Vec<Self> = vec![];
 
-    pub fn handle(ctx: Context<Self>) -> Result<()> {
-        let ExecuteProposal { proposal, dao } = ctx.accounts;
+        for (index, account) in accounts.iter().enumerate() {
+            if limited_accounts
+                .iter()
+                .any(|limited| accounts[limited.index].key == account.key)
+            {
+                continue;
+            }
 
-        proposal.state = ProposalState::Executed;
+            let Ok(token_account) = InterfaceAccount::<InterfaceTokenAccount>::try_from(account)
+            else {
+                continue;
+            };
 
-        let dao_key = dao.key();
-        let treasury_seeds = &[dao_key.as_ref(), &[dao.treasury_pda_bump]];
-        let signer = &[&treasury_seeds[..]];
+            let spending_limit = spending_limits
+                .iter()
+                .find(|spending_limit| spending_limit.mint == token_account.mint);
 
-        let mut svm_instruction: Instruction = proposal.instruction.borrow().into();
-        for acc in svm_instruction.accounts.iter_mut() {
-            if acc.pubkey == dao.treasury.key() {
-                acc.is_signer = true;
+            if let Some(spending_limit) = spending_limit {
+                if token_account.owner == treasury {
+                    limited_accounts.push(Self {
+                        index,
+                        spending_limit: *spending_limit,
+                        amount: token_account.amount,
+                        delegate: token_account.delegate.into(),
+                    });
+                }
             }
         }
 
-        solana_program::program::invoke_signed(&svm_instruction, ctx.remaining_accounts, signer)?;
+        limited_accounts
+    }
+
+    /// How much the account's balance went down by.
+    fn outflow<'info>(
+        &self,
+        accounts: &'info [AccountInfo<'info>],
+        treasury: Pubkey,
+    ) -> Result<u64> {
+        // closed accounts can't be deserialized, and they gave up their whole
+        // balance
+        let amount_after =
+            match InterfaceAccount::<InterfaceTokenAccount>::try_from(&accounts[self.index]) {
+                Ok(token_account) => {
+                    // otherwise, the new owner or delegate could move the tokens
+                    // out without going through autocrat
+                    require!(
+                        token_account.owner == treasury
+                            && Option::<Pubkey>::from(token_account.delegate) == self.delegate,
+                        AutocratError::TreasuryTokenAccountAuthorityChanged
+                    );
+
+                    token_account.amount
+                }
+                Err(_) => 0,
+            };
+
+        Ok(self.amount.saturating_sub(amount_after))
+    }
+}
+
+/// Executes one of a proposal's instructions with the treasury as a signer,
+/// and counts whatever it sends out of the treasury against the proposal's
+/// snapshot of the DAO's spending limits. Shared by all kinds of proposals.
+pub fn execute_proposal_instruction<'info>(
+    instruction: &ProposalInstruction,
+    accounts: &'info [AccountInfo<'info>],
+    dao: &mut Account<'info, Dao>,
+    spending_limits: &[SpendingLimit],
+    treasury_outflows: &mut Vec<TreasuryOutflow>,
+) -> Result<()> {
+    let dao_key = dao.key();
+    let treasury_seeds = &[dao_key.as_ref(), &[dao.treasury_pda_bump]];
+    let signer = &[&treasury_seeds[..]];
+
+    let mut svm_instruction: Instruction = instruction.into();
+    for acc in svm_instruction.accounts.iter_mut() {
+        if acc.pubkey == dao.treasury.key() {
+            acc.is_signer = true;
+        }
+    }
+
+    let limited_accounts = LimitedTreasuryAccount::collect(accounts, dao.treasury, spending_limits);
+
+    solana_program::program::invoke_signed(&svm_instruction, accounts, signer)?;
+
+    // the instruction may have been `update_dao` or another instruction that
+    // changed the DAO, which we don't want to overwrite
+    dao.reload()?;
+
+    let slot = Clock::get()?.slot;
+
+    for limited in limited_accounts {
+        let outflow = limited.outflow(accounts, dao.treasury)?;
+
+        if outflow > 0 {
+            dao.record_treasury_outflow(&limited.spending_limit, treasury_outflows, outflow, slot)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Executes the next of a passed proposal's instructions, if it has any left,
+/// and returns the index of the instruction that was executed. Shared by all
+/// kinds of proposals.
+pub fn execute_next_instruction<'info>(
+    proposal: &mut impl ProposalLifecycle,
+    instructions: &ProposalInstructions,
+    accounts: &'info [AccountInfo<'info>],
+    dao: &mut Account<'info, Dao>,
+) -> Result<Option<u16>> {
+    let instruction_index = proposal.num_instructions_executed();
+    let instruction = instructions.instructions.get(instruction_index as usize);
+
+    if let Some(instruction) = instruction {
+        let spending_limits = proposal.spending_limits().to_vec();
+        execute_proposal_instruction(
+            instruction,
+            accounts,
+            dao,
+            &spending_limits,
+            proposal.treasury_outflows_mut(),
+        )?;
+    }
+
+    proposal.record_execution(instruction.is_some(), instructions.instructions.len());
+
+    Ok(instruction.map(|_| instruction_index))
+}
+
+impl<'info, 'c: 'info> ExecuteProposal<'info> {
+    pub fn validate(&self) -> Result<()> {
+        self.proposal.validate_execute(Clock::get()?.slot)
+    }
+
+    /// Executes the next of the proposal's instructions, with the instruction's
+    /// accounts passed in as remaining accounts. Proposals with several
+    /// instructions need one call per instruction, since they may not all fit
+    /// in one transaction.
+    pub fn handle(ctx: Context<'_, '_, 'c, 'info, Self>) -> Result<()> {
+        let ExecuteProposal {
+            proposal,
+            instructions,
+            dao,
+            event_authority: _,
+            program: _,
+        } = ctx.accounts;
+
+        let instruction_index =
+            execute_next_instruction(&mut **proposal, instructions, ctx.remaining_accounts, dao)?;
+
+        let clock = Clock::get()?;
+        emit_cpi!(ExecuteProposalEvent {
+            common: CommonFields::new(&clock),
+            proposal: proposal.key(),
+            dao: proposal.dao,
+            instruction_index,
+            state: proposal.state,
+        });
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use anchor_lang::solana_program::program_option::COption;
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_spl::token::spl_token;
+
+    fn token_account_data(mint: Pubkey, owner: Pubkey, amount: u64) -> Vec<u8> {
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        data
+    }
+
+    fn set_token_account(account: &AccountInfo, mint: Pubkey, owner: Pubkey, amount: u64) {
+        account
+            .data
+            .borrow_mut()
+            .copy_from_slice(&token_account_data(mint, owner, amount));
+    }
+
+    #[test]
+    fn only_the_treasurys_limited_accounts_are_counted_once() {
+        let treasury = Pubkey::new_unique();
+        let usdc_mint = Pubkey::new_unique();
+        let spending_limits = [SpendingLimit::new(usdc_mint, Some(100), None, 1_000)];
+
+        let keys = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let mut lamports = [1; 4];
+        let mut data = [
+            token_account_data(usdc_mint, treasury, 100),
+            token_account_data(usdc_mint, treasury, 100),
+            token_account_data(Pubkey::new_unique(), treasury, 100),
+            token_account_data(usdc_mint, Pubkey::new_unique(), 100),
+        ];
+        let [a, b, c, d] = &mut lamports;
+        let [a_data, b_data, c_data, d_data] = &mut data;
+        let accounts = vec![
+            AccountInfo::new(&keys[0], false, true, a, a_data, &spl_token::ID, false, 0),
+            // the same account, passed in twice
+            AccountInfo::new(&keys[0], false, true, b, b_data, &spl_token::ID, false, 0),
+            // a mint without a limit in the proposal's snapshot
+            AccountInfo::new(&keys[1], false, true, c, c_data, &spl_token::ID, false, 0),
+            // not the treasury's
+            AccountInfo::new(&keys[2], false, true, d, d_data, &spl_token::ID, false, 0),
+        ];
+
+        let limited_accounts =
+            LimitedTreasuryAccount::collect(&accounts, treasury, &spending_limits);
+
+        assert_eq!(limited_accounts.len(), 1);
+        assert_eq!(limited_accounts[0].index, 0);
+        assert_eq!(limited_accounts[0].amount, 100);
+        assert_eq!(limited_accounts[0].spending_limit, spending_limits[0]);
+    }
+
+    #[test]
+    fn outflows_are_balance_decreases() {
+        let treasury = Pubkey::new_unique();
+        let usdc_mint = Pubkey::new_unique();
+        let spending_limits = [SpendingLimit::new(usdc_mint, Some(100), None, 1_000)];
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 1;
+        let mut data = token_account_data(usdc_mint, treasury, 100);
+        let accounts = vec![AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &spl_token::ID,
+            false,
+            0,
+        )];
+
+        let limited_accounts =
+            LimitedTreasuryAccount::collect(&accounts, treasury, &spending_limits);
+        let limited = &limited_accounts[0];
+
+        set_token_account(&accounts[0], usdc_mint, treasury, 40);
+        assert_eq!(limited.outflow(&accounts, treasury).unwrap(), 60);
+
+        // deposits aren't outflows
+        set_token_account(&accounts[0], usdc_mint, treasury, 150);
+        assert_eq!(limited.outflow(&accounts, treasury).unwrap(), 0);
+
+        // a closed account gave up its whole balance
+        accounts[0].data.borrow_mut().fill(0);
+        assert_eq!(limited.outflow(&accounts, treasury).unwrap(), 100);
+    }
+
+    #[test]
+    fn outflows_require_the_same_owner_and_delegate() {
+        let treasury = Pubkey::new_unique();
+        let usdc_mint = Pubkey::new_unique();
+        let spending_limits = [SpendingLimit::new(usdc_mint, Some(100), None, 1_000)];
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 1;
+        let mut data = token_account_data(usdc_mint, treasury, 100);
+        let accounts = vec![AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &spl_token::ID,
+            false,
+            0,
+        )];
+
+        let limited_accounts =
+            LimitedTreasuryAccount::collect(&accounts, treasury, &spending_limits);
+        let limited = &limited_accounts[0];
+
+        set_token_account(&accounts[0], usdc_mint, Pubkey::new_unique(), 100);
+        assert_eq!(
+            limited.outflow(&accounts, treasury).unwrap_err(),
+            AutocratError::TreasuryTokenAccountAuthorityChanged.into()
+        );
+
+        let mut delegated =
+            spl_token::state::Account::unpack(&token_account_data(usdc_mint, treasury, 100))
+                .unwrap();
+        delegated.delegate = COption::Some(Pubkey::new_unique());
+        delegated.delegated_amount = 100;
+        delegated.pack_into_slice(&mut accounts[0].data.borrow_mut());
+        assert_eq!(
+            limited.outflow(&accounts, treasury).unwrap_err(),
+            AutocratError::TreasuryTokenAccountAuthorityChanged.into()
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use super::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExpireProposal<'info> {
+    #[account(mut, has_one = dao)]
+    pub proposal: Account<'info, Proposal>,
+    pub dao: Box<Account<'info, Dao>>,
+}
+
+impl ExpireProposal<'_> {
+    pub fn validate(&self) -> Result<()> {
+        self.proposal.validate_expire(Clock::get()?.slot)
+    }
+
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.state = ProposalState::Expired;
+
+        let clock = Clock::get()?;
+        emit_cpi!(ExpireProposalEvent {
+            common: CommonFields::new(&clock),
+            proposal: proposal.key(),
+            dao: proposal.dao,
+            num_instructions_executed: proposal.num_instructions_executed,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+use anchor_spl::associated_token::get_associated_token_address;
+
+use super::*;
+
+/// Gives the proposer back the LP tokens that they locked in each option's
+/// market, with the markets passed in as `[amm, lp_user_account,
+/// lp_vault_account]` for each option in outcome order, and returns the
+/// markets. Shared by finalizing and cancelling multi-option proposals.
+pub fn unlock_option_lp_tokens<'info>(
+    proposal: &MultiOptionProposal,
+    market_accounts: &'info [AccountInfo<'info>],
+    treasury: &AccountInfo<'info>,
+    treasury_signer: &[&[&[u8]]],
+    token_program: &Program<'info, Token>,
+) -> Result<Vec<Account<'info, Amm>>> {
+    require_eq!(
+        market_accounts.len(),
+        proposal.options.len() * 3,
+        AutocratError::InvalidOptionAccounts
+    );
+
+    let mut amms = Vec::with_capacity(proposal.options.len());
+
+    for (option, accounts) in proposal.options.iter().zip(market_accounts.chunks(3)) {
+        let amm = Account::<Amm>::try_from(&accounts[0])?;
+        let lp_user_account = Account::<TokenAccount>::try_from(&accounts[1])?;
+        let lp_vault_account = Account::<TokenAccount>::try_from(&accounts[2])?;
+
+        require!(
+            amm.key() == option.amm
+                && lp_user_account.key()
+                    == get_associated_token_address(&proposal.proposer, &amm.lp_mint)
+                && lp_vault_account.key()
+                    == get_associated_token_address(&treasury.key(), &amm.lp_mint),
+            AutocratError::InvalidOptionAccounts
+        );
+
+        unlock_lp_tokens(
+            option.lp_tokens_locked,
+            &lp_vault_account,
+            &lp_user_account,
+            treasury,
+            treasury_signer,
+            token_program,
+        )?;
+
+        amms.push(amm);
+    }
+
+    Ok(amms)
+}
+
+/// Every option's market is passed in through the remaining accounts, as
+/// `[amm, lp_user_account, lp_vault_account]` for each option in outcome order.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinalizeMultiOptionProposal<'info> {
+    #[account(mut,
+        has_one = question,
+        has_one = dao,
+    )]
+    pub proposal: Box<Account<'info, MultiOptionProposal>>,
+    #[account(mut, has_one = treasury)]
+    pub dao: Box<Account<'info, Dao>>,
+    /// Created here for proposals that were made before proposer records
+    /// existed.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<ProposerRecord>(),
+        seeds = [b"proposer_record", dao.key().as_ref(), proposal.proposer.as_ref()],
+        bump,
+    )]
+    pub proposer_record: Box<Account<'info, ProposerRecord>>,
+    #[account(mut)]
+    pub question: Account<'info, Question>,
+    /// CHECK: it's okay
+    pub treasury: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub vault_program: Program<'info, ConditionalVaultProgram>,
+    /// CHECK: checked by vault program
+    pub vault_event_authority: UncheckedAccount<'info>,
+    /// Only needed if the proposal has a `spot_oracle_snapshot`.
+    #[account(
+        constraint = Some(spot_amm.key()) == proposal.spot_oracle_snapshot.map(|snapshot| snapshot.amm)
+            @ AutocratError::InvalidSpotAmm
+    )]
+    pub spot_amm: Option<Box<Account<'info, Amm>>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info, 'c: 'info> FinalizeMultiOptionProposal<'info> {
+    pub fn validate(&self) -> Result<()> {
+        self.proposal.validate_finalize(Clock::get()?.slot)
+    }
+
+    pub fn handle(ctx: Context<'_, '_, 'c, 'info, Self>) -> Result<()> {
+        let FinalizeMultiOptionProposal {
+            proposal,
+            dao,
+            proposer_record,
+            question,
+            treasury,
+            vault_program,
+            token_program,
+            vault_event_authority,
+            spot_amm,
+            payer: _,
+            system_program: _,
+            event_authority: _,
+            program: _,
+        } = ctx.accounts;
+
+        let num_options = proposal.options.len();
+
+        let proposer_key = proposal.proposer;
+        let nonce = proposal.nonce;
+        let proposal_seeds = &[
+            b"multi_option_proposal",
+            proposer_key.as_ref(),
+            &nonce.to_le_bytes(),
+            &[proposal.pda_bump],
+        ];
+        let proposal_signer = &[&proposal_seeds[..]];
+
+        let dao_key = dao.key();
+        let treasury_seeds = &[dao_key.as_ref(), &[dao.treasury_pda_bump]];
+        let treasury_signer = &[&treasury_seeds[..]];
+
+        let amms = unlock_option_lp_tokens(
+            proposal,
+            ctx.remaining_accounts,
+            &treasury.to_account_info(),
+            treasury_signer,
+            token_program,
+        )?;
+
+        proposal.spot_twap = spot_market_twap(
+            proposal.spot_oracle_snapshot,
+            spot_amm.as_ref().map(|spot_amm| &***spot_amm),
+            &***proposal,
+        )?;
+
+        let clock = Clock::get()?;
+
+        let mut twaps = Vec::with_capacity(num_options);
+        let mut markets_had_liquidity = true;
+
+        for (amm, option) in amms.iter().zip(&proposal.options) {
+            twaps.push(market_twap(amm, &***proposal)?);
+
+            markets_had_liquidity &=
+                market_had_liquidity(amm, &option.market_snapshot, &***proposal, clock.slot)?;
+        }
+
+        let decision = MultiOptionDecision {
+            option_twaps: twaps.clone(),
+            markets_had_liquidity,
+        };
+        let winning_option = proposal.winning_option_of(&decision);
+
+        let mut payout_numerators = vec![0; num_options];
+        payout_numerators[winning_option] = 1;
+
+        proposal.state = if winning_option == 0 {
+            ProposalState::Failed
+        } else {
+            ProposalState::Passed
+        };
+        proposer_record.initialize_if_new(dao.key(), proposal.proposer, ctx.bumps.proposer_record);
+        proposer_record.close_proposal(dao);
+        // can't truncate because there are at most `MAX_PROPOSAL_OPTIONS` options
+        proposal.winning_option = Some(winning_option as u8);
+        proposal.slot_finalized = clock.slot;
+        proposal.decision = Some(decision);
+
+        resolve_proposal_question(
+            question,
+            proposal.to_account_info(),
+            proposal_signer,
+            vault_program,
+            vault_event_authority,
+            payout_numerators,
+        )?;
+
+        emit_cpi!(FinalizeMultiOptionProposalEvent {
+            common: CommonFields::new(&clock),
+            proposal: proposal.key(),
+            dao: proposal.dao,
+            option_twaps: twaps,
+            pass_threshold_bps: proposal.pass_threshold_bps,
+            decision_rule: proposal.dao_snapshot.decision_rule,
+            spot_twap: proposal.spot_twap,
+            markets_had_liquidity,
+            winning_option: winning_option as u8,
+            state: proposal.state,
+        });
+
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use conditional_vault::{cpi::accounts::ResolveQuestion, ResolveQuestionArgs};
 
 use super::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct FinalizeProposal<'info> {
     #[account(mut,
@@ -9,12 +10,24 @@ pub struct FinalizeProposal<'info> {
         has_one = pass_amm,
         has_one = fail_amm,
         has_one = dao,
+        has_one = instructions,
     )]
     pub proposal: Account<'info, Proposal>,
+    pub instructions: Box<Account<'info, ProposalInstructions>>,
     pub pass_amm: Account<'info, Amm>,
     pub fail_amm: Account<'info, Amm>,
-    #[account(has_one = treasury)]
+    #[account(mut, has_one = treasury)]
     pub dao: Box<Account<'info, Dao>>,
+    /// Created here for proposals that were made before proposer records
+    /// existed.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<ProposerRecord>(),
+        seeds = [b"proposer_record", dao.key().as_ref(), proposal.proposer.as_ref()],
+        bump,
+    )]
+    pub proposer_record: Box<Account<'info, ProposerRecord>>,
     #[account(mut)]
     pub question: Account<'info, Question>,
     /// CHECK: it's okay
@@ -43,44 +56,156 @@ pub struct FinalizeProposal<'info> {
         associated_token::authority = dao.treasury,
     )]
     pub fail_lp_vault_account: Box<Account<'info, TokenAccount>>,
+    /// Only needed if the proposal has a `spot_oracle_snapshot`.
+    #[account(
+        constraint = Some(spot_amm.key()) == proposal.spot_oracle_snapshot.map(|snapshot| snapshot.amm)
+            @ AutocratError::InvalidSpotAmm
+    )]
+    pub spot_amm: Option<Box<Account<'info, Amm>>>,
     pub token_program: Program<'info, Token>,
     pub vault_program: Program<'info, ConditionalVaultProgram>,
     /// CHECK: checked by vault program
     pub vault_event_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
-impl FinalizeProposal<'_> {
-    pub fn validate(&self) -> Result<()> {
-        let clock = Clock::get()?;
+/// Gives the proposer back the LP tokens that they locked in one of their
+/// proposal's markets. Shared by all kinds of proposals.
+pub fn unlock_lp_tokens<'info>(
+    lp_tokens_locked: u64,
+    lp_vault_account: &Account<'info, TokenAccount>,
+    lp_user_account: &Account<'info, TokenAccount>,
+    treasury: &AccountInfo<'info>,
+    treasury_signer: &[&[&[u8]]],
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    // without this, someone can brick a proposal if they have another proposal transfer
+    // out its LP tokens from the treasury.
+    let lp_tokens_to_unlock = std::cmp::min(lp_tokens_locked, lp_vault_account.amount);
 
-        require!(
-            clock.slot >= self.proposal.slot_enqueued + self.dao.slots_per_proposal,
-            AutocratError::ProposalTooYoung
-        );
+    token::transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Transfer {
+                from: lp_vault_account.to_account_info(),
+                to: lp_user_account.to_account_info(),
+                authority: treasury.clone(),
+            },
+        )
+        .with_signer(treasury_signer),
+        lp_tokens_to_unlock,
+    )
+}
 
-        require!(
-            self.proposal.state == ProposalState::Pending,
-            AutocratError::ProposalAlreadyFinalized
-        );
+/// The TWAP of one of a proposal's markets, which needs to have been cranked
+/// over the whole proposal. Shared by all kinds of proposals.
+pub fn market_twap(amm: &Amm, proposal: &impl ProposalLifecycle) -> Result<u128> {
+    let slots_passed = amm
+        .oracle
+        .last_updated_slot
+        .saturating_sub(proposal.slot_enqueued());
 
-        Ok(())
+    require!(
+        slots_passed >= proposal.slots_per_proposal(),
+        AutocratError::MarketsTooYoung
+    );
+
+    amm.get_twap()
+}
+
+/// Whether one of a proposal's markets had at least the DAO's
+/// `min_average_quote_liquidity` on average, from when the proposal was
+/// created until now. A market that was nearly empty for most of the proposal
+/// is too easy to push around, so its TWAP can't be trusted to pass anything.
+/// Shared by all kinds of proposals.
+pub fn market_had_liquidity(
+    amm: &Amm,
+    snapshot: &MarketSnapshot,
+    proposal: &impl ProposalLifecycle,
+    slot: u64,
+) -> Result<bool> {
+    Ok(
+        snapshot.average_quote_liquidity(amm, proposal.slot_enqueued(), slot)?
+            >= proposal.dao_snapshot().min_average_quote_liquidity,
+    )
+}
+
+/// The spot market's TWAP over a proposal, if the proposal took a snapshot of
+/// it. Shared by all kinds of proposals.
+pub fn spot_market_twap(
+    spot_oracle_snapshot: Option<SpotOracleSnapshot>,
+    spot_amm: Option<&Amm>,
+    proposal: &impl ProposalLifecycle,
+) -> Result<Option<u128>> {
+    let Some(snapshot) = spot_oracle_snapshot else {
+        return Ok(None);
+    };
+
+    let Some(spot_amm) = spot_amm else {
+        return err!(AutocratError::MissingSpotAmm);
+    };
+
+    // like the futarchic markets, the spot market needs to have been cranked
+    // over the whole proposal
+    require_gte!(
+        spot_amm.oracle.last_updated_slot,
+        proposal.slot_enqueued() + proposal.slots_per_proposal(),
+        AutocratError::MarketsTooYoung
+    );
+
+    Ok(Some(snapshot.twap_until(spot_amm)?))
+}
+
+/// Resolves a proposal's question with `payout_numerators`, signed by the
+/// proposal. Shared by all kinds of proposals.
+pub fn resolve_proposal_question<'info>(
+    question: &Account<'info, Question>,
+    proposal: AccountInfo<'info>,
+    proposal_signer: &[&[&[u8]]],
+    vault_program: &Program<'info, ConditionalVaultProgram>,
+    vault_event_authority: &AccountInfo<'info>,
+    payout_numerators: Vec<u32>,
+) -> Result<()> {
+    let vault_program = vault_program.to_account_info();
+    let cpi_accounts = ResolveQuestion {
+        question: question.to_account_info(),
+        oracle: proposal,
+        event_authority: vault_event_authority.clone(),
+        program: vault_program.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(vault_program, cpi_accounts).with_signer(proposal_signer);
+    conditional_vault::cpi::resolve_question(cpi_ctx, ResolveQuestionArgs { payout_numerators })
+}
+
+impl FinalizeProposal<'_> {
+    pub fn validate(&self) -> Result<()> {
+        self.proposal.validate_finalize(Clock::get()?.slot)
     }
 
     pub fn handle(ctx: Context<Self>) -> Result<()> {
         let FinalizeProposal {
             proposal,
+            instructions,
             pass_amm,
             fail_amm,
             dao,
+            proposer_record,
             question,
             treasury,
             pass_lp_user_account,
             fail_lp_user_account,
             pass_lp_vault_account,
             fail_lp_vault_account,
+            spot_amm,
             vault_program,
             token_program,
             vault_event_authority,
+            payer: _,
+            system_program: _,
+            event_authority: _,
+            program: _,
         } = ctx.accounts;
 
         let proposer_key = proposal.proposer;
@@ -97,7 +222,7 @@ impl FinalizeProposal<'_> {
         let treasury_seeds = &[dao_key.as_ref(), &[dao.treasury_pda_bump]];
         let treasury_signer = &[&treasury_seeds[..]];
 
-        for (lp_tokens_to_unlock, from, to) in [
+        for (lp_tokens_locked, lp_vault_account, lp_user_account) in [
             (
                 proposal.pass_lp_tokens_locked,
                 pass_lp_vault_account,
@@ -109,66 +234,82 @@ impl FinalizeProposal<'_> {
                 fail_lp_user_account,
             ),
         ] {
-            // without this, someone can brick a proposal if they have another proposal transfer
-            // out its LP tokens from the treasury.
-            let lp_tokens_to_unlock = std::cmp::min(lp_tokens_to_unlock, from.amount);
-
-            token::transfer(
-                CpiContext::new(
-                    token_program.to_account_info(),
-                    Transfer {
-                        from: from.to_account_info(),
-                        to: to.to_account_info(),
-                        authority: treasury.to_account_info(),
-                    },
-                )
-                .with_signer(treasury_signer),
-                lp_tokens_to_unlock,
+            unlock_lp_tokens(
+                lp_tokens_locked,
+                lp_vault_account,
+                lp_user_account,
+                &treasury.to_account_info(),
+                treasury_signer,
+                token_program,
             )?;
         }
 
-        let calculate_twap = |amm: &Amm| -> Result<u128> {
-            let slots_passed = amm.oracle.last_updated_slot - proposal.slot_enqueued;
+        let pass_market_twap = market_twap(pass_amm, &**proposal)?;
+        let fail_market_twap = market_twap(fail_amm, &**proposal)?;
 
-            require!(
-                slots_passed >= dao.slots_per_proposal,
-                AutocratError::MarketsTooYoung
-            );
+        proposal.spot_twap = spot_market_twap(
+            proposal.spot_oracle_snapshot,
+            spot_amm.as_ref().map(|spot_amm| &***spot_amm),
+            &**proposal,
+        )?;
 
-            amm.get_twap()
-        };
+        let clock = Clock::get()?;
 
-        let pass_market_twap = calculate_twap(pass_amm)?;
-        let fail_market_twap = calculate_twap(fail_amm)?;
+        let markets_had_liquidity = market_had_liquidity(
+            pass_amm,
+            &proposal.market_snapshots[PASS_INDEX],
+            &**proposal,
+            clock.slot,
+        )? && market_had_liquidity(
+            fail_amm,
+            &proposal.market_snapshots[FAIL_INDEX],
+            &**proposal,
+            clock.slot,
+        )?;
 
-        // this can't overflow because each twap can only be MAX_PRICE (~1e31),
-        // MAX_BPS + pass_threshold_bps is at most 1e5, and a u128 can hold
-        // 1e38. still, saturate
-        let threshold = fail_market_twap
-            .saturating_mul(MAX_BPS.saturating_add(dao.pass_threshold_bps).into())
-            / MAX_BPS as u128;
+        let decision = ProposalDecision {
+            pass_market_twap,
+            fail_market_twap,
+            proposal_type: instructions.proposal_type(dao.treasury),
+            markets_had_liquidity,
+        };
 
-        let (new_proposal_state, payout_numerators) = if pass_market_twap > threshold {
+        let (new_proposal_state, payout_numerators) = if proposal.passes(&decision) {
             (ProposalState::Passed, vec![0, 1])
         } else {
             (ProposalState::Failed, vec![1, 0])
         };
 
         proposal.state = new_proposal_state;
+        proposal.decision = Some(decision);
+        proposal.record_quote_volumes(pass_amm, fail_amm);
+        proposer_record.initialize_if_new(dao.key(), proposal.proposer, ctx.bumps.proposer_record);
+        proposer_record.close_proposal(dao);
 
-        let vault_program = vault_program.to_account_info();
-        let cpi_accounts = ResolveQuestion {
-            question: question.to_account_info(),
-            oracle: proposal.to_account_info(),
-            event_authority: vault_event_authority.to_account_info(),
-            program: vault_program.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(vault_program, cpi_accounts).with_signer(proposal_signer);
-        conditional_vault::cpi::resolve_question(
-            cpi_ctx,
-            ResolveQuestionArgs { payout_numerators },
+        proposal.slot_finalized = clock.slot;
+
+        resolve_proposal_question(
+            question,
+            proposal.to_account_info(),
+            proposal_signer,
+            vault_program,
+            vault_event_authority,
+            payout_numerators,
         )?;
 
+        emit_cpi!(FinalizeProposalEvent {
+            common: CommonFields::new(&clock),
+            proposal: proposal.key(),
+            dao: proposal.dao,
+            pass_market_twap,
+            fail_market_twap,
+            pass_threshold_bps: proposal.pass_threshold_bps,
+            decision_rule: proposal.dao_snapshot.decision_rule,
+            spot_twap: proposal.spot_twap,
+            markets_had_liquidity,
+            state: proposal.state,
+        });
+
         Ok(())
     }
 }
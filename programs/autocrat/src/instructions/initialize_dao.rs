@@ -8,14 +8,25 @@ pub struct InitializeDaoParams {
     pub min_base_futarchic_liquidity: u64,
     pub pass_threshold_bps: Option<u16>,
     pub slots_per_proposal: Option<u64>,
+    pub min_average_quote_liquidity: Option<u64>,
+    pub execution_delay_slots: Option<u64>,
+    pub veto_authority: Option<Pubkey>,
+    pub execution_window_slots: Option<u64>,
+    pub decision_rule: Option<DecisionRule>,
+    pub proposal_bond: Option<ProposalBond>,
+    pub max_active_proposals: Option<u32>,
+    pub max_active_proposals_per_proposer: Option<u32>,
+    pub spot_amm: Option<Pubkey>,
+    pub treasury_liquidity: Option<TreasuryLiquidity>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializeDAO<'info> {
     #[account(
         init,
         payer = payer,
-        space = 8 + std::mem::size_of::<Dao>()
+        space = Dao::space()
     )]
     pub dao: Account<'info, Dao>,
     #[account(mut)]
@@ -36,6 +47,16 @@ impl InitializeDAO<'_> {
             min_quote_futarchic_liquidity,
             pass_threshold_bps,
             slots_per_proposal,
+            min_average_quote_liquidity,
+            execution_delay_slots,
+            veto_authority,
+            execution_window_slots,
+            decision_rule,
+            proposal_bond,
+            max_active_proposals,
+            max_active_proposals_per_proposer,
+            spot_amm,
+            treasury_liquidity,
         } = params;
 
         let dao = &mut ctx.accounts.dao;
@@ -55,6 +76,31 @@ impl InitializeDAO<'_> {
             twap_max_observation_change_per_update,
             min_base_futarchic_liquidity,
             min_quote_futarchic_liquidity,
+            min_average_quote_liquidity: min_average_quote_liquidity.unwrap_or(0),
+            execution_delay_slots: execution_delay_slots.unwrap_or(0),
+            veto_authority,
+            execution_window_slots: execution_window_slots.unwrap_or(TEN_DAYS_IN_SLOTS),
+            decision_rule: decision_rule.unwrap_or_default(),
+            proposal_categories: vec![],
+            proposal_bond,
+            active_proposal_count: 0,
+            max_active_proposals,
+            max_active_proposals_per_proposer,
+            spot_amm,
+            treasury_liquidity,
+            spending_limits: vec![],
+        });
+
+        dao.validate_params()?;
+
+        let clock = Clock::get()?;
+        emit_cpi!(InitializeDaoEvent {
+            common: CommonFields::new(&clock),
+            dao: dao.key(),
+            token_mint: dao.token_mint,
+            usdc_mint: dao.usdc_mint,
+            treasury: dao.treasury,
+            config: DaoConfig::new(dao),
         });
 
         Ok(())
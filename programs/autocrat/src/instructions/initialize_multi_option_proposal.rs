@@ -0,0 +1,300 @@
+use super::*;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct InitializeMultiOptionProposalParams {
+    pub description_url: String,
+    /// How many LP tokens to lock in each option's market, including the status
+    /// quo's, in outcome order.
+    pub lp_tokens_to_lock: Vec<u64>,
+    pub nonce: u64,
+}
+
+/// Every option's market is passed in through the remaining accounts, as
+/// `[amm, lp_mint, lp_user_account, lp_vault_account]` for each option in
+/// outcome order. Those are followed by the `ProposalInstructions` of every
+/// option except the status quo, also in outcome order.
+///
+/// Unlike regular proposals, these don't go through a `ProposalSetup`. The
+/// question has to be the proposal's canonical one, and the vaults and markets
+/// are PDAs of the question and of the vaults' conditional mints, so there
+/// aren't any other accounts that a proposer could swap in. What the setup adds
+/// on top of that is treasury seeding and bonds, which multi-option proposals
+/// don't support.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: InitializeMultiOptionProposalParams)]
+pub struct InitializeMultiOptionProposal<'info> {
+    #[account(
+        init,
+        payer = proposer,
+        space = MultiOptionProposal::space(&args.description_url, args.lp_tokens_to_lock.len()),
+        seeds = [b"multi_option_proposal", proposer.key().as_ref(), &args.nonce.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Box<Account<'info, MultiOptionProposal>>,
+    #[account(mut)]
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        space = 8 + std::mem::size_of::<ProposerRecord>(),
+        seeds = [b"proposer_record", dao.key().as_ref(), proposer.key().as_ref()],
+        bump
+    )]
+    pub proposer_record: Box<Account<'info, ProposerRecord>>,
+    #[account(
+        constraint = question.oracle == proposal.key(),
+        constraint = question.question_id == pda::proposal_question_id(&proposal.key())
+            @ AutocratError::NonCanonicalQuestion,
+    )]
+    pub question: Box<Account<'info, Question>>,
+    #[account(
+        constraint = quote_vault.underlying_token_mint == dao.usdc_mint,
+        has_one = question,
+    )]
+    pub quote_vault: Box<Account<'info, ConditionalVaultAccount>>,
+    #[account(
+        constraint = base_vault.underlying_token_mint == dao.token_mint,
+        has_one = question,
+    )]
+    pub base_vault: Box<Account<'info, ConditionalVaultAccount>>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    /// Must be the DAO's `spot_amm` if it has one, and omitted otherwise.
+    pub spot_amm: Option<Box<Account<'info, Amm>>>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info, 'c: 'info> InitializeMultiOptionProposal<'info> {
+    pub fn validate(&self, params: &InitializeMultiOptionProposalParams) -> Result<()> {
+        // otherwise, proposers could use multi-option proposals to get around
+        // the bond, and their markets would have less liquidity than the DAO
+        // wants
+        require!(
+            self.dao.proposal_bond.is_none() && self.dao.treasury_liquidity.is_none(),
+            AutocratError::UnsupportedByMultiOptionProposals
+        );
+
+        let num_options = params.lp_tokens_to_lock.len();
+
+        require!(
+            (2..=MAX_PROPOSAL_OPTIONS).contains(&num_options),
+            AutocratError::InvalidNumOptions
+        );
+
+        require_eq!(
+            self.question.num_outcomes(),
+            num_options,
+            AutocratError::InvalidNumOptions
+        );
+
+        self.dao.validate_spot_amm(self.spot_amm.as_deref())?;
+
+        Ok(())
+    }
+
+    pub fn handle(
+        ctx: Context<'_, '_, 'c, 'info, Self>,
+        params: InitializeMultiOptionProposalParams,
+    ) -> Result<()> {
+        let Self {
+            base_vault,
+            quote_vault,
+            question,
+            proposal,
+            dao,
+            proposer_record,
+            proposer,
+            spot_amm,
+            token_program,
+            system_program: _,
+            event_authority: _,
+            program: _,
+        } = ctx.accounts;
+
+        let InitializeMultiOptionProposalParams {
+            description_url,
+            lp_tokens_to_lock,
+            nonce,
+        } = params;
+
+        let num_options = lp_tokens_to_lock.len();
+
+        require_eq!(
+            ctx.remaining_accounts.len(),
+            num_options * 4 + (num_options - 1),
+            AutocratError::InvalidOptionAccounts
+        );
+
+        let (market_accounts, instructions_accounts) =
+            ctx.remaining_accounts.split_at(num_options * 4);
+
+        let mut option_instructions: Vec<Account<ProposalInstructions>> =
+            Vec::with_capacity(num_options - 1);
+        for account in instructions_accounts {
+            // every option needs its own instructions
+            require!(
+                !option_instructions
+                    .iter()
+                    .any(|proposal_instructions| proposal_instructions.key() == account.key()),
+                AutocratError::InvalidOptionAccounts
+            );
+
+            let proposal_instructions = Account::<ProposalInstructions>::try_from(account)?;
+
+            require_keys_eq!(
+                proposal_instructions.authority,
+                proposer.key(),
+                AutocratError::InvalidOptionAccounts
+            );
+            require!(
+                !proposal_instructions.frozen,
+                AutocratError::ProposalInstructionsFrozen
+            );
+
+            option_instructions.push(proposal_instructions);
+        }
+
+        // the markets can't tell which option will win, so every option needs
+        // to meet the requirements of the strictest one
+        let requirements = dao.get_proposal_requirements(
+            option_instructions
+                .iter()
+                .flat_map(|proposal_instructions| &proposal_instructions.instructions),
+        );
+
+        let clock = Clock::get()?;
+
+        let mut options = Vec::with_capacity(num_options);
+
+        for (i, accounts) in market_accounts.chunks(4).enumerate() {
+            let amm = Account::<Amm>::try_from(&accounts[0])?;
+            let lp_mint = Account::<Mint>::try_from(&accounts[1])?;
+            let lp_user_account = Account::<TokenAccount>::try_from(&accounts[2])?;
+            let lp_vault_account = Account::<TokenAccount>::try_from(&accounts[3])?;
+
+            require!(
+                amm.base_mint == base_vault.conditional_token_mints[i]
+                    && amm.quote_mint == quote_vault.conditional_token_mints[i]
+                    && amm.lp_mint == lp_mint.key()
+                    && lp_user_account.key()
+                        == get_associated_token_address(&proposer.key(), &amm.lp_mint)
+                    && lp_vault_account.key()
+                        == get_associated_token_address(&dao.treasury, &amm.lp_mint),
+                AutocratError::InvalidOptionAccounts
+            );
+
+            validate_futarchic_amm(&amm, dao, clock.slot)?;
+
+            let lp_tokens_to_lock = lp_tokens_to_lock[i];
+
+            require_gte!(
+                lp_user_account.amount,
+                lp_tokens_to_lock,
+                AutocratError::InsufficientLpTokenBalance
+            );
+
+            let (base_liquidity, quote_liquidity) =
+                amm.get_base_and_quote_withdrawable(lp_tokens_to_lock, lp_mint.supply)?;
+
+            require_gte!(
+                base_liquidity,
+                requirements.min_base_futarchic_liquidity,
+                AutocratError::InsufficientLpTokenLock
+            );
+            require_gte!(
+                quote_liquidity,
+                requirements.min_quote_futarchic_liquidity,
+                AutocratError::InsufficientLpTokenLock
+            );
+
+            token::transfer(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    Transfer {
+                        from: lp_user_account.to_account_info(),
+                        to: lp_vault_account.to_account_info(),
+                        authority: proposer.to_account_info(),
+                    },
+                ),
+                lp_tokens_to_lock,
+            )?;
+
+            options.push(ProposalOption {
+                amm: amm.key(),
+                lp_tokens_locked: lp_tokens_to_lock,
+                instructions: None,
+                proposal_type: ProposalType::Standard,
+                market_snapshot: MarketSnapshot::new(&amm, clock.slot),
+            });
+        }
+
+        // the status quo doesn't do anything, so it has no instructions
+        for (option, proposal_instructions) in options
+            .iter_mut()
+            .skip(1)
+            .zip(option_instructions.iter_mut())
+        {
+            proposal_instructions.frozen = true;
+            proposal_instructions.exit(ctx.program_id)?;
+
+            option.instructions = Some(proposal_instructions.key());
+            option.proposal_type = proposal_instructions.proposal_type(dao.treasury);
+        }
+
+        proposer_record.initialize_if_new(dao.key(), proposer.key(), ctx.bumps.proposer_record);
+        proposer_record.open_proposal(dao)?;
+
+        dao.proposal_count += 1;
+
+        proposal.set_inner(MultiOptionProposal {
+            number: dao.proposal_count,
+            proposer: proposer.key(),
+            description_url,
+            slot_enqueued: clock.slot,
+            slot_finalized: 0,
+            state: ProposalState::Pending,
+            options,
+            winning_option: None,
+            num_instructions_executed: 0,
+            pass_threshold_bps: requirements.pass_threshold_bps,
+            slots_per_proposal: requirements.slots_per_proposal,
+            base_vault: base_vault.key(),
+            quote_vault: quote_vault.key(),
+            dao: dao.key(),
+            question: question.key(),
+            nonce,
+            pda_bump: ctx.bumps.proposal,
+            dao_snapshot: dao.snapshot(),
+            spot_oracle_snapshot: spot_amm
+                .as_ref()
+                .map(|spot_amm| SpotOracleSnapshot::new(spot_amm)),
+            spot_twap: None,
+            spending_limits: dao.spending_limits_snapshot(),
+            treasury_outflows: vec![],
+            decision: None,
+        });
+
+        emit_cpi!(InitializeMultiOptionProposalEvent {
+            common: CommonFields::new(&clock),
+            proposal: proposal.key(),
+            dao: proposal.dao,
+            proposer: proposal.proposer,
+            number: proposal.number,
+            description_url: proposal.description_url.clone(),
+            question: proposal.question,
+            base_vault: proposal.base_vault,
+            quote_vault: proposal.quote_vault,
+            options: proposal.options.clone(),
+            pass_threshold_bps: proposal.pass_threshold_bps,
+            slots_per_proposal: proposal.slots_per_proposal,
+            nonce: proposal.nonce,
+        });
+
+        Ok(())
+    }
+}
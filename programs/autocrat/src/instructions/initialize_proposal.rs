@@ -6,19 +6,19 @@ use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct InitializeProposalParams {
     pub description_url: String,
-    pub instruction: ProposalInstruction,
     pub pass_lp_tokens_to_lock: u64,
     pub fail_lp_tokens_to_lock: u64,
     pub nonce: u64,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(args: InitializeProposalParams)]
 pub struct InitializeProposal<'info> {
     #[account(
         init,
         payer = proposer,
-        space = 2000,
+        space = Proposal::space(&args.description_url),
         seeds = [b"proposal", proposer.key().as_ref(), &args.nonce.to_le_bytes()],
         bump
     )]
@@ -26,7 +26,24 @@ pub struct InitializeProposal<'info> {
     #[account(mut)]
     pub dao: Box<Account<'info, Dao>>,
     #[account(
-        constraint = question.oracle == proposal.key()
+        init_if_needed,
+        payer = proposer,
+        space = 8 + std::mem::size_of::<ProposerRecord>(),
+        seeds = [b"proposer_record", dao.key().as_ref(), proposer.key().as_ref()],
+        bump
+    )]
+    pub proposer_record: Box<Account<'info, ProposerRecord>>,
+    #[account(
+        mut,
+        constraint = proposal_instructions.authority == proposer.key(),
+    )]
+    pub proposal_instructions: Box<Account<'info, ProposalInstructions>>,
+    #[account(
+        constraint = question.oracle == proposal.key(),
+        constraint = question.question_id == pda::proposal_question_id(&proposal.key())
+            @ AutocratError::NonCanonicalQuestion,
+        constraint = question.num_outcomes() == PROPOSAL_NUM_OUTCOMES as usize
+            @ AutocratError::NonCanonicalQuestion,
     )]
     pub question: Box<Account<'info, Question>>,
     #[account(
@@ -53,6 +70,24 @@ pub struct InitializeProposal<'info> {
         constraint = fail_amm.quote_mint == quote_vault.conditional_token_mints[FAIL_INDEX],
     )]
     pub fail_amm: Box<Account<'info, Amm>>,
+    /// Consumed here, so that the proposal can only use the accounts that
+    /// autocrat created for it.
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"proposal_setup", proposer.key().as_ref(), &args.nonce.to_le_bytes()],
+        bump = proposal_setup.pda_bump,
+        has_one = dao,
+        has_one = question,
+        has_one = base_vault,
+        has_one = quote_vault,
+        has_one = pass_amm,
+        has_one = fail_amm,
+        constraint = proposal_setup.proposal == proposal.key(),
+        constraint = proposal_setup.stage == ProposalSetupStage::Complete
+            @ AutocratError::ProposalSetupIncomplete,
+    )]
+    pub proposal_setup: Box<Account<'info, ProposalSetup>>,
     #[account(
         mut,
         associated_token::mint = pass_amm.lp_mint,
@@ -79,34 +114,68 @@ pub struct InitializeProposal<'info> {
     pub fail_lp_vault_account: Account<'info, TokenAccount>,
     #[account(mut)]
     pub proposer: Signer<'info>,
+    /// The bond accounts are only needed if the DAO has a `proposal_bond`.
+    pub bond_mint: Option<Box<Account<'info, Mint>>>,
+    #[account(
+        mut,
+        token::mint = bond_mint,
+        token::authority = proposer,
+    )]
+    pub bond_user_account: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(
+        init,
+        payer = proposer,
+        seeds = [b"bond", proposal.key().as_ref()],
+        bump,
+        token::mint = bond_mint,
+        token::authority = proposal,
+    )]
+    pub bond_escrow: Option<Box<Account<'info, TokenAccount>>>,
+    /// Must be the DAO's `spot_amm` if it has one, and omitted otherwise.
+    pub spot_amm: Option<Box<Account<'info, Amm>>>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
+/// Checks that a proposal's market was set up the way the DAO wants it. Shared
+/// by all kinds of proposals.
+pub fn validate_futarchic_amm(amm: &Amm, dao: &Dao, slot: u64) -> Result<()> {
+    // an attacker is able to crank 5 observations before a proposal starts
+    require!(
+        slot < amm.created_at_slot + (5 * ONE_MINUTE_IN_SLOTS),
+        AutocratError::AmmTooOld
+    );
+
+    require_eq!(
+        amm.oracle.initial_observation,
+        dao.twap_initial_observation,
+        AutocratError::InvalidInitialObservation
+    );
+
+    require_eq!(
+        amm.oracle.max_observation_change_per_update,
+        dao.twap_max_observation_change_per_update,
+        AutocratError::InvalidMaxObservationChange
+    );
+
+    Ok(())
+}
+
 impl InitializeProposal<'_> {
     pub fn validate(&self) -> Result<()> {
         let clock = Clock::get()?;
 
-        for amm in [&self.pass_amm, &self.fail_amm] {
-            // an attacker is able to crank 5 observations before a proposal starts
-            require!(
-                clock.slot < amm.created_at_slot + (5 * ONE_MINUTE_IN_SLOTS),
-                AutocratError::AmmTooOld
-            );
-
-            require_eq!(
-                amm.oracle.initial_observation,
-                self.dao.twap_initial_observation,
-                AutocratError::InvalidInitialObservation
-            );
+        require!(
+            !self.proposal_instructions.frozen,
+            AutocratError::ProposalInstructionsFrozen
+        );
 
-            require_eq!(
-                amm.oracle.max_observation_change_per_update,
-                self.dao.twap_max_observation_change_per_update,
-                AutocratError::InvalidMaxObservationChange
-            );
+        for amm in [&self.pass_amm, &self.fail_amm] {
+            validate_futarchic_amm(amm, &self.dao, clock.slot)?;
         }
 
+        self.dao.validate_spot_amm(self.spot_amm.as_deref())?;
+
         Ok(())
     }
 
@@ -117,8 +186,11 @@ impl InitializeProposal<'_> {
             question,
             proposal,
             dao,
+            proposer_record,
+            proposal_instructions,
             pass_amm,
             fail_amm,
+            proposal_setup: _,
             pass_lp_mint,
             fail_lp_mint,
             pass_lp_user_account,
@@ -126,13 +198,18 @@ impl InitializeProposal<'_> {
             pass_lp_vault_account,
             fail_lp_vault_account,
             proposer,
+            bond_mint,
+            bond_user_account,
+            bond_escrow,
+            spot_amm,
             token_program,
             system_program: _,
+            event_authority: _,
+            program: _,
         } = ctx.accounts;
 
         let InitializeProposalParams {
             description_url,
-            instruction,
             pass_lp_tokens_to_lock,
             fail_lp_tokens_to_lock,
             nonce,
@@ -149,15 +226,17 @@ impl InitializeProposal<'_> {
             AutocratError::InsufficientLpTokenBalance
         );
 
-        let (pass_base_liquidity, pass_quote_liquidity) =
-            pass_amm.get_base_and_quote_withdrawable(pass_lp_tokens_to_lock, pass_lp_mint.supply);
-        let (fail_base_liquidity, fail_quote_liquidity) =
-            fail_amm.get_base_and_quote_withdrawable(fail_lp_tokens_to_lock, fail_lp_mint.supply);
+        let (pass_base_liquidity, pass_quote_liquidity) = pass_amm
+            .get_base_and_quote_withdrawable(pass_lp_tokens_to_lock, pass_lp_mint.supply)?;
+        let (fail_base_liquidity, fail_quote_liquidity) = fail_amm
+            .get_base_and_quote_withdrawable(fail_lp_tokens_to_lock, fail_lp_mint.supply)?;
+
+        let requirements = dao.get_proposal_requirements(&proposal_instructions.instructions);
 
         for base_liquidity in [pass_base_liquidity, fail_base_liquidity] {
             require_gte!(
                 base_liquidity,
-                dao.min_base_futarchic_liquidity,
+                requirements.min_base_futarchic_liquidity,
                 AutocratError::InsufficientLpTokenLock
             );
         }
@@ -165,7 +244,7 @@ impl InitializeProposal<'_> {
         for quote_liquidity in [pass_quote_liquidity, fail_quote_liquidity] {
             require_gte!(
                 quote_liquidity,
-                dao.min_quote_futarchic_liquidity,
+                requirements.min_quote_futarchic_liquidity,
                 AutocratError::InsufficientLpTokenLock
             );
         }
@@ -195,17 +274,50 @@ impl InitializeProposal<'_> {
             )?;
         }
 
+        if let Some(bond) = dao.proposal_bond {
+            let (Some(bond_mint), Some(bond_user_account), Some(bond_escrow)) =
+                (bond_mint, bond_user_account, bond_escrow)
+            else {
+                return err!(AutocratError::MissingBondAccounts);
+            };
+
+            require_keys_eq!(bond_mint.key(), bond.mint, AutocratError::InvalidBondMint);
+
+            token::transfer(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    Transfer {
+                        from: bond_user_account.to_account_info(),
+                        to: bond_escrow.to_account_info(),
+                        authority: proposer.to_account_info(),
+                    },
+                ),
+                bond.amount,
+            )?;
+        }
+
         let clock = Clock::get()?;
 
+        proposer_record.initialize_if_new(dao.key(), proposer.key(), ctx.bumps.proposer_record);
+        proposer_record.open_proposal(dao)?;
+
         dao.proposal_count += 1;
 
+        // so that what the markets are trading on can't change underneath them
+        proposal_instructions.frozen = true;
+
         proposal.set_inner(Proposal {
+            version: PROPOSAL_VERSION,
             number: dao.proposal_count,
             proposer: proposer.key(),
             description_url,
             slot_enqueued: clock.slot,
+            slot_finalized: 0,
             state: ProposalState::Pending,
-            instruction,
+            instructions: proposal_instructions.key(),
+            num_instructions_executed: 0,
+            pass_threshold_bps: requirements.pass_threshold_bps,
+            slots_per_proposal: requirements.slots_per_proposal,
             pass_amm: pass_amm.key(),
             fail_amm: fail_amm.key(),
             base_vault: base_vault.key(),
@@ -216,6 +328,43 @@ impl InitializeProposal<'_> {
             nonce,
             pda_bump: ctx.bumps.proposal,
             question: question.key(),
+            bond: dao.proposal_bond,
+            bond_settled: false,
+            spot_oracle_snapshot: spot_amm
+                .as_deref()
+                .map(|spot_amm| SpotOracleSnapshot::new(spot_amm)),
+            spot_twap: None,
+            market_snapshots: [
+                MarketSnapshot::new(fail_amm, clock.slot),
+                MarketSnapshot::new(pass_amm, clock.slot),
+            ],
+            quote_volumes: [0; 2],
+            treasury_lp_tokens: [0; 2],
+            treasury_max_price_deviation_bps: 0,
+            decision: None,
+            dao_snapshot: dao.snapshot(),
+            spending_limits: dao.spending_limits_snapshot(),
+            treasury_outflows: vec![],
+        });
+
+        emit_cpi!(InitializeProposalEvent {
+            common: CommonFields::new(&clock),
+            proposal: proposal.key(),
+            dao: proposal.dao,
+            proposer: proposal.proposer,
+            number: proposal.number,
+            description_url: proposal.description_url.clone(),
+            instructions: proposal.instructions,
+            question: proposal.question,
+            base_vault: proposal.base_vault,
+            quote_vault: proposal.quote_vault,
+            pass_amm: proposal.pass_amm,
+            fail_amm: proposal.fail_amm,
+            pass_lp_tokens_locked: proposal.pass_lp_tokens_locked,
+            fail_lp_tokens_locked: proposal.fail_lp_tokens_locked,
+            pass_threshold_bps: proposal.pass_threshold_bps,
+            slots_per_proposal: proposal.slots_per_proposal,
+            nonce: proposal.nonce,
         });
 
         Ok(())
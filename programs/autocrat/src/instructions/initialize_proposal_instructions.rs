@@ -0,0 +1,25 @@
+use super::*;
+
+#[derive(Accounts)]
+pub struct InitializeProposalInstructions<'info> {
+    pub authority: Signer<'info>,
+    #[account(zero, signer)]
+    pub proposal_instructions: Box<Account<'info, ProposalInstructions>>,
+}
+
+impl InitializeProposalInstructions<'_> {
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let Self {
+            authority,
+            proposal_instructions,
+        } = ctx.accounts;
+
+        proposal_instructions.set_inner(ProposalInstructions {
+            authority: authority.key(),
+            frozen: false,
+            instructions: vec![],
+        });
+
+        Ok(())
+    }
+}
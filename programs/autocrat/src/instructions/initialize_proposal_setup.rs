@@ -0,0 +1,161 @@
+use super::*;
+
+use anchor_spl::associated_token::{self, AssociatedToken, Create};
+use conditional_vault::cpi::accounts as vault_accounts;
+use conditional_vault::InitializeQuestionArgs;
+
+/// The remaining accounts are the conditional token mints of the base vault,
+/// followed by the conditional token mints of the quote vault.
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct InitializeProposalSetup<'info> {
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + std::mem::size_of::<ProposalSetup>(),
+        seeds = [b"proposal_setup", proposer.key().as_ref(), &nonce.to_le_bytes()],
+        bump
+    )]
+    pub proposal_setup: Box<Account<'info, ProposalSetup>>,
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    /// CHECK: initialized by the vault program
+    #[account(mut)]
+    pub question: UncheckedAccount<'info>,
+    /// CHECK: initialized by the vault program
+    #[account(mut)]
+    pub base_vault: UncheckedAccount<'info>,
+    /// CHECK: initialized by the vault program
+    #[account(mut)]
+    pub quote_vault: UncheckedAccount<'info>,
+    #[account(address = dao.token_mint)]
+    pub token_mint: Box<Account<'info, Mint>>,
+    #[account(address = dao.usdc_mint)]
+    pub usdc_mint: Box<Account<'info, Mint>>,
+    /// CHECK: initialized by the associated token program
+    #[account(mut)]
+    pub base_vault_underlying_token_account: UncheckedAccount<'info>,
+    /// CHECK: initialized by the associated token program
+    #[account(mut)]
+    pub quote_vault_underlying_token_account: UncheckedAccount<'info>,
+    pub vault_program: Program<'info, ConditionalVaultProgram>,
+    /// CHECK: checked by vault program
+    pub vault_event_authority: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info, 'c: 'info> InitializeProposalSetup<'info> {
+    pub fn handle(ctx: Context<'_, '_, 'c, 'info, Self>, nonce: u64) -> Result<()> {
+        let Self {
+            proposal_setup,
+            dao,
+            proposer,
+            question,
+            base_vault,
+            quote_vault,
+            token_mint,
+            usdc_mint,
+            base_vault_underlying_token_account,
+            quote_vault_underlying_token_account,
+            vault_program,
+            vault_event_authority,
+            token_program,
+            associated_token_program,
+            system_program,
+        } = ctx.accounts;
+
+        require_eq!(
+            ctx.remaining_accounts.len(),
+            4,
+            AutocratError::InvalidOptionAccounts
+        );
+
+        let (proposal, _) = pda::find_proposal_address(&proposer.key(), nonce);
+
+        conditional_vault::cpi::initialize_question(
+            CpiContext::new(
+                vault_program.to_account_info(),
+                vault_accounts::InitializeQuestion {
+                    question: question.to_account_info(),
+                    payer: proposer.to_account_info(),
+                    system_program: system_program.to_account_info(),
+                    event_authority: vault_event_authority.to_account_info(),
+                    program: vault_program.to_account_info(),
+                },
+            ),
+            InitializeQuestionArgs {
+                question_id: pda::proposal_question_id(&proposal),
+                oracle: proposal,
+                num_outcomes: PROPOSAL_NUM_OUTCOMES,
+            },
+        )?;
+
+        let (base_conditional_mints, quote_conditional_mints) = ctx.remaining_accounts.split_at(2);
+
+        for (vault, underlying_token_mint, vault_underlying_token_account, conditional_mints) in [
+            (
+                &base_vault,
+                token_mint.to_account_info(),
+                &base_vault_underlying_token_account,
+                base_conditional_mints,
+            ),
+            (
+                &quote_vault,
+                usdc_mint.to_account_info(),
+                &quote_vault_underlying_token_account,
+                quote_conditional_mints,
+            ),
+        ] {
+            associated_token::create_idempotent(CpiContext::new(
+                associated_token_program.to_account_info(),
+                Create {
+                    payer: proposer.to_account_info(),
+                    associated_token: vault_underlying_token_account.to_account_info(),
+                    authority: vault.to_account_info(),
+                    mint: underlying_token_mint.clone(),
+                    system_program: system_program.to_account_info(),
+                    token_program: token_program.to_account_info(),
+                },
+            ))?;
+
+            conditional_vault::cpi::initialize_conditional_vault(
+                CpiContext::new(
+                    vault_program.to_account_info(),
+                    vault_accounts::InitializeConditionalVault {
+                        vault: vault.to_account_info(),
+                        question: question.to_account_info(),
+                        underlying_token_mint,
+                        vault_underlying_token_account: vault_underlying_token_account
+                            .to_account_info(),
+                        payer: proposer.to_account_info(),
+                        token_program: token_program.to_account_info(),
+                        associated_token_program: associated_token_program.to_account_info(),
+                        system_program: system_program.to_account_info(),
+                        event_authority: vault_event_authority.to_account_info(),
+                        program: vault_program.to_account_info(),
+                    },
+                )
+                .with_remaining_accounts(conditional_mints.to_vec()),
+            )?;
+        }
+
+        proposal_setup.set_inner(ProposalSetup {
+            proposer: proposer.key(),
+            dao: dao.key(),
+            nonce,
+            proposal,
+            stage: ProposalSetupStage::CreatePassAmm,
+            question: question.key(),
+            base_vault: base_vault.key(),
+            quote_vault: quote_vault.key(),
+            pass_amm: Pubkey::default(),
+            fail_amm: Pubkey::default(),
+            pda_bump: ctx.bumps.proposal_setup,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use super::*;
+
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+use anchor_lang::Discriminator;
+
+#[derive(Accounts)]
+pub struct MigrateDao<'info> {
+    /// CHECK: deserialized in `handle`, since it isn't in the current layout
+    #[account(mut, owner = crate::ID)]
+    pub dao: UncheckedAccount<'info>,
+    /// Pays for the DAO's account to grow to the current layout.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateDao<'_> {
+    /// Grows a DAO created before its config had anything past the TWAP and
+    /// liquidity settings, and fills in the rest of its config with defaults
+    /// that keep its proposals working the way they did before. That's the
+    /// same as `initialize_dao`'s defaults, except that passed proposals never
+    /// expire, since they couldn't before. Anyone can call this, since the
+    /// defaults don't change how the DAO's proposals work.
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let Self {
+            dao,
+            payer,
+            system_program,
+        } = ctx.accounts;
+
+        let old_dao = {
+            let data = dao.try_borrow_data()?;
+
+            require!(
+                data.len() >= 8 && data[..8] == Dao::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+
+            // old DAOs were allocated with exactly this much space, and newer
+            // ones with `Dao::space()`
+            require_eq!(
+                data.len(),
+                8 + std::mem::size_of::<DaoV0>(),
+                AutocratError::DaoAlreadyMigrated
+            );
+
+            DaoV0::deserialize(&mut &data[8..])?
+        };
+
+        let migrated = Dao {
+            treasury_pda_bump: old_dao.treasury_pda_bump,
+            treasury: old_dao.treasury,
+            token_mint: old_dao.token_mint,
+            usdc_mint: old_dao.usdc_mint,
+            proposal_count: old_dao.proposal_count,
+            pass_threshold_bps: old_dao.pass_threshold_bps,
+            slots_per_proposal: old_dao.slots_per_proposal,
+            twap_initial_observation: old_dao.twap_initial_observation,
+            twap_max_observation_change_per_update: old_dao.twap_max_observation_change_per_update,
+            min_quote_futarchic_liquidity: old_dao.min_quote_futarchic_liquidity,
+            min_base_futarchic_liquidity: old_dao.min_base_futarchic_liquidity,
+            min_average_quote_liquidity: 0,
+            execution_delay_slots: 0,
+            veto_authority: None,
+            // `execution_end_slot` saturates, so this is no window at all
+            execution_window_slots: u64::MAX,
+            decision_rule: DecisionRule::default(),
+            proposal_categories: vec![],
+            proposal_bond: None,
+            // proposals that were already pending aren't counted, which
+            // `ProposerRecord::close_proposal` allows for
+            active_proposal_count: 0,
+            max_active_proposals: None,
+            max_active_proposals_per_proposer: None,
+            spot_amm: None,
+            treasury_liquidity: None,
+            spending_limits: vec![],
+        };
+
+        let space = Dao::space();
+        let rent_exempt_lamports = Rent::get()?.minimum_balance(space);
+
+        if rent_exempt_lamports > dao.lamports() {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    SystemTransfer {
+                        from: payer.to_account_info(),
+                        to: dao.to_account_info(),
+                    },
+                ),
+                rent_exempt_lamports - dao.lamports(),
+            )?;
+        }
+
+        dao.realloc(space, true)?;
+
+        let mut data = dao.try_borrow_mut_data()?;
+        migrated.try_serialize(&mut &mut data[..])?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use super::*;
+
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+use anchor_lang::Discriminator;
+
+#[derive(Accounts)]
+pub struct MigrateProposal<'info> {
+    /// CHECK: deserialized in `handle`, since it may not be in the current layout
+    #[account(mut, owner = crate::ID)]
+    pub proposal: UncheckedAccount<'info>,
+    pub dao: Box<Account<'info, Dao>>,
+    /// Receives the proposal's instruction, which older proposals stored inline.
+    #[account(zero, signer)]
+    pub proposal_instructions: Box<Account<'info, ProposalInstructions>>,
+    /// Pays for the proposal's account to grow to the current layout.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateProposal<'_> {
+    /// Rewrites a finalized proposal created before proposals were versioned
+    /// into the current layout, so that it can be executed and read by this
+    /// version of autocrat. Anyone can call this, since it doesn't change what
+    /// the proposal does.
+    ///
+    /// Older proposals didn't store their threshold, duration or any of the
+    /// DAO's config, so they get the DAO's current ones, and they don't have a
+    /// recorded decision.
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let Self {
+            proposal,
+            dao,
+            proposal_instructions,
+            payer,
+            system_program,
+        } = ctx.accounts;
+
+        let old_proposal = {
+            let data = proposal.try_borrow_data()?;
+
+            require!(
+                data.len() >= 8 && data[..8] == Proposal::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+
+            // an old proposal's first byte could happen to match, so also check
+            // that the rest of it is in the current layout
+            require!(
+                data.get(8) != Some(&PROPOSAL_VERSION)
+                    || Proposal::try_deserialize(&mut &data[..]).is_err(),
+                AutocratError::ProposalAlreadyMigrated
+            );
+
+            ProposalV0::deserialize(&mut &data[8..])?
+        };
+
+        require_keys_eq!(old_proposal.dao, dao.key(), ErrorCode::ConstraintHasOne);
+
+        // pending proposals would also need their markets migrated and their
+        // proposer's record created before they could be finalized, so they
+        // need to be finalized by the version of autocrat that created them
+        require!(
+            old_proposal.state != ProposalState::Pending,
+            AutocratError::CannotMigratePendingProposal
+        );
+
+        let num_instructions_executed = match old_proposal.state {
+            ProposalState::Executed => 1,
+            _ => 0,
+        };
+
+        proposal_instructions.set_inner(ProposalInstructions {
+            authority: old_proposal.proposer,
+            frozen: true,
+            instructions: vec![old_proposal.instruction],
+        });
+
+        let migrated = Proposal {
+            version: PROPOSAL_VERSION,
+            number: old_proposal.number,
+            proposer: old_proposal.proposer,
+            slot_enqueued: old_proposal.slot_enqueued,
+            slot_finalized: 0,
+            state: old_proposal.state,
+            instructions: proposal_instructions.key(),
+            num_instructions_executed,
+            pass_threshold_bps: dao.pass_threshold_bps,
+            slots_per_proposal: dao.slots_per_proposal,
+            pass_amm: old_proposal.pass_amm,
+            fail_amm: old_proposal.fail_amm,
+            base_vault: old_proposal.base_vault,
+            quote_vault: old_proposal.quote_vault,
+            dao: old_proposal.dao,
+            pass_lp_tokens_locked: old_proposal.pass_lp_tokens_locked,
+            fail_lp_tokens_locked: old_proposal.fail_lp_tokens_locked,
+            nonce: old_proposal.nonce,
+            pda_bump: old_proposal.pda_bump,
+            question: old_proposal.question,
+            bond: None,
+            bond_settled: false,
+            spot_oracle_snapshot: None,
+            spot_twap: None,
+            market_snapshots: [MarketSnapshot::default(); 2],
+            quote_volumes: [0; 2],
+            treasury_lp_tokens: [0; 2],
+            treasury_max_price_deviation_bps: 0,
+            decision: None,
+            dao_snapshot: dao.snapshot(),
+            spending_limits: dao.spending_limits_snapshot(),
+            treasury_outflows: vec![],
+            description_url: old_proposal.description_url,
+        };
+
+        let space = Proposal::space(&migrated.description_url);
+        let rent_exempt_lamports = Rent::get()?.minimum_balance(space);
+
+        if rent_exempt_lamports > proposal.lamports() {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    SystemTransfer {
+                        from: payer.to_account_info(),
+                        to: proposal.to_account_info(),
+                    },
+                ),
+                rent_exempt_lamports - proposal.lamports(),
+            )?;
+        }
+
+        if space > proposal.data_len() {
+            proposal.realloc(space, true)?;
+        }
+
+        let mut data = proposal.try_borrow_mut_data()?;
+        migrated.try_serialize(&mut &mut data[..])?;
+
+        Ok(())
+    }
+}
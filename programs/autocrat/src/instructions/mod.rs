@@ -1,13 +1,57 @@
 use super::*;
 
+pub mod add_proposal_category;
+pub mod add_proposal_instruction;
+pub mod cancel_multi_option_proposal;
+pub mod cancel_proposal;
+pub mod execute_multi_option_proposal;
 pub mod execute_proposal;
+pub mod expire_multi_option_proposal;
+pub mod expire_proposal;
+pub mod finalize_multi_option_proposal;
 pub mod finalize_proposal;
 pub mod initialize_dao;
+pub mod initialize_multi_option_proposal;
 pub mod initialize_proposal;
+pub mod initialize_proposal_instructions;
+pub mod initialize_proposal_setup;
+pub mod migrate_dao;
+pub mod migrate_proposal;
+pub mod remove_proposal_category;
+pub mod remove_spending_limit;
+pub mod seed_proposal_market;
+pub mod set_spending_limit;
+pub mod settle_proposal_bond;
+pub mod settle_treasury_positions;
+pub mod setup_proposal_amm;
 pub mod update_dao;
+pub mod veto_multi_option_proposal;
+pub mod veto_proposal;
 
+pub use add_proposal_category::*;
+pub use add_proposal_instruction::*;
+pub use cancel_multi_option_proposal::*;
+pub use cancel_proposal::*;
+pub use execute_multi_option_proposal::*;
 pub use execute_proposal::*;
+pub use expire_multi_option_proposal::*;
+pub use expire_proposal::*;
+pub use finalize_multi_option_proposal::*;
 pub use finalize_proposal::*;
 pub use initialize_dao::*;
+pub use initialize_multi_option_proposal::*;
 pub use initialize_proposal::*;
+pub use initialize_proposal_instructions::*;
+pub use initialize_proposal_setup::*;
+pub use migrate_dao::*;
+pub use migrate_proposal::*;
+pub use remove_proposal_category::*;
+pub use remove_spending_limit::*;
+pub use seed_proposal_market::*;
+pub use set_spending_limit::*;
+pub use settle_proposal_bond::*;
+pub use settle_treasury_positions::*;
+pub use setup_proposal_amm::*;
 pub use update_dao::*;
+pub use veto_multi_option_proposal::*;
+pub use veto_proposal::*;
@@ -0,0 +1,21 @@
+use super::*;
+
+#[derive(Accounts)]
+pub struct RemoveProposalCategory<'info> {
+    #[account(mut, has_one = treasury)]
+    pub dao: Account<'info, Dao>,
+    pub treasury: Signer<'info>,
+}
+
+impl RemoveProposalCategory<'_> {
+    /// Removes the category that matches `program_id` and `data_prefix`, if
+    /// there is one.
+    pub fn handle(ctx: Context<Self>, program_id: Pubkey, data_prefix: Vec<u8>) -> Result<()> {
+        ctx.accounts.dao.proposal_categories.retain(|category| {
+            category.program_id != program_id
+                || category.data_prefix[..category.data_prefix_len as usize] != data_prefix[..]
+        });
+
+        Ok(())
+    }
+}
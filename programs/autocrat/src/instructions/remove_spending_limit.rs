@@ -0,0 +1,31 @@
+use super::*;
+
+#[derive(Accounts)]
+pub struct RemoveSpendingLimit<'info> {
+    #[account(mut, has_one = treasury)]
+    pub dao: Account<'info, Dao>,
+    pub treasury: Signer<'info>,
+}
+
+impl RemoveSpendingLimit<'_> {
+    /// Removes the spending limit for `mint`, if there is one. Proposals that
+    /// already exist keep being checked against it. If anything's been spent
+    /// in its window, the window is kept until it's empty so that setting the
+    /// limit again doesn't reset it.
+    pub fn handle(ctx: Context<Self>, mint: Pubkey) -> Result<()> {
+        let dao = &mut ctx.accounts.dao;
+
+        if let Some(spending_limit) = dao
+            .spending_limits
+            .iter_mut()
+            .find(|spending_limit| spending_limit.mint == mint)
+        {
+            spending_limit.max_per_proposal = None;
+            spending_limit.max_per_window = None;
+        }
+
+        dao.prune_spending_limits(Clock::get()?.slot);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,324 @@
+use super::*;
+
+use amm::instructions::AddLiquidityArgs;
+use amm::program::Amm as AmmProgram;
+use anchor_spl::associated_token::get_associated_token_address;
+use conditional_vault::cpi::accounts::InteractWithVault;
+
+/// Checks that a market's price is within `max_price_deviation_bps` of its TWAP
+/// oracle's last observation. Otherwise, someone could push the price away
+/// just before the treasury adds or removes liquidity and then trade it back
+/// against the treasury.
+pub fn validate_market_price(amm: &Amm, max_price_deviation_bps: u16) -> Result<()> {
+    let spot_price = amm.get_spot_price()?;
+    let last_observation = amm.oracle.last_observation;
+    let max_deviation =
+        last_observation.saturating_mul(max_price_deviation_bps.into()) / MAX_BPS as u128;
+
+    require!(
+        spot_price.abs_diff(last_observation) <= max_deviation,
+        AutocratError::MarketPriceOutOfRange
+    );
+
+    Ok(())
+}
+
+/// The remaining accounts are, for the base vault and then the quote vault,
+/// `[conditional_mint_0, conditional_mint_1, treasury_conditional_account_0,
+/// treasury_conditional_account_1]`. The treasury's conditional token accounts
+/// and LP token account need to be created beforehand.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SeedProposalMarket<'info> {
+    #[account(
+        mut,
+        has_one = dao,
+        has_one = question,
+        has_one = base_vault,
+        has_one = quote_vault,
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(has_one = treasury)]
+    pub dao: Box<Account<'info, Dao>>,
+    /// CHECK: checked by `has_one`
+    pub treasury: UncheckedAccount<'info>,
+    pub question: Box<Account<'info, Question>>,
+    #[account(mut)]
+    pub base_vault: Box<Account<'info, ConditionalVaultAccount>>,
+    #[account(mut)]
+    pub quote_vault: Box<Account<'info, ConditionalVaultAccount>>,
+    /// CHECK: checked by the vault program
+    #[account(mut)]
+    pub base_vault_underlying_token_account: UncheckedAccount<'info>,
+    /// CHECK: checked by the vault program
+    #[account(mut)]
+    pub quote_vault_underlying_token_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = dao.token_mint,
+        associated_token::authority = treasury,
+    )]
+    pub treasury_base_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = dao.usdc_mint,
+        associated_token::authority = treasury,
+    )]
+    pub treasury_quote_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = amm.key() == proposal.pass_amm || amm.key() == proposal.fail_amm
+            @ AutocratError::InvalidSeedMarket,
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+    #[account(mut, address = amm.lp_mint)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    /// CHECK: checked by the AMM program
+    #[account(mut)]
+    pub amm_vault_ata_base: UncheckedAccount<'info>,
+    /// CHECK: checked by the AMM program
+    #[account(mut)]
+    pub amm_vault_ata_quote: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = amm.lp_mint,
+        associated_token::authority = treasury,
+    )]
+    pub treasury_lp_account: Box<Account<'info, TokenAccount>>,
+    pub vault_program: Program<'info, ConditionalVaultProgram>,
+    /// CHECK: checked by vault program
+    pub vault_event_authority: UncheckedAccount<'info>,
+    pub amm_program: Program<'info, AmmProgram>,
+    /// CHECK: checked by the AMM program
+    pub amm_event_authority: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info, 'c: 'info> SeedProposalMarket<'info> {
+    fn outcome_index(&self) -> usize {
+        if self.amm.key() == self.proposal.pass_amm {
+            PASS_INDEX
+        } else {
+            FAIL_INDEX
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.proposal.state == ProposalState::Pending,
+            AutocratError::ProposalAlreadyFinalized
+        );
+
+        require_eq!(
+            self.proposal.treasury_lp_tokens[self.outcome_index()],
+            0,
+            AutocratError::MarketAlreadySeeded
+        );
+
+        let Some(treasury_liquidity) = self.dao.treasury_liquidity else {
+            return err!(AutocratError::TreasuryLiquidityDisabled);
+        };
+
+        validate_market_price(&self.amm, treasury_liquidity.max_price_deviation_bps)
+    }
+
+    /// The LP tokens that the market's reserves give for the treasury's
+    /// liquidity. Markets always have the proposer's liquidity by the time
+    /// they're seeded, and the AMM needs a nonzero minimum once they do.
+    fn min_lp_tokens(&self, treasury_liquidity: &TreasuryLiquidity) -> Result<u64> {
+        let (lp_tokens, _) = self
+            .amm
+            .get_liquidity_to_add(treasury_liquidity.quote_amount, self.lp_mint.supply)?;
+
+        Ok(lp_tokens)
+    }
+
+    /// Splits as many of the treasury's tokens as it needs and adds them as
+    /// liquidity to one of the proposal's markets, up to the DAO's
+    /// `treasury_liquidity`. Conditional tokens left over from seeding one
+    /// market are used for the other, so seeding both only splits once.
+    pub fn handle(ctx: Context<'_, '_, 'c, 'info, Self>) -> Result<()> {
+        let outcome_index = ctx.accounts.outcome_index();
+
+        let Some(treasury_liquidity) = ctx.accounts.dao.treasury_liquidity else {
+            return err!(AutocratError::TreasuryLiquidityDisabled);
+        };
+        let min_lp_tokens = ctx.accounts.min_lp_tokens(&treasury_liquidity)?;
+
+        let Self {
+            proposal,
+            dao,
+            treasury,
+            question,
+            base_vault,
+            quote_vault,
+            base_vault_underlying_token_account,
+            quote_vault_underlying_token_account,
+            treasury_base_account,
+            treasury_quote_account,
+            amm,
+            lp_mint,
+            amm_vault_ata_base,
+            amm_vault_ata_quote,
+            treasury_lp_account,
+            vault_program,
+            vault_event_authority,
+            amm_program,
+            amm_event_authority,
+            token_program,
+            event_authority: _,
+            program: _,
+        } = ctx.accounts;
+
+        require_eq!(
+            ctx.remaining_accounts.len(),
+            8,
+            AutocratError::InvalidOptionAccounts
+        );
+
+        let dao_key = dao.key();
+        let treasury_seeds = &[dao_key.as_ref(), &[dao.treasury_pda_bump]];
+        let treasury_signer = &[&treasury_seeds[..]];
+
+        let (base_accounts, quote_accounts) = ctx.remaining_accounts.split_at(4);
+
+        let mut treasury_conditional_accounts = Vec::with_capacity(2);
+
+        for (
+            vault,
+            vault_underlying_token_account,
+            treasury_underlying_account,
+            accounts,
+            needed,
+        ) in [
+            (
+                base_vault,
+                base_vault_underlying_token_account,
+                treasury_base_account,
+                base_accounts,
+                treasury_liquidity.max_base_amount,
+            ),
+            (
+                quote_vault,
+                quote_vault_underlying_token_account,
+                treasury_quote_account,
+                quote_accounts,
+                treasury_liquidity.quote_amount,
+            ),
+        ] {
+            for (i, account) in accounts[2..].iter().enumerate() {
+                require_keys_eq!(
+                    account.key(),
+                    get_associated_token_address(
+                        &treasury.key(),
+                        &vault.conditional_token_mints[i]
+                    ),
+                    AutocratError::InvalidOptionAccounts
+                );
+            }
+
+            let treasury_conditional_account = &accounts[2 + outcome_index];
+            let balance = Account::<TokenAccount>::try_from(treasury_conditional_account)?.amount;
+
+            let shortfall = needed.saturating_sub(balance);
+
+            if shortfall > 0 {
+                conditional_vault::cpi::split_tokens(
+                    CpiContext::new(
+                        vault_program.to_account_info(),
+                        InteractWithVault {
+                            question: question.to_account_info(),
+                            vault: vault.to_account_info(),
+                            vault_underlying_token_account: vault_underlying_token_account
+                                .to_account_info(),
+                            authority: treasury.to_account_info(),
+                            user_underlying_token_account: treasury_underlying_account
+                                .to_account_info(),
+                            token_program: token_program.to_account_info(),
+                            event_authority: vault_event_authority.to_account_info(),
+                            program: vault_program.to_account_info(),
+                        },
+                    )
+                    .with_remaining_accounts(accounts.to_vec())
+                    .with_signer(treasury_signer),
+                    shortfall,
+                )?;
+            }
+
+            treasury_conditional_accounts.push(treasury_conditional_account.clone());
+        }
+
+        let lp_balance_before = treasury_lp_account.amount;
+
+        amm::cpi::add_liquidity(
+            CpiContext::new(
+                amm_program.to_account_info(),
+                amm::cpi::accounts::AddOrRemoveLiquidity {
+                    user: treasury.to_account_info(),
+                    amm: amm.to_account_info(),
+                    lp_mint: lp_mint.to_account_info(),
+                    user_lp_account: treasury_lp_account.to_account_info(),
+                    user_base_account: treasury_conditional_accounts[0].clone(),
+                    user_quote_account: treasury_conditional_accounts[1].clone(),
+                    vault_ata_base: amm_vault_ata_base.to_account_info(),
+                    vault_ata_quote: amm_vault_ata_quote.to_account_info(),
+                    token_program: token_program.to_account_info(),
+                    event_authority: amm_event_authority.to_account_info(),
+                    program: amm_program.to_account_info(),
+                },
+            )
+            .with_signer(treasury_signer),
+            AddLiquidityArgs {
+                quote_amount: treasury_liquidity.quote_amount,
+                max_base_amount: treasury_liquidity.max_base_amount,
+                min_lp_tokens,
+            },
+        )?;
+
+        treasury_lp_account.reload()?;
+
+        proposal.treasury_lp_tokens[outcome_index] = treasury_lp_account.amount - lp_balance_before;
+        proposal.treasury_max_price_deviation_bps = treasury_liquidity.max_price_deviation_bps;
+
+        let clock = Clock::get()?;
+        emit_cpi!(SeedProposalMarketEvent {
+            common: CommonFields::new(&clock),
+            proposal: proposal.key(),
+            dao: proposal.dao,
+            amm: amm.key(),
+            lp_tokens: proposal.treasury_lp_tokens[outcome_index],
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use amm::state::PRICE_SCALE;
+
+    #[test]
+    fn market_price_has_to_be_near_the_oracle() {
+        let mut amm = Amm {
+            base_amount: 1_000,
+            quote_amount: 1_000,
+            ..Default::default()
+        };
+        amm.oracle.last_observation = PRICE_SCALE;
+
+        assert!(validate_market_price(&amm, 500).is_ok());
+
+        // 5% above the observation is fine, a bit more isn't
+        amm.quote_amount = 1_050;
+        assert!(validate_market_price(&amm, 500).is_ok());
+        amm.quote_amount = 1_051;
+        assert!(validate_market_price(&amm, 500).is_err());
+
+        amm.quote_amount = 950;
+        assert!(validate_market_price(&amm, 500).is_ok());
+        amm.quote_amount = 949;
+        assert!(validate_market_price(&amm, 500).is_err());
+    }
+}
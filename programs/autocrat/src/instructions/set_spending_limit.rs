@@ -0,0 +1,69 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub struct SetSpendingLimitParams {
+    pub mint: Pubkey,
+    pub max_per_proposal: Option<u64>,
+    pub max_per_window: Option<u64>,
+    pub window_slots: u64,
+}
+
+#[derive(Accounts)]
+pub struct SetSpendingLimit<'info> {
+    #[account(mut, has_one = treasury)]
+    pub dao: Account<'info, Dao>,
+    pub treasury: Signer<'info>,
+}
+
+impl SetSpendingLimit<'_> {
+    /// Adds a spending limit for a mint, or replaces the mint's existing one.
+    /// Replacing a limit keeps what's been spent in its current window, so
+    /// that a proposal can't reset the window by setting the same limit again.
+    /// Proposals that already exist keep being checked against the limit they
+    /// snapshotted, so this can only loosen the limit for later proposals.
+    pub fn handle(ctx: Context<Self>, params: SetSpendingLimitParams) -> Result<()> {
+        let dao = &mut ctx.accounts.dao;
+        let slot = Clock::get()?.slot;
+
+        let SetSpendingLimitParams {
+            mint,
+            max_per_proposal,
+            max_per_window,
+            window_slots,
+        } = params;
+
+        require_neq!(window_slots, 0, AutocratError::InvalidSpendingLimit);
+
+        let existing_limit = dao
+            .spending_limits
+            .iter_mut()
+            .find(|spending_limit| spending_limit.mint == mint);
+
+        match existing_limit {
+            Some(existing_limit) => {
+                existing_limit.max_per_proposal = max_per_proposal;
+                existing_limit.max_per_window = max_per_window;
+                existing_limit.set_window_slots(window_slots, slot);
+            }
+            None => {
+                dao.prune_spending_limits(slot);
+                require_gt!(
+                    MAX_SPENDING_LIMITS,
+                    dao.spending_limits.len(),
+                    AutocratError::TooManySpendingLimits
+                );
+
+                dao.spending_limits.push(SpendingLimit::new(
+                    mint,
+                    max_per_proposal,
+                    max_per_window,
+                    window_slots,
+                ));
+            }
+        }
+
+        dao.validate_params()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+use super::*;
+
+use anchor_spl::token::{close_account, CloseAccount};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleProposalBond<'info> {
+    #[account(mut,
+        has_one = dao,
+        has_one = proposer,
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(has_one = treasury)]
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(
+        mut,
+        seeds = [b"bond", proposal.key().as_ref()],
+        bump,
+    )]
+    pub bond_escrow: Box<Account<'info, TokenAccount>>,
+    /// CHECK: receives the bond escrow's rent, checked by `has_one`
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        token::mint = bond_escrow.mint,
+        token::authority = proposer,
+    )]
+    pub proposer_bond_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: it's okay
+    pub treasury: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        token::mint = bond_escrow.mint,
+        token::authority = treasury,
+    )]
+    pub treasury_bond_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl SettleProposalBond<'_> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.proposal.bond.is_some() && !self.proposal.bond_settled,
+            AutocratError::NoBondToSettle
+        );
+
+        require!(
+            self.proposal.state != ProposalState::Pending,
+            AutocratError::ProposalNotFinalized
+        );
+
+        Ok(())
+    }
+
+    /// Returns the bond to the proposer if their proposal passed or enough was
+    /// traded in its markets, and sends it to the treasury otherwise.
+    /// Permissionless.
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let SettleProposalBond {
+            proposal,
+            dao: _,
+            bond_escrow,
+            proposer,
+            proposer_bond_account,
+            treasury: _,
+            treasury_bond_account,
+            token_program,
+            event_authority: _,
+            program: _,
+        } = ctx.accounts;
+
+        // safe because of the check in `validate`
+        let bond = proposal.bond.unwrap();
+
+        // the volumes were recorded when the proposal left `Pending`, so
+        // trading after that can't change whether the bond is refunded
+        let bond_is_refunded = bond.is_refunded(
+            proposal.state,
+            proposal.quote_volumes[PASS_INDEX],
+            proposal.quote_volumes[FAIL_INDEX],
+        );
+
+        let bond_recipient = if bond_is_refunded {
+            proposer_bond_account
+        } else {
+            treasury_bond_account
+        };
+
+        proposal.bond_settled = true;
+        let bond_amount = bond_escrow.amount;
+
+        let proposer_key = proposal.proposer;
+        let nonce = proposal.nonce;
+        let proposal_seeds = &[
+            b"proposal",
+            proposer_key.as_ref(),
+            &nonce.to_le_bytes(),
+            &[proposal.pda_bump],
+        ];
+        let proposal_signer = &[&proposal_seeds[..]];
+
+        token::transfer(
+            CpiContext::new(
+                token_program.to_account_info(),
+                Transfer {
+                    from: bond_escrow.to_account_info(),
+                    to: bond_recipient.to_account_info(),
+                    authority: proposal.to_account_info(),
+                },
+            )
+            .with_signer(proposal_signer),
+            bond_amount,
+        )?;
+
+        // the proposer paid for the escrow, so they get its rent back
+        close_account(
+            CpiContext::new(
+                token_program.to_account_info(),
+                CloseAccount {
+                    account: bond_escrow.to_account_info(),
+                    destination: proposer.to_account_info(),
+                    authority: proposal.to_account_info(),
+                },
+            )
+            .with_signer(proposal_signer),
+        )?;
+
+        let clock = Clock::get()?;
+        emit_cpi!(SettleProposalBondEvent {
+            common: CommonFields::new(&clock),
+            proposal: proposal.key(),
+            dao: proposal.dao,
+            proposer: proposal.proposer,
+            bond_mint: bond.mint,
+            amount: bond_amount,
+            refunded: bond_is_refunded,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+use super::*;
+
+use amm::instructions::RemoveLiquidityArgs;
+use amm::program::Amm as AmmProgram;
+use anchor_spl::associated_token::get_associated_token_address;
+use conditional_vault::cpi::accounts::InteractWithVault;
+
+/// Takes the same remaining accounts as `seed_proposal_market`.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleTreasuryPositions<'info> {
+    #[account(
+        mut,
+        has_one = dao,
+        has_one = question,
+        has_one = base_vault,
+        has_one = quote_vault,
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(has_one = treasury)]
+    pub dao: Box<Account<'info, Dao>>,
+    /// CHECK: checked by `has_one`
+    pub treasury: UncheckedAccount<'info>,
+    pub question: Box<Account<'info, Question>>,
+    #[account(mut)]
+    pub base_vault: Box<Account<'info, ConditionalVaultAccount>>,
+    #[account(mut)]
+    pub quote_vault: Box<Account<'info, ConditionalVaultAccount>>,
+    /// CHECK: checked by the vault program
+    #[account(mut)]
+    pub base_vault_underlying_token_account: UncheckedAccount<'info>,
+    /// CHECK: checked by the vault program
+    #[account(mut)]
+    pub quote_vault_underlying_token_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = dao.token_mint,
+        associated_token::authority = treasury,
+    )]
+    pub treasury_base_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = dao.usdc_mint,
+        associated_token::authority = treasury,
+    )]
+    pub treasury_quote_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = amm.key() == proposal.pass_amm || amm.key() == proposal.fail_amm
+            @ AutocratError::InvalidSeedMarket,
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+    /// CHECK: checked by the AMM program
+    #[account(mut)]
+    pub lp_mint: UncheckedAccount<'info>,
+    /// CHECK: checked by the AMM program
+    #[account(mut)]
+    pub amm_vault_ata_base: UncheckedAccount<'info>,
+    /// CHECK: checked by the AMM program
+    #[account(mut)]
+    pub amm_vault_ata_quote: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = amm.lp_mint,
+        associated_token::authority = treasury,
+    )]
+    pub treasury_lp_account: Box<Account<'info, TokenAccount>>,
+    pub vault_program: Program<'info, ConditionalVaultProgram>,
+    /// CHECK: checked by vault program
+    pub vault_event_authority: UncheckedAccount<'info>,
+    pub amm_program: Program<'info, AmmProgram>,
+    /// CHECK: checked by the AMM program
+    pub amm_event_authority: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info, 'c: 'info> SettleTreasuryPositions<'info> {
+    fn outcome_index(&self) -> usize {
+        if self.amm.key() == self.proposal.pass_amm {
+            PASS_INDEX
+        } else {
+            FAIL_INDEX
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.question.is_resolved(),
+            AutocratError::ProposalNotFinalized
+        );
+
+        let outcome_index = self.outcome_index();
+
+        require_neq!(
+            self.proposal.treasury_lp_tokens[outcome_index],
+            0,
+            AutocratError::NoTreasuryPositionToSettle
+        );
+
+        // a losing market's tokens redeem for nothing, so there's nothing to
+        // take from the treasury by moving its price
+        if self.question.payout_numerators[outcome_index] != 0 {
+            validate_market_price(&self.amm, self.proposal.treasury_max_price_deviation_bps)?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the liquidity that the treasury added to one of the proposal's
+    /// markets, and turns all of the treasury's conditional tokens back into
+    /// the DAO's token and USDC by merging whatever pairs it can and redeeming
+    /// the rest. Call it once for each market that the treasury seeded.
+    pub fn handle(ctx: Context<'_, '_, 'c, 'info, Self>) -> Result<()> {
+        let outcome_index = ctx.accounts.outcome_index();
+
+        let Self {
+            proposal,
+            dao,
+            treasury,
+            question,
+            base_vault,
+            quote_vault,
+            base_vault_underlying_token_account,
+            quote_vault_underlying_token_account,
+            treasury_base_account,
+            treasury_quote_account,
+            amm,
+            lp_mint,
+            amm_vault_ata_base,
+            amm_vault_ata_quote,
+            treasury_lp_account,
+            vault_program,
+            vault_event_authority,
+            amm_program,
+            amm_event_authority,
+            token_program,
+            event_authority: _,
+            program: _,
+        } = ctx.accounts;
+
+        require_eq!(
+            ctx.remaining_accounts.len(),
+            8,
+            AutocratError::InvalidOptionAccounts
+        );
+
+        let dao_key = dao.key();
+        let treasury_seeds = &[dao_key.as_ref(), &[dao.treasury_pda_bump]];
+        let treasury_signer = &[&treasury_seeds[..]];
+
+        let (base_accounts, quote_accounts) = ctx.remaining_accounts.split_at(4);
+
+        for (vault, accounts) in [(&base_vault, base_accounts), (&quote_vault, quote_accounts)] {
+            for (i, account) in accounts[2..].iter().enumerate() {
+                require_keys_eq!(
+                    account.key(),
+                    get_associated_token_address(
+                        &treasury.key(),
+                        &vault.conditional_token_mints[i]
+                    ),
+                    AutocratError::InvalidOptionAccounts
+                );
+            }
+        }
+
+        let lp_tokens_to_burn =
+            proposal.take_treasury_lp_tokens(outcome_index, treasury_lp_account.amount);
+
+        // the price was checked against the oracle in `validate`, so the
+        // treasury gets its share of the reserves at a fair price
+        if lp_tokens_to_burn > 0 {
+            amm::cpi::remove_liquidity(
+                CpiContext::new(
+                    amm_program.to_account_info(),
+                    amm::cpi::accounts::AddOrRemoveLiquidity {
+                        user: treasury.to_account_info(),
+                        amm: amm.to_account_info(),
+                        lp_mint: lp_mint.to_account_info(),
+                        user_lp_account: treasury_lp_account.to_account_info(),
+                        user_base_account: base_accounts[2 + outcome_index].clone(),
+                        user_quote_account: quote_accounts[2 + outcome_index].clone(),
+                        vault_ata_base: amm_vault_ata_base.to_account_info(),
+                        vault_ata_quote: amm_vault_ata_quote.to_account_info(),
+                        token_program: token_program.to_account_info(),
+                        event_authority: amm_event_authority.to_account_info(),
+                        program: amm_program.to_account_info(),
+                    },
+                )
+                .with_signer(treasury_signer),
+                RemoveLiquidityArgs {
+                    lp_tokens_to_burn,
+                    min_quote_amount: 0,
+                    min_base_amount: 0,
+                },
+            )?;
+        }
+
+        for (vault, vault_underlying_token_account, treasury_underlying_account, accounts) in [
+            (
+                base_vault,
+                base_vault_underlying_token_account,
+                treasury_base_account,
+                base_accounts,
+            ),
+            (
+                quote_vault,
+                quote_vault_underlying_token_account,
+                treasury_quote_account,
+                quote_accounts,
+            ),
+        ] {
+            let vault_accounts = || InteractWithVault {
+                question: question.to_account_info(),
+                vault: vault.to_account_info(),
+                vault_underlying_token_account: vault_underlying_token_account.to_account_info(),
+                authority: treasury.to_account_info(),
+                user_underlying_token_account: treasury_underlying_account.to_account_info(),
+                token_program: token_program.to_account_info(),
+                event_authority: vault_event_authority.to_account_info(),
+                program: vault_program.to_account_info(),
+            };
+
+            let balances = accounts[2..]
+                .iter()
+                .map(|account| Ok(Account::<TokenAccount>::try_from(account)?.amount))
+                .collect::<Result<Vec<u64>>>()?;
+
+            // safe because every vault has two conditional tokens
+            let mergeable = *balances.iter().min().unwrap();
+
+            if mergeable > 0 {
+                conditional_vault::cpi::merge_tokens(
+                    CpiContext::new(vault_program.to_account_info(), vault_accounts())
+                        .with_remaining_accounts(accounts.to_vec())
+                        .with_signer(treasury_signer),
+                    mergeable,
+                )?;
+            }
+
+            if balances.iter().any(|balance| *balance > mergeable) {
+                conditional_vault::cpi::redeem_tokens(
+                    CpiContext::new(vault_program.to_account_info(), vault_accounts())
+                        .with_remaining_accounts(accounts.to_vec())
+                        .with_signer(treasury_signer),
+                )?;
+            }
+        }
+
+        let clock = Clock::get()?;
+        emit_cpi!(SettleTreasuryPositionsEvent {
+            common: CommonFields::new(&clock),
+            proposal: proposal.key(),
+            dao: proposal.dao,
+            amm: amm.key(),
+            lp_tokens_burned: lp_tokens_to_burn,
+        });
+
+        Ok(())
+    }
+}
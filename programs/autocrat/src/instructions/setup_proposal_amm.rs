@@ -0,0 +1,218 @@
+use super::*;
+
+use amm::instructions::{AddLiquidityArgs, CreateAmmArgs};
+use amm::program::Amm as AmmProgram;
+use amm::state::AmmCurve;
+use anchor_spl::associated_token::{self, AssociatedToken, Create};
+
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetupProposalAmmParams {
+    pub quote_amount: u64,
+    pub max_base_amount: u64,
+    pub min_lp_tokens: u64,
+}
+
+#[derive(Accounts)]
+pub struct SetupProposalAmm<'info> {
+    #[account(
+        mut,
+        has_one = proposer,
+        has_one = dao,
+        has_one = base_vault,
+        has_one = quote_vault,
+    )]
+    pub proposal_setup: Box<Account<'info, ProposalSetup>>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    #[account(has_one = treasury)]
+    pub dao: Box<Account<'info, Dao>>,
+    /// CHECK: checked by `has_one`
+    pub treasury: UncheckedAccount<'info>,
+    pub base_vault: Box<Account<'info, ConditionalVaultAccount>>,
+    pub quote_vault: Box<Account<'info, ConditionalVaultAccount>>,
+    /// CHECK: initialized by the AMM program
+    #[account(mut)]
+    pub amm: UncheckedAccount<'info>,
+    /// CHECK: initialized by the AMM program
+    #[account(mut)]
+    pub lp_mint: UncheckedAccount<'info>,
+    /// CHECK: checked against the vaults in `validate`
+    pub base_mint: UncheckedAccount<'info>,
+    /// CHECK: checked against the vaults in `validate`
+    pub quote_mint: UncheckedAccount<'info>,
+    /// CHECK: initialized by the AMM program
+    #[account(mut)]
+    pub vault_ata_base: UncheckedAccount<'info>,
+    /// CHECK: initialized by the AMM program
+    #[account(mut)]
+    pub vault_ata_quote: UncheckedAccount<'info>,
+    /// CHECK: initialized by the associated token program
+    #[account(mut)]
+    pub user_lp_account: UncheckedAccount<'info>,
+    /// CHECK: checked by the AMM program
+    #[account(mut)]
+    pub user_base_account: UncheckedAccount<'info>,
+    /// CHECK: checked by the AMM program
+    #[account(mut)]
+    pub user_quote_account: UncheckedAccount<'info>,
+    /// CHECK: initialized by the associated token program
+    #[account(mut)]
+    pub treasury_lp_account: UncheckedAccount<'info>,
+    pub amm_program: Program<'info, AmmProgram>,
+    /// CHECK: checked by the AMM program
+    pub amm_event_authority: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl SetupProposalAmm<'_> {
+    /// The index of the conditional tokens that the next market trades.
+    fn outcome_index(&self) -> Result<usize> {
+        match self.proposal_setup.stage {
+            ProposalSetupStage::CreatePassAmm => Ok(PASS_INDEX),
+            ProposalSetupStage::CreateFailAmm => Ok(FAIL_INDEX),
+            ProposalSetupStage::Complete => err!(AutocratError::ProposalSetupComplete),
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let outcome_index = self.outcome_index()?;
+
+        require_keys_eq!(
+            self.base_mint.key(),
+            self.base_vault.conditional_token_mints[outcome_index],
+            AutocratError::InvalidSetupMint
+        );
+        require_keys_eq!(
+            self.quote_mint.key(),
+            self.quote_vault.conditional_token_mints[outcome_index],
+            AutocratError::InvalidSetupMint
+        );
+
+        Ok(())
+    }
+
+    /// Creates the next market, creates LP token accounts for the proposer and
+    /// the treasury, and adds the proposer's liquidity. The proposer needs to
+    /// have split enough tokens to cover `quote_amount` and `max_base_amount`.
+    pub fn handle(ctx: Context<Self>, params: SetupProposalAmmParams) -> Result<()> {
+        let Self {
+            proposal_setup,
+            proposer,
+            dao,
+            treasury,
+            base_vault: _,
+            quote_vault: _,
+            amm,
+            lp_mint,
+            base_mint,
+            quote_mint,
+            vault_ata_base,
+            vault_ata_quote,
+            user_lp_account,
+            user_base_account,
+            user_quote_account,
+            treasury_lp_account,
+            amm_program,
+            amm_event_authority,
+            token_program,
+            associated_token_program,
+            system_program,
+        } = ctx.accounts;
+
+        let SetupProposalAmmParams {
+            quote_amount,
+            max_base_amount,
+            min_lp_tokens,
+        } = params;
+
+        amm::cpi::create_amm(
+            CpiContext::new(
+                amm_program.to_account_info(),
+                amm::cpi::accounts::CreateAmm {
+                    user: proposer.to_account_info(),
+                    amm: amm.to_account_info(),
+                    lp_mint: lp_mint.to_account_info(),
+                    base_mint: base_mint.to_account_info(),
+                    quote_mint: quote_mint.to_account_info(),
+                    vault_ata_base: vault_ata_base.to_account_info(),
+                    vault_ata_quote: vault_ata_quote.to_account_info(),
+                    associated_token_program: associated_token_program.to_account_info(),
+                    token_program: token_program.to_account_info(),
+                    system_program: system_program.to_account_info(),
+                    event_authority: amm_event_authority.to_account_info(),
+                    program: amm_program.to_account_info(),
+                },
+            ),
+            CreateAmmArgs {
+                twap_initial_observation: dao.twap_initial_observation,
+                twap_max_observation_change_per_update: dao.twap_max_observation_change_per_update,
+                curve: AmmCurve::ConstantProduct,
+            },
+        )?;
+
+        // the proposer needs an account to receive their LP tokens, and
+        // `initialize_proposal` needs the treasury's to lock them in
+        for (lp_account, authority) in [
+            (
+                user_lp_account.to_account_info(),
+                proposer.to_account_info(),
+            ),
+            (
+                treasury_lp_account.to_account_info(),
+                treasury.to_account_info(),
+            ),
+        ] {
+            associated_token::create_idempotent(CpiContext::new(
+                associated_token_program.to_account_info(),
+                Create {
+                    payer: proposer.to_account_info(),
+                    associated_token: lp_account,
+                    authority,
+                    mint: lp_mint.to_account_info(),
+                    system_program: system_program.to_account_info(),
+                    token_program: token_program.to_account_info(),
+                },
+            ))?;
+        }
+
+        amm::cpi::add_liquidity(
+            CpiContext::new(
+                amm_program.to_account_info(),
+                amm::cpi::accounts::AddOrRemoveLiquidity {
+                    user: proposer.to_account_info(),
+                    amm: amm.to_account_info(),
+                    lp_mint: lp_mint.to_account_info(),
+                    user_lp_account: user_lp_account.to_account_info(),
+                    user_base_account: user_base_account.to_account_info(),
+                    user_quote_account: user_quote_account.to_account_info(),
+                    vault_ata_base: vault_ata_base.to_account_info(),
+                    vault_ata_quote: vault_ata_quote.to_account_info(),
+                    token_program: token_program.to_account_info(),
+                    event_authority: amm_event_authority.to_account_info(),
+                    program: amm_program.to_account_info(),
+                },
+            ),
+            AddLiquidityArgs {
+                quote_amount,
+                max_base_amount,
+                min_lp_tokens,
+            },
+        )?;
+
+        match proposal_setup.stage {
+            ProposalSetupStage::CreatePassAmm => {
+                proposal_setup.pass_amm = amm.key();
+                proposal_setup.stage = ProposalSetupStage::CreateFailAmm;
+            }
+            ProposalSetupStage::CreateFailAmm => {
+                proposal_setup.fail_amm = amm.key();
+                proposal_setup.stage = ProposalSetupStage::Complete;
+            }
+            ProposalSetupStage::Complete => return err!(AutocratError::ProposalSetupComplete),
+        }
+
+        Ok(())
+    }
+}
@@ -8,8 +8,38 @@ pub struct UpdateDaoParams {
     pub twap_max_observation_change_per_update: Option<u128>,
     pub min_quote_futarchic_liquidity: Option<u64>,
     pub min_base_futarchic_liquidity: Option<u64>,
+    pub min_average_quote_liquidity: Option<u64>,
+    pub execution_delay_slots: Option<u64>,
+    /// `Some(None)` removes the veto authority.
+    pub veto_authority: Option<Option<Pubkey>>,
+    pub execution_window_slots: Option<u64>,
+    pub decision_rule: Option<DecisionRule>,
+    /// `Some(None)` removes the bond.
+    pub proposal_bond: Option<Option<ProposalBond>>,
+    /// `Some(None)` removes the cap.
+    pub max_active_proposals: Option<Option<u32>>,
+    /// `Some(None)` removes the cap.
+    pub max_active_proposals_per_proposer: Option<Option<u32>>,
+    /// `Some(None)` removes the spot market.
+    pub spot_amm: Option<Option<Pubkey>>,
+    /// `Some(None)` stops the treasury from seeding new markets.
+    pub treasury_liquidity: Option<Option<TreasuryLiquidity>>,
 }
 
+impl UpdateDaoParams {
+    /// Whether these params change a setting that makes this a
+    /// `ProposalType::SecurityChange`.
+    pub fn changes_security_settings(&self) -> bool {
+        self.treasury_liquidity.is_some()
+            || self.veto_authority.is_some()
+            || self.decision_rule.is_some()
+    }
+}
+
+/// Unlike autocrat's other instructions, this one logs its event with `emit!`
+/// rather than `emit_cpi!`. It's only ever called by the DAO's own proposals,
+/// and proposals created before events existed don't pass the event
+/// authority, so adding it would make them impossible to execute.
 #[derive(Accounts)]
 pub struct UpdateDao<'info> {
     #[account(mut, has_one = treasury)]
@@ -35,6 +65,25 @@ impl UpdateDao<'_> {
         update_dao_if_passed!(twap_max_observation_change_per_update);
         update_dao_if_passed!(min_quote_futarchic_liquidity);
         update_dao_if_passed!(min_base_futarchic_liquidity);
+        update_dao_if_passed!(min_average_quote_liquidity);
+        update_dao_if_passed!(execution_delay_slots);
+        update_dao_if_passed!(veto_authority);
+        update_dao_if_passed!(execution_window_slots);
+        update_dao_if_passed!(decision_rule);
+        update_dao_if_passed!(proposal_bond);
+        update_dao_if_passed!(max_active_proposals);
+        update_dao_if_passed!(max_active_proposals_per_proposer);
+        update_dao_if_passed!(spot_amm);
+        update_dao_if_passed!(treasury_liquidity);
+
+        dao.validate_params()?;
+
+        let clock = Clock::get()?;
+        emit!(UpdateDaoEvent {
+            common: CommonFields::new(&clock),
+            dao: dao.key(),
+            config: DaoConfig::new(dao),
+        });
 
         Ok(())
     }
@@ -0,0 +1,33 @@
+use super::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct VetoMultiOptionProposal<'info> {
+    #[account(mut, has_one = dao)]
+    pub proposal: Box<Account<'info, MultiOptionProposal>>,
+    pub dao: Box<Account<'info, Dao>>,
+    pub veto_authority: Signer<'info>,
+}
+
+impl VetoMultiOptionProposal<'_> {
+    pub fn validate(&self) -> Result<()> {
+        self.proposal
+            .validate_veto(&self.dao, self.veto_authority.key())
+    }
+
+    /// Same as `veto_proposal`.
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.state = ProposalState::Vetoed;
+
+        let clock = Clock::get()?;
+        emit_cpi!(VetoProposalEvent {
+            common: CommonFields::new(&clock),
+            proposal: proposal.key(),
+            dao: proposal.dao,
+            veto_authority: ctx.accounts.veto_authority.key(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use super::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct VetoProposal<'info> {
+    #[account(mut, has_one = dao)]
+    pub proposal: Account<'info, Proposal>,
+    pub dao: Box<Account<'info, Dao>>,
+    pub veto_authority: Signer<'info>,
+}
+
+impl VetoProposal<'_> {
+    pub fn validate(&self) -> Result<()> {
+        self.proposal
+            .validate_veto(&self.dao, self.veto_authority.key())
+    }
+
+    /// Stops a passed proposal from being executed. The proposal's markets
+    /// have already been resolved, so this doesn't change anyone's payouts.
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.state = ProposalState::Vetoed;
+
+        let clock = Clock::get()?;
+        emit_cpi!(VetoProposalEvent {
+            common: CommonFields::new(&clock),
+            proposal: proposal.key(),
+            dao: proposal.dao,
+            veto_authority: ctx.accounts.veto_authority.key(),
+        });
+
+        Ok(())
+    }
+}
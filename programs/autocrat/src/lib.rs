@@ -3,26 +3,60 @@
 //!
 //! Autocrat has two types of accounts: DAOs and proposals. Every DAO has its
 //! own token, its own treasury account, and list of configs. Proposals are
-//! created for a specific DAO, and contain an SVM instruction and a URL that
-//! should point to a description and justification of that instruction.
+//! created for a specific DAO, and contain a list of SVM instructions and a URL
+//! that should point to a description and justification of those instructions.
 //!
 //! Proposals pass through various states in their lifecycle. Here's a description
 //! of these states:
 //! - Pre-creation: this is when you initialize the accounts needed for a proposal,
 //!   including the vaults and the AMM accounts. The proposer will also deposit to
-//!   create their LP during this time.
+//!   create their LP during this time, and write the proposal's instructions
+//!   to a `ProposalInstructions` account over as many transactions as they need.
+//!   Autocrat creates the question, vaults and markets itself, see
+//!   `ProposalSetup`. All of these accounts are PDAs of the proposal's address,
+//!   see the `pda` module.
 //! - Trading: to create a proposal, the proposer must call
 //!   `initialize_proposal`, which requires them to lock up some LP tokens in each
 //!   of the markets. Once a proposal is created, anyone can trade its markets.
 //!   Prices of these markets are aggregated into a time-weighted average price
-//!   oracle.
+//!   oracle. If the DAO has `treasury_liquidity`, anyone can also have the
+//!   treasury add liquidity to both markets with `seed_proposal_market`.
 //! - Pass or fail: if the TWAP of the pass market is sufficiently higher than the
-//!   TWAP of the fail market, the proposal will pass. If it's not, the proposal will
+//!   TWAP of the fail market, the proposal will pass. What counts as sufficiently
+//!   higher depends on the DAO's `DecisionRule`. If it's not, the proposal will
 //!   fail. If it passes, both vaults will be finalized, allowing pTOKEN holders to
 //!   redeem. If it fails, both vaults will be reverted, allowing fTOKEN holders to
 //!   redeem.
+//! - Cancelled: for the first hour of trading, the proposer can back out by
+//!   calling `cancel_proposal`. Their LP is unlocked and both outcomes pay out
+//!   half, so traders can redeem at roughly what they paid.
 //! - Executed: if a proposal passes, anyone can make autocrat execute its SVM
-//!   instruction by calling `execute_proposal`.
+//!   instructions by calling `execute_proposal` once per instruction, in order.
+//!   What these can send out of the treasury is capped by the DAO's
+//!   `spending_limits` as they were when the proposal was created.
+//!   DAOs can require passed proposals to wait `execution_delay_slots` first.
+//! - Expired: a passed proposal that hasn't been fully executed within the DAO's
+//!   `execution_window_slots` can't be executed anymore, and anyone can mark it
+//!   as expired by calling `expire_proposal`.
+//! - Vetoed: until a passed proposal has been fully executed, the DAO's
+//!   `veto_authority`, if it has one, can stop it with `veto_proposal`.
+//!
+//! Once a proposal is no longer pending, anyone can call
+//! `settle_treasury_positions` for each market the treasury seeded, which
+//! takes out the treasury's liquidity and redeems its conditional tokens.
+//!
+//! If a DAO has a `proposal_bond`, proposers also escrow the bond when they
+//! create a proposal. Once the proposal is no longer pending, anyone can call
+//! `settle_proposal_bond`, which returns the bond if the proposal passed or
+//! enough was traded in its markets, and sends it to the treasury otherwise.
+//!
+//! DAOs can also create multi-option proposals, which trade a question with
+//! more than two outcomes. The first outcome is the status quo, and every other
+//! outcome has its own instructions. These go through the same lifecycle as
+//! regular proposals, except that the option with the highest TWAP wins if it
+//! beats the status quo under the DAO's decision rule. They don't have bonds
+//! and the treasury can't seed their markets, so DAOs with a `proposal_bond` or
+//! `treasury_liquidity` can't create them.
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
@@ -33,10 +67,13 @@ use conditional_vault::Question;
 // use conditional_vault::VaultStatus;
 
 pub mod error;
+pub mod events;
 pub mod instructions;
+pub mod pda;
 pub mod state;
 
 pub use crate::error::AutocratError;
+pub use crate::events::*;
 pub use crate::instructions::*;
 pub use crate::state::*;
 
@@ -45,7 +82,6 @@ use amm::state::Amm;
 use solana_program::instruction::Instruction;
 #[cfg(not(feature = "no-entrypoint"))]
 use solana_security_txt::security_txt;
-use std::borrow::Borrow;
 
 #[cfg(not(feature = "no-entrypoint"))]
 security_txt! {
@@ -64,6 +100,16 @@ declare_id!("autowMzCbM29YXMgVG3T62Hkgo7RcyrvgQQkd54fDQL");
 pub const SLOTS_PER_10_SECS: u64 = 25;
 pub const THREE_DAYS_IN_SLOTS: u64 = 3 * 24 * 60 * 6 * SLOTS_PER_10_SECS;
 
+// long enough for a proposer to notice a mistake in their proposal, short
+// enough that the markets haven't had time to say much about it
+pub const PROPOSAL_CANCELLATION_WINDOW_SLOTS: u64 = 60 * 6 * SLOTS_PER_10_SECS;
+
+// shorter proposals don't give traders enough time to move the TWAP, which
+// makes them cheap to manipulate
+pub const MIN_SLOTS_PER_PROPOSAL: u64 = 24 * 60 * 6 * SLOTS_PER_10_SECS;
+
+pub const TEN_DAYS_IN_SLOTS: u64 = 10 * 24 * 60 * 6 * SLOTS_PER_10_SECS;
+
 pub const TEN_DAYS_IN_SECONDS: i64 = 10 * 24 * 60 * 60;
 
 // by default, the pass price needs to be 3% higher than the fail price
@@ -71,11 +117,33 @@ pub const DEFAULT_PASS_THRESHOLD_BPS: u16 = 300;
 
 pub const MAX_BPS: u16 = 10_000;
 
+// bumped whenever `Proposal`'s layout changes in a way that needs a migration
+pub const PROPOSAL_VERSION: u8 = 1;
+
 // the index of the fail and pass outcomes in the question and the index of
 // the pass and fail conditional tokens in the conditional vault
 pub const FAIL_INDEX: usize = 0;
 pub const PASS_INDEX: usize = 1;
 
+// proposals only ever trade on pass and fail
+pub const PROPOSAL_NUM_OUTCOMES: u8 = 2;
+
+// multi-option proposals need to crank every option's market at finalization,
+// so keep the number of options small enough to fit in one transaction
+pub const MAX_PROPOSAL_OPTIONS: usize = 6;
+
+// keeps the DAO account, which has to be big enough for all of its categories,
+// under 2KB
+pub const MAX_PROPOSAL_CATEGORIES: usize = 16;
+
+// every limited mint is tracked on each proposal as it's executed, so keep
+// this small
+pub const MAX_SPENDING_LIMITS: usize = 4;
+
+// the more buckets, the closer a spending limit's window is to exactly
+// `window_slots` long
+pub const SPENDING_WINDOW_BUCKETS: usize = 8;
+
 // TWAP can only move by $5 per slot
 pub const DEFAULT_MAX_OBSERVATION_CHANGE_PER_UPDATE_LOTS: u64 = 5_000;
 
@@ -87,6 +155,34 @@ pub mod autocrat {
         InitializeDAO::handle(ctx, params)
     }
 
+    pub fn initialize_proposal_instructions(
+        ctx: Context<InitializeProposalInstructions>,
+    ) -> Result<()> {
+        InitializeProposalInstructions::handle(ctx)
+    }
+
+    pub fn add_proposal_instruction(
+        ctx: Context<AddProposalInstruction>,
+        instruction: ProposalInstruction,
+    ) -> Result<()> {
+        AddProposalInstruction::handle(ctx, instruction)
+    }
+
+    pub fn initialize_proposal_setup<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, InitializeProposalSetup<'info>>,
+        nonce: u64,
+    ) -> Result<()> {
+        InitializeProposalSetup::handle(ctx, nonce)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn setup_proposal_amm(
+        ctx: Context<SetupProposalAmm>,
+        params: SetupProposalAmmParams,
+    ) -> Result<()> {
+        SetupProposalAmm::handle(ctx, params)
+    }
+
     #[access_control(ctx.accounts.validate())]
     pub fn initialize_proposal(
         ctx: Context<InitializeProposal>,
@@ -95,17 +191,127 @@ pub mod autocrat {
         InitializeProposal::handle(ctx, params)
     }
 
+    #[access_control(ctx.accounts.validate())]
+    pub fn seed_proposal_market<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SeedProposalMarket<'info>>,
+    ) -> Result<()> {
+        SeedProposalMarket::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn settle_treasury_positions<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SettleTreasuryPositions<'info>>,
+    ) -> Result<()> {
+        SettleTreasuryPositions::handle(ctx)
+    }
+
     #[access_control(ctx.accounts.validate())]
     pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
         FinalizeProposal::handle(ctx)
     }
 
     #[access_control(ctx.accounts.validate())]
-    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        CancelProposal::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn execute_proposal<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ExecuteProposal<'info>>,
+    ) -> Result<()> {
         ExecuteProposal::handle(ctx)
     }
 
+    #[access_control(ctx.accounts.validate())]
+    pub fn settle_proposal_bond(ctx: Context<SettleProposalBond>) -> Result<()> {
+        SettleProposalBond::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn expire_proposal(ctx: Context<ExpireProposal>) -> Result<()> {
+        ExpireProposal::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn veto_proposal(ctx: Context<VetoProposal>) -> Result<()> {
+        VetoProposal::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate(&params))]
+    pub fn initialize_multi_option_proposal<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, InitializeMultiOptionProposal<'info>>,
+        params: InitializeMultiOptionProposalParams,
+    ) -> Result<()> {
+        InitializeMultiOptionProposal::handle(ctx, params)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn finalize_multi_option_proposal<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, FinalizeMultiOptionProposal<'info>>,
+    ) -> Result<()> {
+        FinalizeMultiOptionProposal::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn cancel_multi_option_proposal<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CancelMultiOptionProposal<'info>>,
+    ) -> Result<()> {
+        CancelMultiOptionProposal::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn execute_multi_option_proposal<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ExecuteMultiOptionProposal<'info>>,
+    ) -> Result<()> {
+        ExecuteMultiOptionProposal::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn expire_multi_option_proposal(ctx: Context<ExpireMultiOptionProposal>) -> Result<()> {
+        ExpireMultiOptionProposal::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn veto_multi_option_proposal(ctx: Context<VetoMultiOptionProposal>) -> Result<()> {
+        VetoMultiOptionProposal::handle(ctx)
+    }
+
+    pub fn migrate_proposal(ctx: Context<MigrateProposal>) -> Result<()> {
+        MigrateProposal::handle(ctx)
+    }
+
+    pub fn migrate_dao(ctx: Context<MigrateDao>) -> Result<()> {
+        MigrateDao::handle(ctx)
+    }
+
     pub fn update_dao(ctx: Context<UpdateDao>, dao_params: UpdateDaoParams) -> Result<()> {
         UpdateDao::handle(ctx, dao_params)
     }
+
+    #[access_control(ctx.accounts.validate(&category))]
+    pub fn add_proposal_category(
+        ctx: Context<AddProposalCategory>,
+        category: ProposalCategory,
+    ) -> Result<()> {
+        AddProposalCategory::handle(ctx, category)
+    }
+
+    pub fn remove_proposal_category(
+        ctx: Context<RemoveProposalCategory>,
+        program_id: Pubkey,
+        data_prefix: Vec<u8>,
+    ) -> Result<()> {
+        RemoveProposalCategory::handle(ctx, program_id, data_prefix)
+    }
+
+    pub fn set_spending_limit(
+        ctx: Context<SetSpendingLimit>,
+        params: SetSpendingLimitParams,
+    ) -> Result<()> {
+        SetSpendingLimit::handle(ctx, params)
+    }
+
+    pub fn remove_spending_limit(ctx: Context<RemoveSpendingLimit>, mint: Pubkey) -> Result<()> {
+        RemoveSpendingLimit::handle(ctx, mint)
+    }
 }
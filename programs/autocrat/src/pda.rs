@@ -0,0 +1,331 @@
+//! Helpers for deriving the addresses of a proposal's accounts, so that
+//! clients and indexers can find everything a proposal uses from just its
+//! proposer and nonce.
+//!
+//! A proposal's question always uses the proposal's address as both its
+//! `question_id` and its oracle, and always has `PROPOSAL_NUM_OUTCOMES`
+//! outcomes. Since questions,
+//! vaults, conditional token mints and markets are all PDAs of the accounts
+//! before them, this makes every account a proposal trades on canonical.
+use super::*;
+
+use amm::state::{AMM_LP_MINT_SEED_PREFIX, AMM_SEED_PREFIX};
+use anchor_spl::associated_token::get_associated_token_address;
+
+pub fn find_treasury_address(dao: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[dao.as_ref()], &crate::ID)
+}
+
+pub fn find_proposal_address(proposer: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"proposal", proposer.as_ref(), &nonce.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+pub fn find_multi_option_proposal_address(proposer: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"multi_option_proposal",
+            proposer.as_ref(),
+            &nonce.to_le_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_proposal_setup_address(proposer: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"proposal_setup", proposer.as_ref(), &nonce.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+pub fn find_proposer_record_address(dao: &Pubkey, proposer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"proposer_record", dao.as_ref(), proposer.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn find_bond_escrow_address(proposal: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"bond", proposal.as_ref()], &crate::ID)
+}
+
+/// The `question_id` that a proposal's question must use.
+pub fn proposal_question_id(proposal: &Pubkey) -> [u8; 32] {
+    proposal.to_bytes()
+}
+
+/// Multi-option proposals use the same derivation, with one outcome per option.
+pub fn find_question_address(proposal: &Pubkey, num_outcomes: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"question",
+            proposal_question_id(proposal).as_ref(),
+            proposal.as_ref(),
+            &[num_outcomes],
+        ],
+        &conditional_vault::ID,
+    )
+}
+
+pub fn find_vault_address(question: &Pubkey, underlying_token_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"conditional_vault",
+            question.as_ref(),
+            underlying_token_mint.as_ref(),
+        ],
+        &conditional_vault::ID,
+    )
+}
+
+pub fn find_conditional_token_mint_address(vault: &Pubkey, outcome_index: usize) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"conditional_token", vault.as_ref(), &[outcome_index as u8]],
+        &conditional_vault::ID,
+    )
+}
+
+pub fn find_amm_address(base_mint: &Pubkey, quote_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[AMM_SEED_PREFIX, base_mint.as_ref(), quote_mint.as_ref()],
+        &amm::ID,
+    )
+}
+
+pub fn find_lp_mint_address(amm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AMM_LP_MINT_SEED_PREFIX, amm.as_ref()], &amm::ID)
+}
+
+/// The addresses of one of a proposal's markets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarketAddresses {
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub amm: Pubkey,
+    pub lp_mint: Pubkey,
+    pub proposer_lp_account: Pubkey,
+    pub treasury_lp_account: Pubkey,
+}
+
+/// Every address a proposal uses, derived the same way as autocrat derives
+/// and checks them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProposalAddresses {
+    pub proposal: Pubkey,
+    pub proposal_setup: Pubkey,
+    pub proposer_record: Pubkey,
+    pub bond_escrow: Pubkey,
+    pub treasury: Pubkey,
+    pub question: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub base_vault_underlying_token_account: Pubkey,
+    pub quote_vault_underlying_token_account: Pubkey,
+    pub pass_market: MarketAddresses,
+    pub fail_market: MarketAddresses,
+}
+
+impl ProposalAddresses {
+    pub fn derive(dao: &Dao, dao_key: &Pubkey, proposer: &Pubkey, nonce: u64) -> Self {
+        let (proposal, _) = find_proposal_address(proposer, nonce);
+        let (question, _) = find_question_address(&proposal, PROPOSAL_NUM_OUTCOMES);
+        let (base_vault, _) = find_vault_address(&question, &dao.token_mint);
+        let (quote_vault, _) = find_vault_address(&question, &dao.usdc_mint);
+
+        let market = |outcome_index| {
+            let (base_mint, _) = find_conditional_token_mint_address(&base_vault, outcome_index);
+            let (quote_mint, _) = find_conditional_token_mint_address(&quote_vault, outcome_index);
+            let (amm, _) = find_amm_address(&base_mint, &quote_mint);
+            let (lp_mint, _) = find_lp_mint_address(&amm);
+
+            MarketAddresses {
+                base_mint,
+                quote_mint,
+                amm,
+                lp_mint,
+                proposer_lp_account: get_associated_token_address(proposer, &lp_mint),
+                treasury_lp_account: get_associated_token_address(&dao.treasury, &lp_mint),
+            }
+        };
+
+        Self {
+            proposal,
+            proposal_setup: find_proposal_setup_address(proposer, nonce).0,
+            proposer_record: find_proposer_record_address(dao_key, proposer).0,
+            bond_escrow: find_bond_escrow_address(&proposal).0,
+            treasury: dao.treasury,
+            question,
+            base_vault,
+            quote_vault,
+            base_vault_underlying_token_account: get_associated_token_address(
+                &base_vault,
+                &dao.token_mint,
+            ),
+            quote_vault_underlying_token_account: get_associated_token_address(
+                &quote_vault,
+                &dao.usdc_mint,
+            ),
+            pass_market: market(PASS_INDEX),
+            fail_market: market(FAIL_INDEX),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::state::dao::tests::dao;
+
+    // these spell out the seeds from each program's `init` constraints, rather
+    // than reusing the helpers above
+    #[test]
+    fn proposal_addresses_match_the_init_seeds() {
+        let dao = dao();
+        let dao_key = Pubkey::new_unique();
+        let proposer = Pubkey::new_unique();
+        let nonce = 7u64;
+
+        let addresses = ProposalAddresses::derive(&dao, &dao_key, &proposer, nonce);
+
+        let (proposal, _) = Pubkey::find_program_address(
+            &[b"proposal", proposer.as_ref(), &nonce.to_le_bytes()],
+            &crate::ID,
+        );
+        assert_eq!(addresses.proposal, proposal);
+        assert_eq!(
+            addresses.proposal_setup,
+            Pubkey::find_program_address(
+                &[b"proposal_setup", proposer.as_ref(), &nonce.to_le_bytes()],
+                &crate::ID,
+            )
+            .0
+        );
+        assert_eq!(
+            addresses.proposer_record,
+            Pubkey::find_program_address(
+                &[b"proposer_record", dao_key.as_ref(), proposer.as_ref()],
+                &crate::ID,
+            )
+            .0
+        );
+        assert_eq!(
+            addresses.bond_escrow,
+            Pubkey::find_program_address(&[b"bond", proposal.as_ref()], &crate::ID).0
+        );
+        assert_eq!(addresses.treasury, dao.treasury);
+
+        let (question, _) = Pubkey::find_program_address(
+            &[
+                b"question",
+                proposal.as_ref(),
+                proposal.as_ref(),
+                &[PROPOSAL_NUM_OUTCOMES],
+            ],
+            &conditional_vault::ID,
+        );
+        assert_eq!(addresses.question, question);
+
+        for (vault, underlying_token_account, mint) in [
+            (
+                addresses.base_vault,
+                addresses.base_vault_underlying_token_account,
+                dao.token_mint,
+            ),
+            (
+                addresses.quote_vault,
+                addresses.quote_vault_underlying_token_account,
+                dao.usdc_mint,
+            ),
+        ] {
+            assert_eq!(
+                vault,
+                Pubkey::find_program_address(
+                    &[b"conditional_vault", question.as_ref(), mint.as_ref()],
+                    &conditional_vault::ID,
+                )
+                .0
+            );
+            assert_eq!(
+                underlying_token_account,
+                get_associated_token_address(&vault, &mint)
+            );
+        }
+
+        for (market, outcome_index) in [
+            (addresses.pass_market, PASS_INDEX),
+            (addresses.fail_market, FAIL_INDEX),
+        ] {
+            let conditional_token_mint = |vault: &Pubkey| {
+                Pubkey::find_program_address(
+                    &[b"conditional_token", vault.as_ref(), &[outcome_index as u8]],
+                    &conditional_vault::ID,
+                )
+                .0
+            };
+            assert_eq!(
+                market.base_mint,
+                conditional_token_mint(&addresses.base_vault)
+            );
+            assert_eq!(
+                market.quote_mint,
+                conditional_token_mint(&addresses.quote_vault)
+            );
+
+            let (amm, _) = Pubkey::find_program_address(
+                &[
+                    b"amm__",
+                    market.base_mint.as_ref(),
+                    market.quote_mint.as_ref(),
+                ],
+                &amm::ID,
+            );
+            assert_eq!(market.amm, amm);
+            let (lp_mint, _) =
+                Pubkey::find_program_address(&[b"amm_lp_mint", amm.as_ref()], &amm::ID);
+            assert_eq!(market.lp_mint, lp_mint);
+            assert_eq!(
+                market.proposer_lp_account,
+                get_associated_token_address(&proposer, &lp_mint)
+            );
+            assert_eq!(
+                market.treasury_lp_account,
+                get_associated_token_address(&dao.treasury, &lp_mint)
+            );
+        }
+
+        assert_ne!(addresses.pass_market, addresses.fail_market);
+    }
+
+    #[test]
+    fn proposal_question_id_is_the_proposal_address() {
+        let proposal = Pubkey::new_unique();
+
+        assert_eq!(proposal_question_id(&proposal), proposal.to_bytes());
+    }
+
+    #[test]
+    fn multi_option_proposals_use_their_own_seeds() {
+        let proposer = Pubkey::new_unique();
+
+        assert_eq!(
+            find_multi_option_proposal_address(&proposer, 0).0,
+            Pubkey::find_program_address(
+                &[
+                    b"multi_option_proposal",
+                    proposer.as_ref(),
+                    &0u64.to_le_bytes()
+                ],
+                &crate::ID,
+            )
+            .0
+        );
+        assert_ne!(
+            find_multi_option_proposal_address(&proposer, 0),
+            find_proposal_address(&proposer, 0)
+        );
+    }
+}
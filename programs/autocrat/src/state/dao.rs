@@ -1,5 +1,88 @@
 pub use super::*;
 
+/// How a DAO decides whether a proposal passed, given the TWAPs of its pass
+/// and fail markets.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum DecisionRule {
+    /// Pass if the pass TWAP is more than `pass_threshold_bps` above the fail TWAP.
+    #[default]
+    Relative,
+    /// Pass unless the fail TWAP is more than `fail_threshold_bps` above the
+    /// pass TWAP. This is the same as a negative `pass_threshold_bps`, for DAOs
+    /// that want proposals to pass unless the market is confident they shouldn't.
+    DefaultPass { fail_threshold_bps: u16 },
+    /// Pass if the pass TWAP is more than `threshold` above the fail TWAP, in
+    /// the same units as AMM prices. At most the DAO's `twap_initial_observation`.
+    Absolute { threshold: u128 },
+    /// `Relative`, but the pass TWAP also needs to be at least the spot price,
+    /// so that proposals can't pass by tanking both markets. Needs the DAO to
+    /// have a `spot_amm`.
+    ///
+    /// The spot price is the TWAP of the DAO's `spot_amm` over the proposal.
+    /// Proposals without one never pass.
+    RelativeAboveSpot,
+    /// `Relative`, but with a threshold for each `ProposalType` other than
+    /// `Standard`. Treasury spends and security changes can be bundled with
+    /// anything else, so they can only make a proposal's threshold stricter
+    /// than its `pass_threshold_bps`.
+    ByProposalType {
+        parameter_change_threshold_bps: u16,
+        treasury_spend_threshold_bps: u16,
+        security_change_threshold_bps: u16,
+    },
+}
+
+/// A deposit that proposers put up when they create a proposal, so that spam
+/// costs more than the rent and the time value of the locked LP.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct ProposalBond {
+    /// Either the DAO's token or USDC.
+    pub mint: Pubkey,
+    pub amount: u64,
+    /// The bond is returned if the proposal passes or if at least this much
+    /// quote was traded in each of its markets. Otherwise, it goes to the
+    /// treasury. Liquidity that the proposer or the treasury adds doesn't
+    /// count, since it isn't traded. Proposers can still trade against
+    /// themselves, but every swap pays a fee to the market's other LPs, so
+    /// this should be high enough that faking it costs more than the bond.
+    pub refund_quote_volume: u64,
+}
+
+impl ProposalBond {
+    /// Whether a proposal that ended up in `state`, and that had this much
+    /// quote traded in its markets, gets its bond back.
+    pub fn is_refunded(
+        &self,
+        state: ProposalState,
+        pass_quote_volume: u128,
+        fail_quote_volume: u128,
+    ) -> bool {
+        match state {
+            ProposalState::Passed | ProposalState::Executed | ProposalState::Expired => true,
+            _ => {
+                let refund_quote_volume = self.refund_quote_volume as u128;
+
+                pass_quote_volume >= refund_quote_volume && fail_quote_volume >= refund_quote_volume
+            }
+        }
+    }
+}
+
+/// How much liquidity the treasury adds to each of a proposal's markets, so
+/// that proposers don't need to bring all of it themselves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct TreasuryLiquidity {
+    pub quote_amount: u64,
+    /// The most of the DAO's token the treasury adds along with
+    /// `quote_amount`. How much is actually added depends on the market's price.
+    pub max_base_amount: u64,
+    /// How far, in bps, a market's price can be from its TWAP oracle's last
+    /// observation for the treasury to seed it. The observation can only move
+    /// by `twap_max_observation_change_per_update` each update, so it's much
+    /// harder to push around than the price.
+    pub max_price_deviation_bps: u16,
+}
+
 #[account]
 pub struct Dao {
     pub treasury_pda_bump: u8,
@@ -35,4 +118,650 @@ pub struct Dao {
     /// 10 * 1_000_000_000 (10 META).
     pub min_quote_futarchic_liquidity: u64,
     pub min_base_futarchic_liquidity: u64,
+    /// Locking liquidity only proves that a market had liquidity when the proposal
+    /// was created. To pass, both markets also need to have had a time-weighted
+    /// average quote reserve of at least this much. 0 disables the check.
+    pub min_average_quote_liquidity: u64,
+    /// How long a passed proposal has to wait before it can be executed. This
+    /// gives the `veto_authority` time to step in if the markets got it wrong.
+    pub execution_delay_slots: u64,
+    /// Can veto passed proposals before they're executed, as a last line of
+    /// defense against market manipulation. This could be a multisig or the
+    /// treasury of another futarchy.
+    pub veto_authority: Option<Pubkey>,
+    /// How long a passed proposal can be executed for once its execution delay
+    /// is over. After that, its instructions are considered stale and anyone can
+    /// mark it as expired.
+    pub execution_window_slots: u64,
+    pub decision_rule: DecisionRule,
+    /// At most `MAX_PROPOSAL_CATEGORIES`. Instructions that aren't in any
+    /// category use the DAO's own threshold, duration and liquidity.
+    pub proposal_categories: Vec<ProposalCategory>,
+    /// Multi-option proposals can't be bonded, so DAOs that set this can only
+    /// create regular proposals.
+    pub proposal_bond: Option<ProposalBond>,
+    /// The number of proposals, of either kind, that haven't been finalized or
+    /// cancelled yet.
+    pub active_proposal_count: u32,
+    /// Caps on active proposals, so that the DAO's attention, and its traders'
+    /// capital, isn't spread across too many markets at once. `None` means
+    /// no cap.
+    pub max_active_proposals: Option<u32>,
+    pub max_active_proposals_per_proposer: Option<u32>,
+    /// A market for the DAO's token against USDC, such as a META/USDC `Amm`,
+    /// whose TWAP over each proposal is recorded when it's finalized. If this
+    /// is set, proposals need to pass it in when they're created and finalized.
+    pub spot_amm: Option<Pubkey>,
+    /// If set, anyone can make the treasury seed each market of a regular
+    /// proposal with this much liquidity through `seed_proposal_market`.
+    pub treasury_liquidity: Option<TreasuryLiquidity>,
+    /// At most `MAX_SPENDING_LIMITS`, at most one per mint.
+    pub spending_limits: Vec<SpendingLimit>,
+}
+
+/// The layout of DAOs created before any of the fields after
+/// `min_base_futarchic_liquidity` were added. These can be brought up to date
+/// with `migrate_dao`.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct DaoV0 {
+    pub treasury_pda_bump: u8,
+    pub treasury: Pubkey,
+    pub token_mint: Pubkey,
+    pub usdc_mint: Pubkey,
+    pub proposal_count: u32,
+    pub pass_threshold_bps: u16,
+    pub slots_per_proposal: u64,
+    pub twap_initial_observation: u128,
+    pub twap_max_observation_change_per_update: u128,
+    pub min_quote_futarchic_liquidity: u64,
+    pub min_base_futarchic_liquidity: u64,
+}
+
+impl Dao {
+    pub fn space() -> usize {
+        8 + std::mem::size_of::<Self>()
+            + MAX_PROPOSAL_CATEGORIES * std::mem::size_of::<ProposalCategory>()
+            + MAX_SPENDING_LIMITS * std::mem::size_of::<SpendingLimit>()
+    }
+
+    /// Checks that the DAO's config can't stop proposals from being created,
+    /// traded, finalized or executed. Called whenever the config changes.
+    pub fn validate_params(&self) -> Result<()> {
+        self.default_requirements().validate()?;
+
+        for category in &self.proposal_categories {
+            category.requirements.validate()?;
+        }
+
+        for spending_limit in &self.spending_limits {
+            spending_limit.validate()?;
+        }
+
+        // an initial observation of 0 means the TWAP starts from nothing, and
+        // a max change of 0 means it can never move from where it starts
+        require_neq!(
+            self.twap_initial_observation,
+            0,
+            AutocratError::InvalidTwapInitialObservation
+        );
+        require_neq!(
+            self.twap_max_observation_change_per_update,
+            0,
+            AutocratError::InvalidMaxObservationChangePerUpdate
+        );
+
+        require_neq!(
+            self.execution_window_slots,
+            0,
+            AutocratError::InvalidExecutionWindow
+        );
+
+        match self.decision_rule {
+            DecisionRule::DefaultPass { fail_threshold_bps } => require_gte!(
+                MAX_BPS,
+                fail_threshold_bps,
+                AutocratError::InvalidThresholdBps
+            ),
+            DecisionRule::ByProposalType {
+                parameter_change_threshold_bps,
+                treasury_spend_threshold_bps,
+                security_change_threshold_bps,
+            } => {
+                for threshold_bps in [
+                    parameter_change_threshold_bps,
+                    treasury_spend_threshold_bps,
+                    security_change_threshold_bps,
+                ] {
+                    require_gte!(MAX_BPS, threshold_bps, AutocratError::InvalidThresholdBps);
+                }
+            }
+            // a threshold above the starting price means that the pass market
+            // would need to more than double, which no proposal will do
+            DecisionRule::Absolute { threshold } => require_gte!(
+                self.twap_initial_observation,
+                threshold,
+                AutocratError::InvalidAbsoluteThreshold
+            ),
+            // otherwise, no proposal could ever pass
+            DecisionRule::RelativeAboveSpot => {
+                require!(self.spot_amm.is_some(), AutocratError::SpotAmmRequired)
+            }
+            DecisionRule::Relative => {}
+        }
+
+        for max_active_proposals in [
+            self.max_active_proposals,
+            self.max_active_proposals_per_proposer,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            require_neq!(
+                max_active_proposals,
+                0,
+                AutocratError::InvalidMaxActiveProposals
+            );
+        }
+
+        if let Some(treasury_liquidity) = self.treasury_liquidity {
+            require!(
+                treasury_liquidity.quote_amount > 0
+                    && treasury_liquidity.max_base_amount > 0
+                    && treasury_liquidity.max_price_deviation_bps <= MAX_BPS,
+                AutocratError::InvalidTreasuryLiquidity
+            );
+        }
+
+        self.validate_proposal_bond(&self.proposal_bond)
+    }
+
+    pub fn validate_proposal_bond(&self, proposal_bond: &Option<ProposalBond>) -> Result<()> {
+        if let Some(proposal_bond) = proposal_bond {
+            require!(
+                proposal_bond.mint == self.token_mint || proposal_bond.mint == self.usdc_mint,
+                AutocratError::InvalidBondMint
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn default_requirements(&self) -> ProposalRequirements {
+        ProposalRequirements {
+            pass_threshold_bps: self.pass_threshold_bps,
+            slots_per_proposal: self.slots_per_proposal,
+            min_quote_futarchic_liquidity: self.min_quote_futarchic_liquidity,
+            min_base_futarchic_liquidity: self.min_base_futarchic_liquidity,
+        }
+    }
+
+    /// Classifies each instruction into its most specific category, and
+    /// returns the strictest requirements of those categories. Instructions
+    /// that don't match any category, and proposals with no instructions, get
+    /// the DAO's default requirements.
+    pub fn get_proposal_requirements<'a>(
+        &self,
+        instructions: impl IntoIterator<Item = &'a ProposalInstruction>,
+    ) -> ProposalRequirements {
+        instructions
+            .into_iter()
+            .map(|instruction| {
+                self.proposal_categories
+                    .iter()
+                    .filter(|category| category.matches(instruction))
+                    .max_by_key(|category| category.data_prefix_len)
+                    .map_or(self.default_requirements(), |category| {
+                        category.requirements
+                    })
+            })
+            .reduce(ProposalRequirements::strictest)
+            .unwrap_or(self.default_requirements())
+    }
+
+    /// Checks that `spot_amm` is this DAO's spot market, and that one is passed
+    /// exactly when the DAO has one. Whatever is passed gets snapshotted as the
+    /// proposal's spot oracle, so an arbitrary market can't be accepted.
+    pub fn validate_spot_amm(&self, spot_amm: Option<&Account<Amm>>) -> Result<()> {
+        match (self.spot_amm, spot_amm) {
+            (None, None) => Ok(()),
+            (Some(_), None) => err!(AutocratError::MissingSpotAmm),
+            (None, Some(_)) => err!(AutocratError::InvalidSpotAmm),
+            (Some(spot_amm_key), Some(spot_amm)) => {
+                require_keys_eq!(spot_amm.key(), spot_amm_key, AutocratError::InvalidSpotAmm);
+                require!(
+                    spot_amm.base_mint == self.token_mint && spot_amm.quote_mint == self.usdc_mint,
+                    AutocratError::InvalidSpotAmm
+                );
+
+                Ok(())
+            }
+        }
+    }
+
+    pub fn snapshot(&self) -> DaoSnapshot {
+        DaoSnapshot {
+            decision_rule: self.decision_rule,
+            min_average_quote_liquidity: self.min_average_quote_liquidity,
+            execution_delay_slots: self.execution_delay_slots,
+            execution_window_slots: self.execution_window_slots,
+        }
+    }
+}
+
+/// The parts of a DAO's config that decide how a proposal is finalized and
+/// executed. Proposals copy these from their DAO when they're created, so that
+/// `update_dao` can't change the rules for proposals that are already trading.
+/// Proposals already store their threshold and duration separately, since
+/// those depend on their category.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct DaoSnapshot {
+    pub decision_rule: DecisionRule,
+    pub min_average_quote_liquidity: u64,
+    pub execution_delay_slots: u64,
+    pub execution_window_slots: u64,
+}
+
+impl DaoSnapshot {
+    /// Whether a proposal with these TWAPs passes under the DAO's decision rule.
+    /// `pass_threshold_bps` is the proposal's threshold, which depends on its
+    /// category. `proposal_type` is what the proposal's instructions do.
+    /// `spot_twap` is the spot market's TWAP over the proposal, if it
+    /// was recorded.
+    pub fn proposal_passes(
+        &self,
+        pass_market_twap: u128,
+        fail_market_twap: u128,
+        pass_threshold_bps: u16,
+        proposal_type: ProposalType,
+        spot_twap: Option<u128>,
+    ) -> bool {
+        // this can't overflow because each twap can only be MAX_PRICE (~1e31),
+        // MAX_BPS + threshold_bps is at most 1e5, and a u128 can hold 1e38.
+        // still, saturate
+        let beats_by_bps = |a: u128, b: u128, threshold_bps: u16| {
+            a > b.saturating_mul(MAX_BPS.saturating_add(threshold_bps).into()) / MAX_BPS as u128
+        };
+
+        match self.decision_rule {
+            DecisionRule::Relative => {
+                beats_by_bps(pass_market_twap, fail_market_twap, pass_threshold_bps)
+            }
+            DecisionRule::DefaultPass { fail_threshold_bps } => {
+                !beats_by_bps(fail_market_twap, pass_market_twap, fail_threshold_bps)
+            }
+            DecisionRule::Absolute { threshold } => {
+                pass_market_twap > fail_market_twap.saturating_add(threshold)
+            }
+            DecisionRule::RelativeAboveSpot => {
+                // fail closed, since the DAO's `twap_initial_observation` is
+                // too stale to stand in for spot
+                let above_spot = spot_twap.is_some_and(|spot_twap| pass_market_twap >= spot_twap);
+
+                beats_by_bps(pass_market_twap, fail_market_twap, pass_threshold_bps) && above_spot
+            }
+            DecisionRule::ByProposalType {
+                parameter_change_threshold_bps,
+                treasury_spend_threshold_bps,
+                security_change_threshold_bps,
+            } => {
+                let threshold_bps = match proposal_type {
+                    ProposalType::Standard => pass_threshold_bps,
+                    ProposalType::ParameterChange => parameter_change_threshold_bps,
+                    ProposalType::TreasurySpend => {
+                        treasury_spend_threshold_bps.max(pass_threshold_bps)
+                    }
+                    ProposalType::SecurityChange => {
+                        security_change_threshold_bps.max(pass_threshold_bps)
+                    }
+                };
+
+                beats_by_bps(pass_market_twap, fail_market_twap, threshold_bps)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn dao() -> Dao {
+        Dao {
+            treasury_pda_bump: 255,
+            treasury: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            usdc_mint: Pubkey::new_unique(),
+            proposal_count: 0,
+            pass_threshold_bps: DEFAULT_PASS_THRESHOLD_BPS,
+            slots_per_proposal: THREE_DAYS_IN_SLOTS,
+            twap_initial_observation: 400 * 1_000_000_000_000,
+            twap_max_observation_change_per_update: 8 * 1_000_000_000_000,
+            min_quote_futarchic_liquidity: 5_000 * 1_000_000,
+            min_base_futarchic_liquidity: 10 * 1_000_000_000,
+            min_average_quote_liquidity: 0,
+            execution_delay_slots: 0,
+            veto_authority: None,
+            execution_window_slots: TEN_DAYS_IN_SLOTS,
+            decision_rule: DecisionRule::default(),
+            proposal_categories: vec![],
+            proposal_bond: None,
+            active_proposal_count: 0,
+            max_active_proposals: None,
+            max_active_proposals_per_proposer: None,
+            spot_amm: None,
+            treasury_liquidity: None,
+            spending_limits: vec![],
+        }
+    }
+
+    fn assert_invalid(dao: Dao, error: AutocratError) {
+        assert_eq!(dao.validate_params().unwrap_err(), error.into());
+    }
+
+    #[test]
+    fn default_params_are_valid() {
+        assert!(dao().validate_params().is_ok());
+    }
+
+    #[test]
+    fn pass_threshold_bps_is_at_most_max_bps() {
+        let mut dao = dao();
+
+        dao.pass_threshold_bps = MAX_BPS;
+        assert!(dao.validate_params().is_ok());
+
+        dao.pass_threshold_bps = MAX_BPS + 1;
+        assert_invalid(dao, AutocratError::InvalidThresholdBps);
+    }
+
+    #[test]
+    fn slots_per_proposal_is_at_least_the_minimum() {
+        let mut dao = dao();
+
+        dao.slots_per_proposal = MIN_SLOTS_PER_PROPOSAL;
+        assert!(dao.validate_params().is_ok());
+
+        dao.slots_per_proposal = MIN_SLOTS_PER_PROPOSAL - 1;
+        assert_invalid(dao.clone(), AutocratError::InvalidSlotsPerProposal);
+
+        dao.slots_per_proposal = 1;
+        assert_invalid(dao.clone(), AutocratError::InvalidSlotsPerProposal);
+
+        dao.slots_per_proposal = 0;
+        assert_invalid(dao, AutocratError::InvalidSlotsPerProposal);
+    }
+
+    #[test]
+    fn category_requirements_are_validated() {
+        let mut dao = dao();
+        let mut category = ProposalCategory {
+            program_id: Pubkey::new_unique(),
+            data_prefix: [0; 8],
+            data_prefix_len: 0,
+            requirements: dao.default_requirements(),
+        };
+
+        dao.proposal_categories = vec![category];
+        assert!(dao.validate_params().is_ok());
+
+        category.requirements.pass_threshold_bps = MAX_BPS + 1;
+        dao.proposal_categories = vec![category];
+        assert_invalid(dao.clone(), AutocratError::InvalidThresholdBps);
+
+        category.requirements.pass_threshold_bps = MAX_BPS;
+        category.requirements.slots_per_proposal = 0;
+        dao.proposal_categories = vec![category];
+        assert_invalid(dao, AutocratError::InvalidSlotsPerProposal);
+    }
+
+    #[test]
+    fn proposals_get_their_categories_requirements() {
+        let mut dao = dao();
+        let program_id = Pubkey::new_unique();
+
+        let requirements = |pass_threshold_bps, slots_per_proposal| ProposalRequirements {
+            pass_threshold_bps,
+            slots_per_proposal,
+            ..dao.default_requirements()
+        };
+        let program_category = ProposalCategory {
+            program_id,
+            data_prefix: [0; 8],
+            data_prefix_len: 0,
+            requirements: requirements(1_000, MIN_SLOTS_PER_PROPOSAL),
+        };
+        let instruction_category = ProposalCategory {
+            program_id,
+            data_prefix: [1, 2, 0, 0, 0, 0, 0, 0],
+            data_prefix_len: 2,
+            requirements: requirements(500, 2 * MIN_SLOTS_PER_PROPOSAL),
+        };
+        dao.proposal_categories = vec![program_category, instruction_category];
+
+        let instruction = |program_id, data: &[u8]| ProposalInstruction {
+            program_id,
+            accounts: vec![],
+            data: data.to_vec(),
+        };
+        let uncategorized = instruction(Pubkey::new_unique(), &[1, 2]);
+        let program = instruction(program_id, &[1, 3]);
+        let specific = instruction(program_id, &[1, 2, 3]);
+
+        assert_eq!(
+            dao.get_proposal_requirements(&[]),
+            dao.default_requirements()
+        );
+        assert_eq!(
+            dao.get_proposal_requirements([&uncategorized]),
+            dao.default_requirements()
+        );
+        assert_eq!(
+            dao.get_proposal_requirements([&program]),
+            program_category.requirements
+        );
+
+        // the category with the longest matching prefix wins, even if it has a
+        // lower threshold
+        assert_eq!(
+            dao.get_proposal_requirements([&specific]),
+            instruction_category.requirements
+        );
+
+        // proposals with several instructions get the strictest of each
+        assert_eq!(
+            dao.get_proposal_requirements([&specific, &program, &uncategorized]),
+            instruction_category
+                .requirements
+                .strictest(program_category.requirements)
+                .strictest(dao.default_requirements())
+        );
+    }
+
+    #[test]
+    fn twap_initial_observation_is_not_zero() {
+        let mut dao = dao();
+
+        dao.twap_initial_observation = 0;
+        assert_invalid(dao, AutocratError::InvalidTwapInitialObservation);
+    }
+
+    #[test]
+    fn twap_max_observation_change_per_update_is_not_zero() {
+        let mut dao = dao();
+
+        dao.twap_max_observation_change_per_update = 0;
+        assert_invalid(dao, AutocratError::InvalidMaxObservationChangePerUpdate);
+    }
+
+    #[test]
+    fn execution_window_slots_is_not_zero() {
+        let mut dao = dao();
+
+        dao.execution_window_slots = 0;
+        assert_invalid(dao, AutocratError::InvalidExecutionWindow);
+    }
+
+    #[test]
+    fn decision_rule_thresholds_are_at_most_max_bps() {
+        let mut dao = dao();
+
+        dao.decision_rule = DecisionRule::DefaultPass {
+            fail_threshold_bps: MAX_BPS,
+        };
+        assert!(dao.validate_params().is_ok());
+
+        dao.decision_rule = DecisionRule::DefaultPass {
+            fail_threshold_bps: MAX_BPS + 1,
+        };
+        assert_invalid(dao.clone(), AutocratError::InvalidThresholdBps);
+
+        let by_proposal_type = |thresholds: [u16; 3]| DecisionRule::ByProposalType {
+            parameter_change_threshold_bps: thresholds[0],
+            treasury_spend_threshold_bps: thresholds[1],
+            security_change_threshold_bps: thresholds[2],
+        };
+
+        dao.decision_rule = by_proposal_type([MAX_BPS; 3]);
+        assert!(dao.validate_params().is_ok());
+
+        for i in 0..3 {
+            let mut thresholds = [MAX_BPS; 3];
+            thresholds[i] += 1;
+
+            dao.decision_rule = by_proposal_type(thresholds);
+            assert_invalid(dao.clone(), AutocratError::InvalidThresholdBps);
+        }
+    }
+
+    #[test]
+    fn absolute_threshold_is_at_most_the_initial_observation() {
+        let mut dao = dao();
+
+        dao.decision_rule = DecisionRule::Absolute {
+            threshold: dao.twap_initial_observation,
+        };
+        assert!(dao.validate_params().is_ok());
+
+        dao.decision_rule = DecisionRule::Absolute {
+            threshold: dao.twap_initial_observation + 1,
+        };
+        assert_invalid(dao, AutocratError::InvalidAbsoluteThreshold);
+    }
+
+    #[test]
+    fn relative_above_spot_needs_a_spot_amm() {
+        let mut dao = dao();
+        dao.decision_rule = DecisionRule::RelativeAboveSpot;
+
+        dao.spot_amm = Some(Pubkey::new_unique());
+        assert!(dao.validate_params().is_ok());
+
+        dao.spot_amm = None;
+        assert_invalid(dao, AutocratError::SpotAmmRequired);
+    }
+
+    #[test]
+    fn max_active_proposals_is_not_zero() {
+        let mut dao = dao();
+
+        dao.max_active_proposals = Some(1);
+        dao.max_active_proposals_per_proposer = Some(1);
+        assert!(dao.validate_params().is_ok());
+
+        dao.max_active_proposals = Some(0);
+        assert_invalid(dao.clone(), AutocratError::InvalidMaxActiveProposals);
+
+        dao.max_active_proposals = None;
+        dao.max_active_proposals_per_proposer = Some(0);
+        assert_invalid(dao, AutocratError::InvalidMaxActiveProposals);
+    }
+
+    #[test]
+    fn treasury_liquidity_is_valid() {
+        let mut dao = dao();
+        let treasury_liquidity = TreasuryLiquidity {
+            quote_amount: 1,
+            max_base_amount: 1,
+            max_price_deviation_bps: MAX_BPS,
+        };
+
+        dao.treasury_liquidity = Some(treasury_liquidity);
+        assert!(dao.validate_params().is_ok());
+
+        dao.treasury_liquidity = Some(TreasuryLiquidity {
+            quote_amount: 0,
+            ..treasury_liquidity
+        });
+        assert_invalid(dao.clone(), AutocratError::InvalidTreasuryLiquidity);
+
+        dao.treasury_liquidity = Some(TreasuryLiquidity {
+            max_base_amount: 0,
+            ..treasury_liquidity
+        });
+        assert_invalid(dao.clone(), AutocratError::InvalidTreasuryLiquidity);
+
+        dao.treasury_liquidity = Some(TreasuryLiquidity {
+            max_price_deviation_bps: MAX_BPS + 1,
+            ..treasury_liquidity
+        });
+        assert_invalid(dao, AutocratError::InvalidTreasuryLiquidity);
+    }
+
+    #[test]
+    fn proposal_bond_mint_is_the_dao_token_or_usdc() {
+        let mut dao = dao();
+
+        dao.proposal_bond = Some(ProposalBond {
+            mint: dao.usdc_mint,
+            amount: 1,
+            refund_quote_volume: 0,
+        });
+        assert!(dao.validate_params().is_ok());
+
+        dao.proposal_bond = Some(ProposalBond {
+            mint: Pubkey::new_unique(),
+            amount: 1,
+            refund_quote_volume: 0,
+        });
+        assert_invalid(dao, AutocratError::InvalidBondMint);
+    }
+
+    #[test]
+    fn relative_above_spot_needs_a_spot_twap() {
+        let mut dao = dao();
+        dao.decision_rule = DecisionRule::RelativeAboveSpot;
+        let snapshot = dao.snapshot();
+
+        assert!(snapshot.proposal_passes(110, 100, 500, ProposalType::Standard, Some(110)));
+        assert!(!snapshot.proposal_passes(110, 100, 500, ProposalType::Standard, Some(111)));
+        assert!(!snapshot.proposal_passes(110, 100, 500, ProposalType::Standard, None));
+    }
+
+    #[test]
+    fn bonds_are_refunded_for_passed_or_traded_proposals() {
+        let bond = ProposalBond {
+            mint: Pubkey::new_unique(),
+            amount: 1,
+            refund_quote_volume: 1_000,
+        };
+
+        for state in [
+            ProposalState::Passed,
+            ProposalState::Executed,
+            ProposalState::Expired,
+        ] {
+            assert!(bond.is_refunded(state, 0, 0));
+        }
+
+        // vetoed proposals are judged by their markets, like failed ones
+        for state in [
+            ProposalState::Failed,
+            ProposalState::Vetoed,
+            ProposalState::Cancelled,
+        ] {
+            assert!(bond.is_refunded(state, 1_000, 1_000));
+            assert!(!bond.is_refunded(state, 999, 1_000));
+            assert!(!bond.is_refunded(state, 1_000, 999));
+        }
+    }
 }
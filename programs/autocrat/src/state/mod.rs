@@ -1,7 +1,19 @@
 pub mod dao;
+pub mod multi_option_proposal;
 pub mod proposal;
+pub mod proposal_category;
+pub mod proposal_instructions;
+pub mod proposal_setup;
+pub mod proposer_record;
+pub mod spending_limit;
 
 pub use dao::*;
+pub use multi_option_proposal::*;
 pub use proposal::*;
+pub use proposal_category::*;
+pub use proposal_instructions::*;
+pub use proposal_setup::*;
+pub use proposer_record::*;
+pub use spending_limit::*;
 
 pub use super::*;
@@ -0,0 +1,241 @@
+use super::*;
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, PartialEq, Eq)]
+pub struct ProposalOption {
+    pub amm: Pubkey,
+    pub lp_tokens_locked: u64,
+    /// The `ProposalInstructions` to execute if this option wins. `None` for
+    /// the status quo.
+    pub instructions: Option<Pubkey>,
+    /// What the option's instructions do, which can change the threshold that
+    /// it has to beat the status quo by. Recorded when the proposal is
+    /// created, since its instructions are frozen from then on.
+    pub proposal_type: ProposalType,
+    /// Taken of the option's market when the proposal was created.
+    pub market_snapshot: MarketSnapshot,
+}
+
+/// The inputs that `finalize_multi_option_proposal` decided a proposal with,
+/// like `ProposalDecision`.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, PartialEq, Eq)]
+pub struct MultiOptionDecision {
+    /// In the same order as the proposal's options.
+    pub option_twaps: Vec<u128>,
+    /// If this is false, the status quo won regardless of the TWAPs.
+    pub markets_had_liquidity: bool,
+}
+
+/// A proposal with more than two outcomes, such as "which of these three
+/// vendors should we hire?". Each outcome of the question has its own market
+/// and its own instructions, and the option whose market has the highest TWAP
+/// wins, as long as it beats the status quo under the DAO's decision rule.
+#[account]
+pub struct MultiOptionProposal {
+    pub number: u32,
+    pub proposer: Pubkey,
+    pub description_url: String,
+    pub slot_enqueued: u64,
+    /// 0 until the proposal is finalized.
+    pub slot_finalized: u64,
+    /// `Passed` if an option other than the status quo won.
+    pub state: ProposalState,
+    /// In the same order as the question's outcomes. Option 0 is the status quo,
+    /// which wins if no other option beats it.
+    pub options: Vec<ProposalOption>,
+    /// `None` until the proposal is finalized.
+    pub winning_option: Option<u8>,
+    pub num_instructions_executed: u16,
+    /// The strictest requirements of any of the options' categories.
+    pub pass_threshold_bps: u16,
+    pub slots_per_proposal: u64,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub dao: Pubkey,
+    pub question: Pubkey,
+    pub nonce: u64,
+    pub pda_bump: u8,
+    pub dao_snapshot: DaoSnapshot,
+    /// Taken if the DAO had a `spot_amm` when this proposal was created.
+    pub spot_oracle_snapshot: Option<SpotOracleSnapshot>,
+    /// The spot market's TWAP over this proposal, recorded when it's finalized.
+    pub spot_twap: Option<u128>,
+    /// The DAO's capped `spending_limits` when this proposal was created,
+    /// which its instructions are checked against.
+    pub spending_limits: Vec<SpendingLimit>,
+    /// How much of each limited mint this proposal has sent out of the
+    /// treasury. See `SpendingLimit`.
+    pub treasury_outflows: Vec<TreasuryOutflow>,
+    /// Set when the proposal is finalized, unless it was cancelled.
+    pub decision: Option<MultiOptionDecision>,
+}
+
+impl MultiOptionProposal {
+    /// The account space needed to store a proposal with this description and
+    /// this many options, including the discriminator.
+    pub fn space(description_url: &str, num_options: usize) -> usize {
+        8 + std::mem::size_of::<Self>()
+            + description_url.len()
+            + num_options * std::mem::size_of::<ProposalOption>()
+            + num_options * std::mem::size_of::<u128>()
+            + MAX_SPENDING_LIMITS * std::mem::size_of::<SpendingLimit>()
+            + MAX_SPENDING_LIMITS * std::mem::size_of::<TreasuryOutflow>()
+    }
+
+    /// Which option wins with `decision`. Every option is compared against the
+    /// status quo using the DAO's decision rule, with the threshold for the
+    /// option's `ProposalType`, and the one with the
+    /// highest TWAP wins. Ties go to the earlier option, to keep things
+    /// predictable.
+    pub fn winning_option_of(&self, decision: &MultiOptionDecision) -> usize {
+        let twaps = &decision.option_twaps;
+
+        twaps
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(i, twap)| {
+                decision.markets_had_liquidity
+                    && self.dao_snapshot.proposal_passes(
+                        **twap,
+                        twaps[0],
+                        self.pass_threshold_bps,
+                        self.options[*i].proposal_type,
+                        self.spot_twap,
+                    )
+            })
+            .min_by_key(|(i, twap)| (std::cmp::Reverse(**twap), *i))
+            .map_or(0, |(i, _)| i)
+    }
+
+    /// The instructions of the winning option, if an option other than the
+    /// status quo won.
+    pub fn winning_instructions(&self) -> Option<Pubkey> {
+        self.winning_option
+            .and_then(|option| self.options[option as usize].instructions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::state::dao::tests::dao;
+
+    fn proposal(num_options: usize) -> MultiOptionProposal {
+        let mut dao = dao();
+        dao.decision_rule = DecisionRule::ByProposalType {
+            parameter_change_threshold_bps: 2_000,
+            treasury_spend_threshold_bps: 1_000,
+            security_change_threshold_bps: 3_000,
+        };
+
+        MultiOptionProposal {
+            number: 0,
+            proposer: Pubkey::new_unique(),
+            description_url: String::new(),
+            slot_enqueued: 1_000,
+            slot_finalized: 0,
+            state: ProposalState::Pending,
+            options: vec![
+                ProposalOption {
+                    amm: Pubkey::new_unique(),
+                    lp_tokens_locked: 0,
+                    instructions: None,
+                    proposal_type: ProposalType::Standard,
+                    market_snapshot: MarketSnapshot::default(),
+                };
+                num_options
+            ],
+            winning_option: None,
+            num_instructions_executed: 0,
+            pass_threshold_bps: 500,
+            slots_per_proposal: dao.slots_per_proposal,
+            base_vault: Pubkey::new_unique(),
+            quote_vault: Pubkey::new_unique(),
+            dao: Pubkey::new_unique(),
+            question: Pubkey::new_unique(),
+            nonce: 0,
+            pda_bump: 255,
+            dao_snapshot: dao.snapshot(),
+            spot_oracle_snapshot: None,
+            spot_twap: None,
+            spending_limits: vec![],
+            treasury_outflows: vec![],
+            decision: None,
+        }
+    }
+
+    fn decision(option_twaps: Vec<u128>) -> MultiOptionDecision {
+        MultiOptionDecision {
+            option_twaps,
+            markets_had_liquidity: true,
+        }
+    }
+
+    #[test]
+    fn highest_option_that_beats_the_status_quo_wins() {
+        let proposal = proposal(4);
+
+        assert_eq!(
+            proposal.winning_option_of(&decision(vec![100, 104, 110, 108])),
+            2
+        );
+        assert_eq!(
+            proposal.winning_option_of(&decision(vec![100, 110, 110, 108])),
+            1
+        );
+        assert_eq!(
+            proposal.winning_option_of(&decision(vec![100, 105, 104, 90])),
+            0
+        );
+        assert_eq!(
+            proposal.winning_option_of(&MultiOptionDecision {
+                markets_had_liquidity: false,
+                ..decision(vec![100, 200, 200, 200])
+            }),
+            0
+        );
+    }
+
+    #[test]
+    fn options_need_the_threshold_for_their_type() {
+        let mut proposal = proposal(3);
+        proposal.options[2].proposal_type = ProposalType::ParameterChange;
+
+        // 15% clears the regular 5% threshold but not the 20% one
+        assert_eq!(
+            proposal.winning_option_of(&decision(vec![100, 110, 115])),
+            1
+        );
+        assert_eq!(
+            proposal.winning_option_of(&decision(vec![100, 104, 115])),
+            0
+        );
+        assert_eq!(
+            proposal.winning_option_of(&decision(vec![100, 110, 121])),
+            2
+        );
+
+        proposal.options[2].proposal_type = ProposalType::SecurityChange;
+        assert_eq!(
+            proposal.winning_option_of(&decision(vec![100, 110, 125])),
+            1
+        );
+        assert_eq!(
+            proposal.winning_option_of(&decision(vec![100, 110, 131])),
+            2
+        );
+
+        // a spend threshold below the regular one doesn't make spending easier
+        proposal.options[2].proposal_type = ProposalType::TreasurySpend;
+        proposal.pass_threshold_bps = 1_500;
+        assert_eq!(
+            proposal.winning_option_of(&decision(vec![100, 100, 112])),
+            0
+        );
+        assert_eq!(
+            proposal.winning_option_of(&decision(vec![100, 100, 116])),
+            2
+        );
+    }
+}
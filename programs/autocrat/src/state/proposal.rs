@@ -6,6 +6,9 @@ pub enum ProposalState {
     Passed,
     Failed,
     Executed,
+    Cancelled,
+    Vetoed,
+    Expired,
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, PartialEq, Eq)]
@@ -22,14 +25,102 @@ pub struct ProposalInstruction {
     pub data: Vec<u8>,
 }
 
+/// The state of the DAO's spot market's oracle when a proposal was created, so
+/// that its TWAP over the proposal can be computed when it's finalized.
+#[derive(Clone, Copy, AnchorSerialize, AnchorDeserialize, Debug, PartialEq, Eq)]
+pub struct SpotOracleSnapshot {
+    pub amm: Pubkey,
+    pub aggregator: u128,
+    pub last_updated_slot: u64,
+}
+
+impl SpotOracleSnapshot {
+    pub fn new(spot_amm: &Account<Amm>) -> Self {
+        Self {
+            amm: spot_amm.key(),
+            aggregator: spot_amm.oracle.aggregator,
+            last_updated_slot: spot_amm.oracle.last_updated_slot,
+        }
+    }
+
+    /// The spot market's TWAP between this snapshot and its last update.
+    pub fn twap_until(&self, spot_amm: &Amm) -> Result<u128> {
+        let slots_passed = spot_amm
+            .oracle
+            .last_updated_slot
+            .saturating_sub(self.last_updated_slot);
+
+        require_neq!(slots_passed, 0, AutocratError::MarketsTooYoung);
+
+        // the aggregator wraps on overflow, so the difference is still right
+        // as long as it hasn't wrapped more than once
+        Ok(spot_amm.oracle.aggregator.wrapping_sub(self.aggregator) / slots_passed as u128)
+    }
+}
+
+/// The state of one of a proposal's markets when the proposal was created, so
+/// that what happened in the market over the proposal can be measured when
+/// it's finalized.
+#[derive(Clone, Copy, Default, AnchorSerialize, AnchorDeserialize, Debug, PartialEq, Eq)]
+pub struct MarketSnapshot {
+    pub quote_liquidity_aggregator: u128,
+    pub quote_volume: u128,
+}
+
+impl MarketSnapshot {
+    pub fn new(amm: &Amm, slot: u64) -> Self {
+        Self {
+            quote_liquidity_aggregator: amm.get_quote_liquidity_aggregator(slot),
+            quote_volume: amm.quote_volume,
+        }
+    }
+
+    /// How much quote has been swapped in the market since the snapshot.
+    pub fn quote_volume_since(&self, amm: &Amm) -> u128 {
+        amm.quote_volume.saturating_sub(self.quote_volume)
+    }
+
+    /// The market's average quote liquidity between `slot_enqueued`, when the
+    /// snapshot was taken, and `slot`.
+    pub fn average_quote_liquidity(&self, amm: &Amm, slot_enqueued: u64, slot: u64) -> Result<u64> {
+        amm.get_average_quote_liquidity_since(slot_enqueued, self.quote_liquidity_aggregator, slot)
+    }
+}
+
+/// The inputs that `finalize_proposal` decided a proposal with, kept so that
+/// anyone can audit the decision from chain state. Together with the
+/// proposal's `pass_threshold_bps`, `spot_twap` and `dao_snapshot`, these are
+/// everything that `DaoSnapshot::proposal_passes` looked at.
+#[derive(Clone, Copy, AnchorSerialize, AnchorDeserialize, Debug, PartialEq, Eq)]
+pub struct ProposalDecision {
+    pub pass_market_twap: u128,
+    pub fail_market_twap: u128,
+    pub proposal_type: ProposalType,
+    /// If this is false, the proposal failed regardless of its TWAPs.
+    pub markets_had_liquidity: bool,
+}
+
 #[account]
 pub struct Proposal {
+    /// `PROPOSAL_VERSION` for proposals created or migrated by this version of
+    /// autocrat. See `migrate_proposal`. This is always the first byte after
+    /// the discriminator, so clients can filter on it.
+    pub version: u8,
     pub number: u32,
     pub proposer: Pubkey,
     pub description_url: String,
     pub slot_enqueued: u64,
+    /// 0 until the proposal is finalized.
+    pub slot_finalized: u64,
     pub state: ProposalState,
-    pub instruction: ProposalInstruction,
+    /// The `ProposalInstructions` account holding the instructions to execute,
+    /// in order, if this proposal passes.
+    pub instructions: Pubkey,
+    pub num_instructions_executed: u16,
+    /// Taken from the proposal's category when it's initialized, so that
+    /// later changes to the DAO don't affect it.
+    pub pass_threshold_bps: u16,
+    pub slots_per_proposal: u64,
     pub pass_amm: Pubkey,
     pub fail_amm: Pubkey,
     pub base_vault: Pubkey,
@@ -44,6 +135,275 @@ pub struct Proposal {
     pub nonce: u64,
     pub pda_bump: u8,
     pub question: Pubkey,
+    /// The DAO's bond when this proposal was created, if it had one. The bond
+    /// is held in a token account with the seeds `[b"bond", proposal]` until
+    /// it's settled with `settle_proposal_bond`.
+    pub bond: Option<ProposalBond>,
+    pub bond_settled: bool,
+    /// Taken if the DAO had a `spot_amm` when this proposal was created.
+    pub spot_oracle_snapshot: Option<SpotOracleSnapshot>,
+    /// The spot market's TWAP over this proposal, recorded when it's finalized.
+    pub spot_twap: Option<u128>,
+    /// Taken of each market when this proposal was created, indexed by outcome.
+    pub market_snapshots: [MarketSnapshot; 2],
+    /// How much quote was swapped in each market over this proposal, indexed
+    /// by outcome. Recorded when the proposal is finalized or cancelled, so
+    /// that the bond can be settled without trusting the markets afterwards.
+    pub quote_volumes: [u128; 2],
+    /// The LP tokens that the treasury got for seeding each market, indexed by
+    /// outcome. These are held in the same accounts as the proposer's locked LP.
+    /// Set back to 0 once `settle_treasury_positions` has removed them.
+    pub treasury_lp_tokens: [u64; 2],
+    /// The DAO's `max_price_deviation_bps` when the treasury last seeded one of
+    /// this proposal's markets, which also bounds the price that
+    /// `settle_treasury_positions` removes the treasury's liquidity at.
+    pub treasury_max_price_deviation_bps: u16,
+    /// Set when the proposal is finalized, unless it was cancelled.
+    pub decision: Option<ProposalDecision>,
+    pub dao_snapshot: DaoSnapshot,
+    /// The DAO's capped `spending_limits` when this proposal was created,
+    /// which its instructions are checked against.
+    pub spending_limits: Vec<SpendingLimit>,
+    /// How much of each limited mint this proposal has sent out of the
+    /// treasury. See `SpendingLimit`.
+    pub treasury_outflows: Vec<TreasuryOutflow>,
+}
+
+/// The layout of proposals created before proposals were versioned, when every
+/// proposal held exactly one instruction. These can be brought up to date
+/// with `migrate_proposal`.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ProposalV0 {
+    pub number: u32,
+    pub proposer: Pubkey,
+    pub description_url: String,
+    pub slot_enqueued: u64,
+    pub state: ProposalState,
+    pub instruction: ProposalInstruction,
+    pub pass_amm: Pubkey,
+    pub fail_amm: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub dao: Pubkey,
+    pub pass_lp_tokens_locked: u64,
+    pub fail_lp_tokens_locked: u64,
+    pub nonce: u64,
+    pub pda_bump: u8,
+    pub question: Pubkey,
+}
+
+/// The parts of a proposal's lifecycle that work the same way for every kind
+/// of proposal, so that the instructions for each kind can share them.
+pub trait ProposalLifecycle {
+    fn state(&self) -> ProposalState;
+    fn set_state(&mut self, state: ProposalState);
+    fn slot_enqueued(&self) -> u64;
+    fn slots_per_proposal(&self) -> u64;
+    fn slot_finalized(&self) -> u64;
+    fn dao_snapshot(&self) -> &DaoSnapshot;
+    fn num_instructions_executed(&self) -> u16;
+    fn set_num_instructions_executed(&mut self, num_instructions_executed: u16);
+    fn spending_limits(&self) -> &[SpendingLimit];
+    fn treasury_outflows_mut(&mut self) -> &mut Vec<TreasuryOutflow>;
+
+    /// The first slot at which a passed proposal can be executed.
+    fn execution_start_slot(&self) -> u64 {
+        self.slot_finalized()
+            .saturating_add(self.dao_snapshot().execution_delay_slots)
+    }
+
+    /// The first slot at which a passed proposal can no longer be executed.
+    fn execution_end_slot(&self) -> u64 {
+        self.execution_start_slot()
+            .saturating_add(self.dao_snapshot().execution_window_slots)
+    }
+
+    fn validate_cancel(&self, slot: u64) -> Result<()> {
+        require!(
+            self.state() == ProposalState::Pending,
+            AutocratError::ProposalAlreadyFinalized
+        );
+
+        require!(
+            slot < self
+                .slot_enqueued()
+                .saturating_add(PROPOSAL_CANCELLATION_WINDOW_SLOTS),
+            AutocratError::CancellationWindowElapsed
+        );
+
+        Ok(())
+    }
+
+    fn validate_finalize(&self, slot: u64) -> Result<()> {
+        require!(
+            slot >= self.slot_enqueued() + self.slots_per_proposal(),
+            AutocratError::ProposalTooYoung
+        );
+
+        require!(
+            self.state() == ProposalState::Pending,
+            AutocratError::ProposalAlreadyFinalized
+        );
+
+        Ok(())
+    }
+
+    fn validate_execute(&self, slot: u64) -> Result<()> {
+        require!(
+            self.state() == ProposalState::Passed,
+            AutocratError::ProposalNotPassed
+        );
+
+        require_gte!(
+            slot,
+            self.execution_start_slot(),
+            AutocratError::ExecutionDelayNotElapsed
+        );
+
+        require!(
+            slot < self.execution_end_slot(),
+            AutocratError::ExecutionWindowElapsed
+        );
+
+        Ok(())
+    }
+
+    fn validate_expire(&self, slot: u64) -> Result<()> {
+        require!(
+            self.state() == ProposalState::Passed,
+            AutocratError::ProposalNotPassed
+        );
+
+        require_gte!(
+            slot,
+            self.execution_end_slot(),
+            AutocratError::ExecutionWindowNotElapsed
+        );
+
+        Ok(())
+    }
+
+    fn validate_veto(&self, dao: &Dao, veto_authority: Pubkey) -> Result<()> {
+        require!(
+            dao.veto_authority == Some(veto_authority),
+            AutocratError::InvalidVetoAuthority
+        );
+
+        require!(
+            self.state() == ProposalState::Passed,
+            AutocratError::ProposalNotPassed
+        );
+
+        Ok(())
+    }
+
+    /// Counts the instruction that was just executed, if there was one, and
+    /// marks the proposal as executed once all `num_instructions` have been.
+    /// Proposals without any instructions are executed by the first call.
+    fn record_execution(&mut self, executed_instruction: bool, num_instructions: usize) {
+        if executed_instruction {
+            self.set_num_instructions_executed(self.num_instructions_executed() + 1);
+        }
+
+        if self.num_instructions_executed() as usize == num_instructions {
+            self.set_state(ProposalState::Executed);
+        }
+    }
+}
+
+/// Implements `ProposalLifecycle` for a proposal type with the fields it needs.
+macro_rules! impl_proposal_lifecycle {
+    ($proposal:ty) => {
+        impl ProposalLifecycle for $proposal {
+            fn state(&self) -> ProposalState {
+                self.state
+            }
+
+            fn set_state(&mut self, state: ProposalState) {
+                self.state = state;
+            }
+
+            fn slot_enqueued(&self) -> u64 {
+                self.slot_enqueued
+            }
+
+            fn slots_per_proposal(&self) -> u64 {
+                self.slots_per_proposal
+            }
+
+            fn slot_finalized(&self) -> u64 {
+                self.slot_finalized
+            }
+
+            fn dao_snapshot(&self) -> &DaoSnapshot {
+                &self.dao_snapshot
+            }
+
+            fn num_instructions_executed(&self) -> u16 {
+                self.num_instructions_executed
+            }
+
+            fn set_num_instructions_executed(&mut self, num_instructions_executed: u16) {
+                self.num_instructions_executed = num_instructions_executed;
+            }
+
+            fn spending_limits(&self) -> &[SpendingLimit] {
+                &self.spending_limits
+            }
+
+            fn treasury_outflows_mut(&mut self) -> &mut Vec<TreasuryOutflow> {
+                &mut self.treasury_outflows
+            }
+        }
+    };
+}
+
+impl_proposal_lifecycle!(Proposal);
+impl_proposal_lifecycle!(MultiOptionProposal);
+
+impl Proposal {
+    /// The account space needed to store a proposal with this description,
+    /// including the discriminator.
+    pub fn space(description_url: &str) -> usize {
+        // `size_of` counts 24 bytes for the `String` header, which covers the
+        // 4 bytes that borsh uses to store its length
+        8 + std::mem::size_of::<Self>()
+            + description_url.len()
+            + MAX_SPENDING_LIMITS * std::mem::size_of::<SpendingLimit>()
+            + MAX_SPENDING_LIMITS * std::mem::size_of::<TreasuryOutflow>()
+    }
+
+    /// Whether the proposal passes with `decision`, under the threshold and
+    /// DAO config that it was created with.
+    pub fn passes(&self, decision: &ProposalDecision) -> bool {
+        decision.markets_had_liquidity
+            && self.dao_snapshot.proposal_passes(
+                decision.pass_market_twap,
+                decision.fail_market_twap,
+                self.pass_threshold_bps,
+                decision.proposal_type,
+                self.spot_twap,
+            )
+    }
+
+    /// Records how much quote was swapped in each market since the proposal
+    /// was created, which decides whether its bond is refunded.
+    pub fn record_quote_volumes(&mut self, pass_amm: &Amm, fail_amm: &Amm) {
+        self.quote_volumes[PASS_INDEX] =
+            self.market_snapshots[PASS_INDEX].quote_volume_since(pass_amm);
+        self.quote_volumes[FAIL_INDEX] =
+            self.market_snapshots[FAIL_INDEX].quote_volume_since(fail_amm);
+    }
+
+    /// Marks the treasury's LP tokens for one of the markets as settled and
+    /// returns how many to remove, given the balance of the account they're
+    /// held in. The proposer's LP is held in the same account, so this never
+    /// takes more than the treasury got for seeding.
+    pub fn take_treasury_lp_tokens(&mut self, outcome_index: usize, lp_balance: u64) -> u64 {
+        let lp_tokens = std::cmp::min(self.treasury_lp_tokens[outcome_index], lp_balance);
+        self.treasury_lp_tokens[outcome_index] = 0;
+        lp_tokens
+    }
 }
 
 impl From<&ProposalInstruction> for Instruction {
@@ -65,3 +425,240 @@ impl From<&ProposalAccount> for AccountMeta {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::state::dao::tests::dao;
+    use amm::state::SwapType;
+
+    fn proposal() -> Proposal {
+        let dao = dao();
+
+        Proposal {
+            version: PROPOSAL_VERSION,
+            number: 0,
+            proposer: Pubkey::new_unique(),
+            description_url: String::new(),
+            slot_enqueued: 1_000,
+            slot_finalized: 0,
+            state: ProposalState::Pending,
+            instructions: Pubkey::new_unique(),
+            num_instructions_executed: 0,
+            pass_threshold_bps: dao.pass_threshold_bps,
+            slots_per_proposal: dao.slots_per_proposal,
+            pass_amm: Pubkey::new_unique(),
+            fail_amm: Pubkey::new_unique(),
+            base_vault: Pubkey::new_unique(),
+            quote_vault: Pubkey::new_unique(),
+            dao: Pubkey::new_unique(),
+            pass_lp_tokens_locked: 0,
+            fail_lp_tokens_locked: 0,
+            nonce: 0,
+            pda_bump: 255,
+            question: Pubkey::new_unique(),
+            bond: None,
+            bond_settled: false,
+            spot_oracle_snapshot: None,
+            spot_twap: None,
+            market_snapshots: [MarketSnapshot::default(); 2],
+            quote_volumes: [0; 2],
+            treasury_lp_tokens: [0; 2],
+            treasury_max_price_deviation_bps: 0,
+            decision: None,
+            dao_snapshot: dao.snapshot(),
+            spending_limits: vec![],
+            treasury_outflows: vec![],
+        }
+    }
+
+    #[test]
+    fn execution_resumes_until_every_instruction_is_executed() {
+        let mut proposal = proposal();
+        proposal.state = ProposalState::Passed;
+
+        proposal.record_execution(true, 3);
+        proposal.record_execution(true, 3);
+        assert_eq!(proposal.num_instructions_executed, 2);
+        assert!(proposal.state == ProposalState::Passed);
+
+        proposal.record_execution(true, 3);
+        assert_eq!(proposal.num_instructions_executed, 3);
+        assert!(proposal.state == ProposalState::Executed);
+    }
+
+    #[test]
+    fn proposals_without_instructions_are_executed_by_the_first_call() {
+        let mut proposal = proposal();
+        proposal.state = ProposalState::Passed;
+
+        proposal.record_execution(false, 0);
+        assert_eq!(proposal.num_instructions_executed, 0);
+        assert!(proposal.state == ProposalState::Executed);
+    }
+
+    #[test]
+    fn proposals_can_only_be_cancelled_during_the_grace_window() {
+        let mut proposal = proposal();
+        let window_end = proposal.slot_enqueued + PROPOSAL_CANCELLATION_WINDOW_SLOTS;
+
+        assert!(proposal.validate_cancel(proposal.slot_enqueued).is_ok());
+        assert!(proposal.validate_cancel(window_end - 1).is_ok());
+        assert_eq!(
+            proposal.validate_cancel(window_end).unwrap_err(),
+            AutocratError::CancellationWindowElapsed.into()
+        );
+
+        proposal.state = ProposalState::Failed;
+        assert_eq!(
+            proposal
+                .validate_cancel(proposal.slot_enqueued)
+                .unwrap_err(),
+            AutocratError::ProposalAlreadyFinalized.into()
+        );
+    }
+
+    #[test]
+    fn passed_proposals_wait_for_the_execution_delay() {
+        let mut proposal = proposal();
+        proposal.state = ProposalState::Passed;
+        proposal.slot_finalized = 5_000;
+        proposal.dao_snapshot.execution_delay_slots = 100;
+
+        assert_eq!(proposal.execution_start_slot(), 5_100);
+        assert_eq!(
+            proposal.validate_execute(5_099).unwrap_err(),
+            AutocratError::ExecutionDelayNotElapsed.into()
+        );
+        assert!(proposal.validate_execute(5_100).is_ok());
+    }
+
+    #[test]
+    fn only_the_veto_authority_can_veto_passed_proposals() {
+        let mut dao = dao();
+        let veto_authority = Pubkey::new_unique();
+        let mut proposal = proposal();
+        proposal.state = ProposalState::Passed;
+
+        assert_eq!(
+            proposal.validate_veto(&dao, veto_authority).unwrap_err(),
+            AutocratError::InvalidVetoAuthority.into()
+        );
+
+        dao.veto_authority = Some(veto_authority);
+        assert!(proposal.validate_veto(&dao, veto_authority).is_ok());
+        assert_eq!(
+            proposal
+                .validate_veto(&dao, Pubkey::new_unique())
+                .unwrap_err(),
+            AutocratError::InvalidVetoAuthority.into()
+        );
+
+        // even after some of its instructions have been executed, but not once
+        // all of them have
+        proposal.num_instructions_executed = 1;
+        assert!(proposal.validate_veto(&dao, veto_authority).is_ok());
+
+        proposal.state = ProposalState::Executed;
+        assert_eq!(
+            proposal.validate_veto(&dao, veto_authority).unwrap_err(),
+            AutocratError::ProposalNotPassed.into()
+        );
+    }
+
+    #[test]
+    fn passed_proposals_expire_after_the_execution_window() {
+        let mut proposal = proposal();
+        proposal.state = ProposalState::Passed;
+        proposal.slot_finalized = 5_000;
+        proposal.dao_snapshot.execution_delay_slots = 100;
+        proposal.dao_snapshot.execution_window_slots = 1_000;
+
+        // the window starts once the delay is over
+        assert_eq!(proposal.execution_end_slot(), 6_100);
+
+        assert!(proposal.validate_execute(6_099).is_ok());
+        assert_eq!(
+            proposal.validate_expire(6_099).unwrap_err(),
+            AutocratError::ExecutionWindowNotElapsed.into()
+        );
+
+        assert_eq!(
+            proposal.validate_execute(6_100).unwrap_err(),
+            AutocratError::ExecutionWindowElapsed.into()
+        );
+        assert!(proposal.validate_expire(6_100).is_ok());
+
+        proposal.state = ProposalState::Executed;
+        assert_eq!(
+            proposal.validate_expire(6_100).unwrap_err(),
+            AutocratError::ProposalNotPassed.into()
+        );
+    }
+
+    #[test]
+    fn execution_slots_saturate() {
+        let mut proposal = proposal();
+        proposal.state = ProposalState::Passed;
+        proposal.slot_finalized = u64::MAX - 1;
+        proposal.dao_snapshot.execution_delay_slots = 100;
+
+        assert_eq!(proposal.execution_start_slot(), u64::MAX);
+        assert_eq!(proposal.execution_end_slot(), u64::MAX);
+        assert!(proposal.validate_expire(u64::MAX - 1).is_err());
+    }
+
+    #[test]
+    fn only_the_treasurys_lp_tokens_are_settled() {
+        let mut proposal = proposal();
+        proposal.treasury_lp_tokens[PASS_INDEX] = 100;
+        proposal.treasury_lp_tokens[FAIL_INDEX] = 80;
+
+        // the rest belongs to the proposer
+        assert_eq!(proposal.take_treasury_lp_tokens(PASS_INDEX, 150), 100);
+        assert_eq!(proposal.treasury_lp_tokens[PASS_INDEX], 0);
+        assert_eq!(proposal.treasury_lp_tokens[FAIL_INDEX], 80);
+
+        // each market is only settled once
+        assert_eq!(proposal.take_treasury_lp_tokens(PASS_INDEX, 50), 0);
+
+        // the account can't hold less than the treasury's LP in practice, but
+        // the balance is all that there is to remove
+        assert_eq!(proposal.take_treasury_lp_tokens(FAIL_INDEX, 60), 60);
+        assert_eq!(proposal.treasury_lp_tokens, [0, 0]);
+    }
+
+    #[test]
+    fn only_trading_over_the_proposal_counts_towards_its_bond() {
+        let mut proposal = proposal();
+        let market = || Amm {
+            base_amount: 1_000_000,
+            quote_amount: 1_000_000,
+            ..Default::default()
+        };
+        let mut pass_amm = market();
+        let mut fail_amm = market();
+
+        // trading from before the proposal doesn't count
+        pass_amm.swap(5_000, SwapType::Buy).unwrap();
+        proposal.market_snapshots = [
+            MarketSnapshot::new(&fail_amm, proposal.slot_enqueued),
+            MarketSnapshot::new(&pass_amm, proposal.slot_enqueued),
+        ];
+
+        // and adding liquidity isn't trading
+        pass_amm.base_amount += 1_000_000;
+        pass_amm.quote_amount += 1_000_000;
+        fail_amm.quote_amount += 1_000_000;
+
+        let base_bought = pass_amm.swap(10_000, SwapType::Buy).unwrap();
+        pass_amm.swap(base_bought, SwapType::Sell).unwrap();
+
+        proposal.record_quote_volumes(&pass_amm, &fail_amm);
+
+        assert!(proposal.quote_volumes[PASS_INDEX] > 19_000);
+        assert!(proposal.quote_volumes[PASS_INDEX] <= 20_000);
+        assert_eq!(proposal.quote_volumes[FAIL_INDEX], 0);
+    }
+}
@@ -0,0 +1,65 @@
+use super::*;
+
+/// Lets a DAO give some proposals more or less scrutiny than others. For
+/// example, a DAO could require proposals that mint its token to run longer
+/// and clear a higher threshold than proposals that make small grants.
+///
+/// An instruction is in a category if it calls `program_id` and its data
+/// starts with the first `data_prefix_len` bytes of `data_prefix`. This can be
+/// used to match an Anchor discriminator or an SPL token instruction tag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct ProposalCategory {
+    pub program_id: Pubkey,
+    pub data_prefix: [u8; 8],
+    pub data_prefix_len: u8,
+    pub requirements: ProposalRequirements,
+}
+
+/// What a proposal needs to satisfy to be created and to pass.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct ProposalRequirements {
+    pub pass_threshold_bps: u16,
+    pub slots_per_proposal: u64,
+    pub min_quote_futarchic_liquidity: u64,
+    pub min_base_futarchic_liquidity: u64,
+}
+
+impl ProposalCategory {
+    pub fn matches(&self, instruction: &ProposalInstruction) -> bool {
+        instruction.program_id == self.program_id
+            && instruction
+                .data
+                .starts_with(&self.data_prefix[..self.data_prefix_len as usize])
+    }
+}
+
+impl ProposalRequirements {
+    pub fn validate(&self) -> Result<()> {
+        require_gte!(
+            MAX_BPS,
+            self.pass_threshold_bps,
+            AutocratError::InvalidThresholdBps
+        );
+        require_gte!(
+            self.slots_per_proposal,
+            MIN_SLOTS_PER_PROPOSAL,
+            AutocratError::InvalidSlotsPerProposal
+        );
+
+        Ok(())
+    }
+
+    /// The requirements that satisfy both `self` and `other`.
+    pub fn strictest(self, other: Self) -> Self {
+        Self {
+            pass_threshold_bps: self.pass_threshold_bps.max(other.pass_threshold_bps),
+            slots_per_proposal: self.slots_per_proposal.max(other.slots_per_proposal),
+            min_quote_futarchic_liquidity: self
+                .min_quote_futarchic_liquidity
+                .max(other.min_quote_futarchic_liquidity),
+            min_base_futarchic_liquidity: self
+                .min_base_futarchic_liquidity
+                .max(other.min_base_futarchic_liquidity),
+        }
+    }
+}
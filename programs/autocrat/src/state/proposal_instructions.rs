@@ -0,0 +1,286 @@
+use super::*;
+
+use anchor_lang::Discriminator;
+
+/// What a proposal's instructions do, which decides the threshold that it
+/// needs under `DecisionRule::ByProposalType`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum ProposalType {
+    #[default]
+    Standard,
+    /// Only calls `update_dao`, without touching any security settings.
+    ParameterChange,
+    /// Moves tokens or SOL out of the treasury.
+    TreasurySpend,
+    /// Changes a setting that guards the treasury or decides which proposals
+    /// pass: the DAO's `treasury_liquidity`, `veto_authority` or
+    /// `decision_rule`, or its spending limits.
+    SecurityChange,
+}
+
+/// The instructions of a proposal, stored separately so that a proposer can
+/// build up a payload that's too big for one transaction. The proposer allocates
+/// this account with whatever space they need, appends instructions to it with
+/// `add_proposal_instruction`, and then passes it into `initialize_proposal`,
+/// which freezes it.
+#[account]
+pub struct ProposalInstructions {
+    pub authority: Pubkey,
+    pub frozen: bool,
+    pub instructions: Vec<ProposalInstruction>,
+}
+
+impl ProposalInstructions {
+    /// Appends an instruction, unless a proposal has already frozen these.
+    pub fn push(&mut self, instruction: ProposalInstruction) -> Result<()> {
+        require!(!self.frozen, AutocratError::ProposalInstructionsFrozen);
+
+        self.instructions.push(instruction);
+
+        Ok(())
+    }
+
+    /// What these instructions do, for a DAO whose treasury is `treasury`.
+    /// Instructions that do several of these things get the first type that
+    /// applies out of `SecurityChange`, `ParameterChange` (only if that's all
+    /// they do), `TreasurySpend` and `Standard`. Empty instructions don't
+    /// change anything, so they're `Standard`.
+    pub fn proposal_type(&self, treasury: Pubkey) -> ProposalType {
+        let is_update_dao = |ix: &ProposalInstruction| {
+            ix.program_id == crate::ID
+                && ix
+                    .data
+                    .starts_with(&crate::instruction::UpdateDao::DISCRIMINATOR)
+        };
+
+        if self.instructions.iter().any(is_security_change) {
+            ProposalType::SecurityChange
+        } else if !self.instructions.is_empty() && self.instructions.iter().all(is_update_dao) {
+            ProposalType::ParameterChange
+        } else if self
+            .instructions
+            .iter()
+            .any(|ix| spends_from_treasury(ix, treasury))
+        {
+            ProposalType::TreasurySpend
+        } else {
+            ProposalType::Standard
+        }
+    }
+}
+
+fn is_security_change(ix: &ProposalInstruction) -> bool {
+    if ix.program_id != crate::ID {
+        return false;
+    }
+
+    let (discriminator, mut params) = ix.data.split_at(ix.data.len().min(8));
+
+    if discriminator == crate::instruction::UpdateDao::DISCRIMINATOR {
+        // params that don't decode can't be executed anyway, but fail closed
+        UpdateDaoParams::deserialize(&mut params)
+            .map_or(true, |params| params.changes_security_settings())
+    } else {
+        discriminator == crate::instruction::SetSpendingLimit::DISCRIMINATOR
+            || discriminator == crate::instruction::RemoveSpendingLimit::DISCRIMINATOR
+    }
+}
+
+/// Only the treasury can sign for its own token accounts and lamports, so any
+/// token or system program instruction that's passed the treasury counts.
+fn spends_from_treasury(ix: &ProposalInstruction, treasury: Pubkey) -> bool {
+    [
+        anchor_spl::token::ID,
+        anchor_spl::token_2022::ID,
+        anchor_lang::system_program::ID,
+    ]
+    .contains(&ix.program_id)
+        && ix.accounts.iter().any(|account| account.pubkey == treasury)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use anchor_lang::InstructionData;
+
+    fn proposal_instructions(instructions: Vec<ProposalInstruction>) -> ProposalInstructions {
+        ProposalInstructions {
+            authority: Pubkey::new_unique(),
+            frozen: false,
+            instructions,
+        }
+    }
+
+    fn instruction(program_id: Pubkey, data: &[u8]) -> ProposalInstruction {
+        ProposalInstruction {
+            program_id,
+            accounts: vec![],
+            data: data.to_vec(),
+        }
+    }
+
+    #[test]
+    fn instructions_are_appended_in_order_until_frozen() {
+        let first = instruction(Pubkey::new_unique(), &[0]);
+        let second = instruction(Pubkey::new_unique(), &[1]);
+
+        let mut proposal_instructions = proposal_instructions(vec![]);
+        assert!(proposal_instructions.push(first.clone()).is_ok());
+        assert!(proposal_instructions.push(second.clone()).is_ok());
+        assert_eq!(
+            proposal_instructions.instructions,
+            vec![first, second.clone()]
+        );
+
+        // as `initialize_proposal` does
+        proposal_instructions.frozen = true;
+        assert_eq!(
+            proposal_instructions.push(second).unwrap_err(),
+            AutocratError::ProposalInstructionsFrozen.into()
+        );
+        assert_eq!(proposal_instructions.instructions.len(), 2);
+    }
+
+    fn update_dao(params: UpdateDaoParams) -> ProposalInstruction {
+        instruction(
+            crate::ID,
+            &crate::instruction::UpdateDao { dao_params: params }.data(),
+        )
+    }
+
+    fn params() -> UpdateDaoParams {
+        UpdateDaoParams {
+            pass_threshold_bps: None,
+            slots_per_proposal: None,
+            twap_initial_observation: None,
+            twap_max_observation_change_per_update: None,
+            min_quote_futarchic_liquidity: None,
+            min_base_futarchic_liquidity: None,
+            min_average_quote_liquidity: None,
+            execution_delay_slots: None,
+            veto_authority: None,
+            execution_window_slots: None,
+            decision_rule: None,
+            proposal_bond: None,
+            max_active_proposals: None,
+            max_active_proposals_per_proposer: None,
+            spot_amm: None,
+            treasury_liquidity: None,
+        }
+    }
+
+    fn transfer(program_id: Pubkey, from: Pubkey) -> ProposalInstruction {
+        ProposalInstruction {
+            accounts: vec![ProposalAccount {
+                pubkey: from,
+                is_signer: true,
+                is_writable: true,
+            }],
+            ..instruction(program_id, &[3])
+        }
+    }
+
+    #[test]
+    fn only_update_dao_is_a_parameter_change() {
+        let treasury = Pubkey::new_unique();
+        let update_dao = update_dao(UpdateDaoParams {
+            pass_threshold_bps: Some(500),
+            ..params()
+        });
+
+        assert_eq!(
+            proposal_instructions(vec![update_dao.clone(), update_dao.clone()])
+                .proposal_type(treasury),
+            ProposalType::ParameterChange
+        );
+
+        let other_program = instruction(
+            Pubkey::new_unique(),
+            &crate::instruction::UpdateDao::DISCRIMINATOR,
+        );
+        assert_eq!(
+            proposal_instructions(vec![update_dao, other_program]).proposal_type(treasury),
+            ProposalType::Standard
+        );
+
+        assert_eq!(
+            proposal_instructions(vec![]).proposal_type(treasury),
+            ProposalType::Standard
+        );
+    }
+
+    #[test]
+    fn security_settings_are_a_security_change() {
+        let treasury = Pubkey::new_unique();
+
+        for params in [
+            UpdateDaoParams {
+                veto_authority: Some(None),
+                ..params()
+            },
+            UpdateDaoParams {
+                decision_rule: Some(DecisionRule::Relative),
+                ..params()
+            },
+            UpdateDaoParams {
+                treasury_liquidity: Some(None),
+                ..params()
+            },
+        ] {
+            assert_eq!(
+                proposal_instructions(vec![update_dao(params)]).proposal_type(treasury),
+                ProposalType::SecurityChange
+            );
+        }
+
+        let remove_spending_limit = instruction(
+            crate::ID,
+            &crate::instruction::RemoveSpendingLimit {
+                mint: Pubkey::new_unique(),
+            }
+            .data(),
+        );
+        let spend = transfer(anchor_spl::token::ID, treasury);
+        assert_eq!(
+            proposal_instructions(vec![spend, remove_spending_limit]).proposal_type(treasury),
+            ProposalType::SecurityChange
+        );
+
+        // `update_dao` that can't be decoded is assumed to change anything
+        let truncated = instruction(crate::ID, &crate::instruction::UpdateDao::DISCRIMINATOR);
+        assert_eq!(
+            proposal_instructions(vec![truncated]).proposal_type(treasury),
+            ProposalType::SecurityChange
+        );
+    }
+
+    #[test]
+    fn transfers_from_the_treasury_are_a_treasury_spend() {
+        let treasury = Pubkey::new_unique();
+        let memo = instruction(Pubkey::new_unique(), &[]);
+
+        for program_id in [
+            anchor_spl::token::ID,
+            anchor_spl::token_2022::ID,
+            anchor_lang::system_program::ID,
+        ] {
+            assert_eq!(
+                proposal_instructions(vec![memo.clone(), transfer(program_id, treasury)])
+                    .proposal_type(treasury),
+                ProposalType::TreasurySpend
+            );
+        }
+
+        assert_eq!(
+            proposal_instructions(vec![transfer(anchor_spl::token::ID, Pubkey::new_unique())])
+                .proposal_type(treasury),
+            ProposalType::Standard
+        );
+        assert_eq!(
+            proposal_instructions(vec![transfer(Pubkey::new_unique(), treasury)])
+                .proposal_type(treasury),
+            ProposalType::Standard
+        );
+    }
+}
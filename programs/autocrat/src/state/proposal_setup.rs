@@ -0,0 +1,38 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum ProposalSetupStage {
+    CreatePassAmm,
+    CreateFailAmm,
+    Complete,
+}
+
+/// Tracks a proposer's progress through creating the accounts that a proposal
+/// needs, since they don't all fit in one transaction. Only the proposer can
+/// advance it, and every account it creates is a canonical PDA derived from the
+/// proposal's address, so nobody can swap in their own question, vaults or
+/// markets halfway through.
+///
+/// 1. `initialize_proposal_setup` creates this account, the question and both
+///    conditional vaults.
+/// 2. `setup_proposal_amm` creates the pass market and adds the proposer's
+///    liquidity to it.
+/// 3. `setup_proposal_amm` does the same for the fail market.
+///
+/// After that, the proposer can call `initialize_proposal` with the same nonce,
+/// which checks the proposal's accounts against this one and closes it.
+#[account]
+pub struct ProposalSetup {
+    pub proposer: Pubkey,
+    pub dao: Pubkey,
+    pub nonce: u64,
+    /// The address that the proposal will have once it's initialized.
+    pub proposal: Pubkey,
+    pub stage: ProposalSetupStage,
+    pub question: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub pass_amm: Pubkey,
+    pub fail_amm: Pubkey,
+    pub pda_bump: u8,
+}
@@ -0,0 +1,57 @@
+use super::*;
+
+/// Tracks how many proposals a proposer has open in a DAO, so that the DAO can
+/// cap it with `max_active_proposals_per_proposer`.
+#[account]
+pub struct ProposerRecord {
+    pub dao: Pubkey,
+    pub proposer: Pubkey,
+    pub active_proposal_count: u32,
+    pub pda_bump: u8,
+}
+
+impl ProposerRecord {
+    /// `init_if_needed` leaves new records zeroed, so this fills them in.
+    pub fn initialize_if_new(&mut self, dao: Pubkey, proposer: Pubkey, pda_bump: u8) {
+        if self.proposer == Pubkey::default() {
+            *self = ProposerRecord {
+                dao,
+                proposer,
+                active_proposal_count: 0,
+                pda_bump,
+            };
+        }
+    }
+
+    /// Counts a new proposal against both the DAO's and the proposer's caps.
+    pub fn open_proposal(&mut self, dao: &mut Dao) -> Result<()> {
+        if let Some(max_active_proposals) = dao.max_active_proposals {
+            require_gt!(
+                max_active_proposals,
+                dao.active_proposal_count,
+                AutocratError::TooManyActiveProposals
+            );
+        }
+
+        if let Some(max_active_proposals_per_proposer) = dao.max_active_proposals_per_proposer {
+            require_gt!(
+                max_active_proposals_per_proposer,
+                self.active_proposal_count,
+                AutocratError::TooManyActiveProposals
+            );
+        }
+
+        dao.active_proposal_count += 1;
+        self.active_proposal_count += 1;
+
+        Ok(())
+    }
+
+    /// Should be called whenever a proposal stops being pending.
+    pub fn close_proposal(&mut self, dao: &mut Dao) {
+        // saturate so that proposals created before these records existed
+        // can still be finalized, which creates their proposer's record
+        dao.active_proposal_count = dao.active_proposal_count.saturating_sub(1);
+        self.active_proposal_count = self.active_proposal_count.saturating_sub(1);
+    }
+}
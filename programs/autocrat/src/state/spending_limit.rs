@@ -0,0 +1,403 @@
+use super::*;
+
+/// Caps how much of one mint the treasury can send out through proposals.
+/// Outflows are measured as the decrease in the balances of the treasury's
+/// token accounts for this mint across each executed instruction.
+///
+/// Proposals snapshot the DAO's limits when they're created and are checked
+/// against those, so a proposal can't loosen its own limits by changing the
+/// DAO's. The window is shared by every proposal, so it's always tracked on the
+/// DAO, and the stricter of the snapshot's and the DAO's `max_per_window`
+/// applies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct SpendingLimit {
+    pub mint: Pubkey,
+    /// The most that any one proposal can send out.
+    pub max_per_proposal: Option<u64>,
+    /// The most that all proposals combined can send out in any `window_slots`
+    /// slots.
+    pub max_per_window: Option<u64>,
+    pub window_slots: u64,
+    /// The outflows in the window, split into `SPENDING_WINDOW_BUCKETS`
+    /// buckets so that it can roll. Bucket number `n` is stored at index
+    /// `n % SPENDING_WINDOW_BUCKETS`, and `latest_bucket` is the newest bucket
+    /// number. Updated by autocrat as proposals are executed.
+    pub window_outflows: [u64; SPENDING_WINDOW_BUCKETS],
+    pub latest_bucket: u64,
+}
+
+/// How much of a mint a proposal has sent out of the treasury so far.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct TreasuryOutflow {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+impl SpendingLimit {
+    pub fn new(
+        mint: Pubkey,
+        max_per_proposal: Option<u64>,
+        max_per_window: Option<u64>,
+        window_slots: u64,
+    ) -> Self {
+        Self {
+            mint,
+            max_per_proposal,
+            max_per_window,
+            window_slots,
+            window_outflows: [0; SPENDING_WINDOW_BUCKETS],
+            latest_bucket: 0,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require_neq!(self.window_slots, 0, AutocratError::InvalidSpendingLimit);
+
+        Ok(())
+    }
+
+    /// Removed limits are kept without any caps until their window is empty,
+    /// so that removing and re-adding a limit doesn't reset its window. These
+    /// aren't snapshotted by new proposals.
+    pub fn is_capped(&self) -> bool {
+        self.max_per_proposal.is_some() || self.max_per_window.is_some()
+    }
+
+    /// The current bucket and every bucket before it that's still stored add
+    /// up to at least `window_slots`, so any `window_slots` slots up to now
+    /// fall within the stored buckets.
+    fn bucket_slots(&self) -> u64 {
+        self.window_slots
+            .div_ceil(SPENDING_WINDOW_BUCKETS as u64 - 1)
+            .max(1)
+    }
+
+    /// Clears the buckets that have rolled out of the window by `slot`.
+    fn roll(&mut self, slot: u64) {
+        let bucket = slot / self.bucket_slots();
+
+        if bucket <= self.latest_bucket {
+            return;
+        }
+
+        let buckets_passed = (bucket - self.latest_bucket).min(SPENDING_WINDOW_BUCKETS as u64);
+        for i in 1..=buckets_passed {
+            self.window_outflows
+                [((self.latest_bucket + i) % SPENDING_WINDOW_BUCKETS as u64) as usize] = 0;
+        }
+
+        self.latest_bucket = bucket;
+    }
+
+    /// How much has been sent out in the window as of `slot`. Since the window
+    /// is rounded up to whole buckets, this can include outflows from up to
+    /// one bucket before the window, but never misses any within it.
+    pub fn window_outflow(&mut self, slot: u64) -> u64 {
+        self.roll(slot);
+
+        self.window_outflows
+            .iter()
+            .fold(0, |total: u64, outflow| total.saturating_add(*outflow))
+    }
+
+    /// Counts an outflow towards the window and checks it against
+    /// `max_per_window`, which the caller picks.
+    pub fn record_outflow(
+        &mut self,
+        amount: u64,
+        slot: u64,
+        max_per_window: Option<u64>,
+    ) -> Result<()> {
+        if let Some(max_per_window) = max_per_window {
+            require_gte!(
+                max_per_window,
+                self.window_outflow(slot).saturating_add(amount),
+                AutocratError::SpendingLimitExceeded
+            );
+        }
+
+        self.roll(slot);
+
+        let index = (self.latest_bucket % SPENDING_WINDOW_BUCKETS as u64) as usize;
+        self.window_outflows[index] = self.window_outflows[index].saturating_add(amount);
+
+        Ok(())
+    }
+
+    /// Changes the length of the window. Everything in the old window is moved
+    /// into the current bucket, so that it counts for at least as long as it
+    /// would have.
+    pub fn set_window_slots(&mut self, window_slots: u64, slot: u64) {
+        if window_slots == self.window_slots {
+            return;
+        }
+
+        let window_outflow = self.window_outflow(slot);
+
+        self.window_slots = window_slots;
+        self.window_outflows = [0; SPENDING_WINDOW_BUCKETS];
+        self.latest_bucket = slot / self.bucket_slots();
+        self.window_outflows[(self.latest_bucket % SPENDING_WINDOW_BUCKETS as u64) as usize] =
+            window_outflow;
+    }
+}
+
+impl Dao {
+    /// The limits that a new proposal should be checked against.
+    pub fn spending_limits_snapshot(&self) -> Vec<SpendingLimit> {
+        self.spending_limits
+            .iter()
+            .filter(|spending_limit| spending_limit.is_capped())
+            .copied()
+            .collect()
+    }
+
+    /// Forgets removed limits whose windows are empty, to make room for new
+    /// ones.
+    pub fn prune_spending_limits(&mut self, slot: u64) {
+        self.spending_limits.retain_mut(|spending_limit| {
+            spending_limit.is_capped() || spending_limit.window_outflow(slot) > 0
+        });
+    }
+
+    /// Counts an outflow from the treasury against `proposal_limit`, which is
+    /// the proposal's snapshot of the mint's limit, against the DAO's window
+    /// for the mint, and against the proposal's `treasury_outflows`.
+    pub fn record_treasury_outflow(
+        &mut self,
+        proposal_limit: &SpendingLimit,
+        treasury_outflows: &mut Vec<TreasuryOutflow>,
+        amount: u64,
+        slot: u64,
+    ) -> Result<()> {
+        let mint = proposal_limit.mint;
+
+        let dao_limit_index = match self
+            .spending_limits
+            .iter()
+            .position(|spending_limit| spending_limit.mint == mint)
+        {
+            Some(index) => index,
+            // the limit was removed after this proposal was created, and its
+            // window has emptied since, so start tracking it again
+            None => {
+                self.prune_spending_limits(slot);
+                require_gt!(
+                    MAX_SPENDING_LIMITS,
+                    self.spending_limits.len(),
+                    AutocratError::TooManySpendingLimits
+                );
+
+                self.spending_limits.push(SpendingLimit::new(
+                    mint,
+                    None,
+                    None,
+                    proposal_limit.window_slots,
+                ));
+                self.spending_limits.len() - 1
+            }
+        };
+
+        let dao_limit = &mut self.spending_limits[dao_limit_index];
+        let max_per_window = [proposal_limit.max_per_window, dao_limit.max_per_window]
+            .into_iter()
+            .flatten()
+            .min();
+        dao_limit.record_outflow(amount, slot, max_per_window)?;
+
+        // proposals only snapshot `MAX_SPENDING_LIMITS` limits, so this can't
+        // outgrow the space that they reserve
+        let total_outflow = match treasury_outflows
+            .iter_mut()
+            .find(|outflow| outflow.mint == mint)
+        {
+            Some(outflow) => {
+                outflow.amount = outflow.amount.saturating_add(amount);
+                outflow.amount
+            }
+            None => {
+                treasury_outflows.push(TreasuryOutflow { mint, amount });
+                amount
+            }
+        };
+
+        if let Some(max_per_proposal) = proposal_limit.max_per_proposal {
+            require_gte!(
+                max_per_proposal,
+                total_outflow,
+                AutocratError::SpendingLimitExceeded
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::state::dao::tests::dao;
+
+    fn spending_limit(mint: Pubkey) -> SpendingLimit {
+        SpendingLimit::new(mint, Some(100), Some(150), 1_000)
+    }
+
+    #[test]
+    fn spending_limit_window_requires_slots() {
+        let mut dao = dao();
+
+        dao.spending_limits = vec![SpendingLimit {
+            window_slots: 0,
+            ..spending_limit(dao.usdc_mint)
+        }];
+        assert_eq!(
+            dao.validate_params().unwrap_err(),
+            AutocratError::InvalidSpendingLimit.into()
+        );
+    }
+
+    #[test]
+    fn treasury_outflows_are_capped_per_proposal() {
+        let mut dao = dao();
+        let limit = spending_limit(dao.usdc_mint);
+        dao.spending_limits = vec![limit];
+
+        let mut treasury_outflows = vec![];
+        assert!(dao
+            .record_treasury_outflow(&limit, &mut treasury_outflows, 60, 0)
+            .is_ok());
+        assert_eq!(
+            dao.record_treasury_outflow(&limit, &mut treasury_outflows, 60, 0)
+                .unwrap_err(),
+            AutocratError::SpendingLimitExceeded.into()
+        );
+        assert_eq!(
+            treasury_outflows,
+            vec![TreasuryOutflow {
+                mint: limit.mint,
+                amount: 120
+            }]
+        );
+    }
+
+    #[test]
+    fn treasury_outflows_are_capped_over_a_rolling_window() {
+        let mut dao = dao();
+        let limit = SpendingLimit {
+            max_per_proposal: None,
+            ..spending_limit(dao.usdc_mint)
+        };
+        dao.spending_limits = vec![limit];
+
+        assert!(dao
+            .record_treasury_outflow(&limit, &mut vec![], 100, 990)
+            .is_ok());
+
+        // a tumbling window would have reset at slot 1_000 and allowed another
+        // 150 straight away
+        assert_eq!(
+            dao.record_treasury_outflow(&limit, &mut vec![], 100, 1_010)
+                .unwrap_err(),
+            AutocratError::SpendingLimitExceeded.into()
+        );
+        assert!(dao
+            .record_treasury_outflow(&limit, &mut vec![], 50, 1_010)
+            .is_ok());
+
+        // everything that was sent out in any `window_slots` is counted
+        for slot in [1_500, 1_989] {
+            assert_eq!(dao.spending_limits[0].window_outflow(slot), 150);
+        }
+
+        // once it's at least a window and a bucket old, it isn't
+        assert_eq!(dao.spending_limits[0].window_outflow(2_000 + 143), 50);
+        assert_eq!(dao.spending_limits[0].window_outflow(100_000), 0);
+        assert!(dao
+            .record_treasury_outflow(&limit, &mut vec![], 150, 100_000)
+            .is_ok());
+    }
+
+    #[test]
+    fn proposals_are_checked_against_their_snapshot() {
+        let mut dao = dao();
+        let limit = spending_limit(dao.usdc_mint);
+        dao.spending_limits = vec![limit];
+
+        let snapshot = dao.spending_limits_snapshot();
+
+        // a proposal that loosens the limit, say by removing it, doesn't get
+        // to spend more itself
+        dao.spending_limits[0].max_per_proposal = None;
+        dao.spending_limits[0].max_per_window = None;
+
+        let mut treasury_outflows = vec![];
+        assert_eq!(
+            dao.record_treasury_outflow(&snapshot[0], &mut treasury_outflows, 101, 0)
+                .unwrap_err(),
+            AutocratError::SpendingLimitExceeded.into()
+        );
+
+        // but tightening the DAO's window applies to every proposal
+        dao.spending_limits[0].max_per_window = Some(10);
+        assert_eq!(
+            dao.record_treasury_outflow(&snapshot[0], &mut vec![], 11, 0)
+                .unwrap_err(),
+            AutocratError::SpendingLimitExceeded.into()
+        );
+    }
+
+    #[test]
+    fn removed_limits_keep_their_window() {
+        let mut dao = dao();
+        let limit = SpendingLimit {
+            max_per_proposal: None,
+            ..spending_limit(dao.usdc_mint)
+        };
+        dao.spending_limits = vec![limit];
+
+        assert!(dao
+            .record_treasury_outflow(&limit, &mut vec![], 150, 0)
+            .is_ok());
+
+        dao.spending_limits[0].max_per_window = None;
+        dao.prune_spending_limits(1);
+        assert!(dao.spending_limits_snapshot().is_empty());
+        assert_eq!(dao.spending_limits[0].window_outflow(1), 150);
+
+        // so proposals from before the removal are still capped by it
+        assert_eq!(
+            dao.record_treasury_outflow(&limit, &mut vec![], 1, 1)
+                .unwrap_err(),
+            AutocratError::SpendingLimitExceeded.into()
+        );
+
+        dao.prune_spending_limits(100_000);
+        assert!(dao.spending_limits.is_empty());
+
+        // if the window was empty, the proposal starts tracking it again
+        assert!(dao
+            .record_treasury_outflow(&limit, &mut vec![], 150, 100_000)
+            .is_ok());
+        assert_eq!(dao.spending_limits[0].window_outflow(100_000), 150);
+        assert!(!dao.spending_limits[0].is_capped());
+    }
+
+    #[test]
+    fn changing_the_window_keeps_its_outflows() {
+        let mut limit = spending_limit(Pubkey::new_unique());
+
+        assert!(limit.record_outflow(150, 500, Some(150)).is_ok());
+
+        limit.set_window_slots(10, 600);
+        assert_eq!(limit.window_outflow(615), 150);
+        assert_eq!(limit.window_outflow(616), 0);
+
+        let mut limit = spending_limit(Pubkey::new_unique());
+        assert!(limit.record_outflow(150, 500, Some(150)).is_ok());
+
+        limit.set_window_slots(100_000, 600);
+        assert_eq!(limit.window_outflow(100_000), 150);
+        assert!(limit.record_outflow(1, 100_000, Some(150)).is_err());
+    }
+}
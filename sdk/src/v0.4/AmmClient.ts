@@ -10,7 +10,7 @@ import { Amm as AmmIDLType, IDL as AmmIDL } from "./types/amm.js";
 
 import BN from "bn.js";
 import { AMM_PROGRAM_ID } from "./constants.js";
-import { Amm, LowercaseKeys } from "./types/index.js";
+import { Amm, AmmCurve, LowercaseKeys } from "./types/index.js";
 import { getAmmLpMintAddr, getAmmAddr } from "./utils/pda.js";
 // import { MethodsBuilder } from "@coral-xyz/anchor/dist/cjs/program/namespace/methods";
 import {
@@ -74,7 +74,8 @@ export class AmmClient {
     baseMint: PublicKey,
     quoteMint: PublicKey,
     twapInitialObservation: number,
-    twapMaxObservationChangePerUpdate?: number
+    twapMaxObservationChangePerUpdate?: number,
+    curve: AmmCurve = { constantProduct: {} }
   ): Promise<PublicKey> {
     if (!twapMaxObservationChangePerUpdate) {
       twapMaxObservationChangePerUpdate = twapInitialObservation * 0.02;
@@ -102,18 +103,21 @@ export class AmmClient {
       baseMint,
       quoteMint,
       twapFirstObservationScaled,
-      twapMaxObservationChangePerUpdateScaled
+      twapMaxObservationChangePerUpdateScaled,
+      curve
     ).rpc();
 
     return amm;
   }
 
-  // both twap values need to be scaled beforehand
+  // both twap values need to be scaled beforehand. stable swap curves only
+  // make sense for mints with the same decimals
   initializeAmmIx(
     baseMint: PublicKey,
     quoteMint: PublicKey,
     twapInitialObservation: BN,
-    twapMaxObservationChangePerUpdate: BN
+    twapMaxObservationChangePerUpdate: BN,
+    curve: AmmCurve = { constantProduct: {} }
   ) {
     let [amm] = getAmmAddr(this.getProgramId(), baseMint, quoteMint);
     let [lpMint] = getAmmLpMintAddr(this.getProgramId(), amm);
@@ -125,6 +129,7 @@ export class AmmClient {
       .createAmm({
         twapInitialObservation,
         twapMaxObservationChangePerUpdate,
+        curve,
       })
       .accounts({
         user: this.provider.publicKey,
@@ -331,6 +336,7 @@ export class AmmClient {
     user: PublicKey = this.provider.publicKey
   ) {
     const receivingToken = swapType.buy ? baseMint : quoteMint;
+    const [lpMint] = getAmmLpMintAddr(this.program.programId, amm);
 
     return this.program.methods
       .swap({
@@ -341,6 +347,7 @@ export class AmmClient {
       .accounts({
         user,
         amm,
+        lpMint,
         userBaseAccount: getAssociatedTokenAddressSync(baseMint, user, true),
         userQuoteAccount: getAssociatedTokenAddressSync(quoteMint, user, true),
         vaultAtaBase: getAssociatedTokenAddressSync(baseMint, amm, true),
@@ -11,7 +11,11 @@ import {
   TransactionInstruction,
 } from "@solana/web3.js";
 import { PriceMath } from "./utils/priceMath.js";
-import { ProposalInstruction, InitializeDaoParams } from "./types/index.js";
+import {
+  ProposalInstruction,
+  InitializeDaoParams,
+  SetupProposalAmmParams,
+} from "./types/index.js";
 
 import { Autocrat, IDL as AutocratIDL } from "./types/autocrat.js";
 import {
@@ -36,7 +40,10 @@ import {
   getConditionalTokenMintAddr,
   getDaoTreasuryAddr,
   getEventAuthorityAddr,
+  getBondEscrowAddr,
   getProposalAddr,
+  getProposalSetupAddr,
+  getProposerRecordAddr,
   getQuestionAddr,
   getVaultAddr,
   getVaultFinalizeMintAddr,
@@ -49,7 +56,6 @@ import {
   getAssociatedTokenAddressSync,
   unpackMint,
 } from "@solana/spl-token";
-import { sha256 } from "@noble/hashes/sha256";
 import { Dao, Proposal } from "./types/index.js";
 
 export type CreateClientParams = {
@@ -158,9 +164,10 @@ export class AutocratClient {
     failLp: PublicKey;
   } {
     let vaultProgramId = this.vaultClient.vaultProgram.programId;
+    // a proposal's question always uses the proposal's address as its id
     const [question] = getQuestionAddr(
       vaultProgramId,
-      sha256(`Will ${proposal} pass?/FAIL/PASS`),
+      proposal.toBytes(),
       proposal,
       2
     );
@@ -302,10 +309,13 @@ export class AutocratClient {
       .signers([daoKeypair]);
   }
 
+  // creates the proposal's instructions, sets up its question, vaults and
+  // markets through a proposal setup, and then initializes it. each step is
+  // its own transaction, since they don't all fit in one
   async initializeProposal(
     dao: PublicKey,
     descriptionUrl: string,
-    instruction: ProposalInstruction,
+    instructions: ProposalInstruction[],
     baseTokensToLP: BN,
     quoteTokensToLP: BN
   ): Promise<PublicKey> {
@@ -319,51 +329,24 @@ export class AutocratClient {
       nonce
     );
 
-    await this.vaultClient.initializeQuestion(
-      sha256(`Will ${proposal} pass?/FAIL/PASS`),
-      proposal,
-      2
+    const proposalInstructions = await this.initializeProposalInstructions(
+      instructions
     );
 
-    const {
-      baseVault,
-      quoteVault,
-      passAmm,
-      failAmm,
-      passBaseMint,
-      passQuoteMint,
-      failBaseMint,
-      failQuoteMint,
-      question,
-    } = this.getProposalPdas(
+    await this.initializeProposalSetupIx(
+      dao,
+      storedDao.tokenMint,
+      storedDao.usdcMint,
+      nonce
+    ).rpc();
+
+    const { baseVault, quoteVault, question } = this.getProposalPdas(
       proposal,
       storedDao.tokenMint,
       storedDao.usdcMint,
       dao
     );
 
-    // it's important that these happen in a single atomic transaction
-    await this.vaultClient
-      .initializeVaultIx(question, storedDao.tokenMint, 2)
-      .postInstructions(
-        await InstructionUtils.getInstructions(
-          this.vaultClient.initializeVaultIx(question, storedDao.usdcMint, 2),
-          this.ammClient.initializeAmmIx(
-            passBaseMint,
-            passQuoteMint,
-            storedDao.twapInitialObservation,
-            storedDao.twapMaxObservationChangePerUpdate
-          ),
-          this.ammClient.initializeAmmIx(
-            failBaseMint,
-            failQuoteMint,
-            storedDao.twapInitialObservation,
-            storedDao.twapMaxObservationChangePerUpdate
-          )
-        )
-      )
-      .rpc();
-
     await this.vaultClient
       .splitTokensIx(
         question,
@@ -385,47 +368,223 @@ export class AutocratClient {
       )
       .rpc();
 
-    await this.ammClient
-      .addLiquidityIx(
-        passAmm,
-        passBaseMint,
-        passQuoteMint,
-        quoteTokensToLP,
-        baseTokensToLP,
-        new BN(0)
-      )
-      .postInstructions(
-        await InstructionUtils.getInstructions(
-          this.ammClient.addLiquidityIx(
-            failAmm,
-            failBaseMint,
-            failQuoteMint,
-            quoteTokensToLP,
-            baseTokensToLP,
-            new BN(0)
-          )
-        )
-      )
-      .rpc();
+    // the setup creates the pass market first
+    for (const outcome of ["pass", "fail"] as const) {
+      await this.setupProposalAmmIx(
+        dao,
+        storedDao.tokenMint,
+        storedDao.usdcMint,
+        nonce,
+        outcome,
+        {
+          quoteAmount: quoteTokensToLP,
+          maxBaseAmount: baseTokensToLP,
+          minLpTokens: new BN(0),
+        }
+      ).rpc();
+    }
 
     // this is how many original tokens are created
     const lpTokens = quoteTokensToLP;
 
     await this.initializeProposalIx(
       descriptionUrl,
-      instruction,
+      proposalInstructions,
       dao,
       storedDao.tokenMint,
       storedDao.usdcMint,
       lpTokens,
       lpTokens,
       nonce,
-      question
+      question,
+      storedDao.spotAmm,
+      storedDao.proposalBond?.mint ?? null
     ).rpc();
 
     return proposal;
   }
 
+  // allocates a proposal instructions account and appends `instructions` to
+  // it, one transaction per instruction so that big payloads still fit
+  async initializeProposalInstructions(
+    instructions: ProposalInstruction[]
+  ): Promise<PublicKey> {
+    const proposalInstructions = Keypair.generate();
+
+    // discriminator, authority, frozen and the vec's length, then each
+    // instruction's program id, accounts and data
+    const space =
+      8 +
+      32 +
+      1 +
+      4 +
+      instructions.reduce(
+        (space, ix) => space + 32 + 4 + ix.accounts.length * 34 + 4 + ix.data.length,
+        0
+      );
+
+    await this.autocrat.methods
+      .initializeProposalInstructions()
+      .accounts({
+        authority: this.provider.publicKey,
+        proposalInstructions: proposalInstructions.publicKey,
+      })
+      .preInstructions([
+        await this.autocrat.account.proposalInstructions.createInstruction(
+          proposalInstructions,
+          space
+        ),
+      ])
+      .signers([proposalInstructions])
+      .rpc();
+
+    for (const instruction of instructions) {
+      await this.addProposalInstructionIx(
+        proposalInstructions.publicKey,
+        instruction
+      ).rpc();
+    }
+
+    return proposalInstructions.publicKey;
+  }
+
+  addProposalInstructionIx(
+    proposalInstructions: PublicKey,
+    instruction: ProposalInstruction
+  ) {
+    return this.autocrat.methods.addProposalInstruction(instruction).accounts({
+      authority: this.provider.publicKey,
+      proposalInstructions,
+    });
+  }
+
+  initializeProposalSetupIx(
+    dao: PublicKey,
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    nonce: BN
+  ) {
+    const [proposal] = getProposalAddr(
+      this.autocrat.programId,
+      this.provider.publicKey,
+      nonce
+    );
+    const [proposalSetup] = getProposalSetupAddr(
+      this.autocrat.programId,
+      this.provider.publicKey,
+      nonce
+    );
+    const {
+      question,
+      baseVault,
+      quoteVault,
+      failBaseMint,
+      passBaseMint,
+      failQuoteMint,
+      passQuoteMint,
+    } = this.getProposalPdas(proposal, baseMint, quoteMint, dao);
+
+    const [vaultEventAuthority] = getEventAuthorityAddr(
+      this.vaultClient.vaultProgram.programId
+    );
+
+    return this.autocrat.methods
+      .initializeProposalSetup(nonce)
+      .accounts({
+        proposalSetup,
+        dao,
+        proposer: this.provider.publicKey,
+        question,
+        baseVault,
+        quoteVault,
+        tokenMint: baseMint,
+        usdcMint: quoteMint,
+        baseVaultUnderlyingTokenAccount: getAssociatedTokenAddressSync(
+          baseMint,
+          baseVault,
+          true
+        ),
+        quoteVaultUnderlyingTokenAccount: getAssociatedTokenAddressSync(
+          quoteMint,
+          quoteVault,
+          true
+        ),
+        vaultProgram: this.vaultClient.vaultProgram.programId,
+        vaultEventAuthority,
+      })
+      .remainingAccounts(
+        [failBaseMint, passBaseMint, failQuoteMint, passQuoteMint].map(
+          (pubkey) => ({ pubkey, isWritable: true, isSigner: false })
+        )
+      )
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitLimit({
+          units: MaxCUs.initializeProposalSetup,
+        }),
+      ]);
+  }
+
+  // the proposer needs to have split enough tokens to cover the liquidity
+  setupProposalAmmIx(
+    dao: PublicKey,
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    nonce: BN,
+    outcome: "pass" | "fail",
+    params: SetupProposalAmmParams
+  ) {
+    const proposer = this.provider.publicKey;
+    const [proposal] = getProposalAddr(this.autocrat.programId, proposer, nonce);
+    const [proposalSetup] = getProposalSetupAddr(
+      this.autocrat.programId,
+      proposer,
+      nonce
+    );
+    const [treasury] = getDaoTreasuryAddr(this.autocrat.programId, dao);
+    const pdas = this.getProposalPdas(proposal, baseMint, quoteMint, dao);
+
+    const [amm, lpMint, ammBaseMint, ammQuoteMint] =
+      outcome == "pass"
+        ? [pdas.passAmm, pdas.passLp, pdas.passBaseMint, pdas.passQuoteMint]
+        : [pdas.failAmm, pdas.failLp, pdas.failBaseMint, pdas.failQuoteMint];
+
+    const [ammEventAuthority] = getEventAuthorityAddr(
+      this.ammClient.getProgramId()
+    );
+
+    return this.autocrat.methods
+      .setupProposalAmm(params)
+      .accounts({
+        proposalSetup,
+        proposer,
+        dao,
+        treasury,
+        baseVault: pdas.baseVault,
+        quoteVault: pdas.quoteVault,
+        amm,
+        lpMint,
+        baseMint: ammBaseMint,
+        quoteMint: ammQuoteMint,
+        vaultAtaBase: getAssociatedTokenAddressSync(ammBaseMint, amm, true),
+        vaultAtaQuote: getAssociatedTokenAddressSync(ammQuoteMint, amm, true),
+        userLpAccount: getAssociatedTokenAddressSync(lpMint, proposer),
+        userBaseAccount: getAssociatedTokenAddressSync(ammBaseMint, proposer),
+        userQuoteAccount: getAssociatedTokenAddressSync(ammQuoteMint, proposer),
+        treasuryLpAccount: getAssociatedTokenAddressSync(
+          lpMint,
+          treasury,
+          true
+        ),
+        ammProgram: this.ammClient.getProgramId(),
+        ammEventAuthority,
+      })
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitLimit({
+          units: MaxCUs.setupProposalAmm,
+        }),
+      ]);
+  }
+
   // async createProposalTxAndPDAs(
   //   dao: PublicKey,
   //   descriptionUrl: string,
@@ -557,52 +716,36 @@ export class AutocratClient {
 
   initializeProposalIx(
     descriptionUrl: string,
-    instruction: ProposalInstruction,
+    proposalInstructions: PublicKey,
     dao: PublicKey,
     baseMint: PublicKey,
     quoteMint: PublicKey,
     passLpTokensToLock: BN,
     failLpTokensToLock: BN,
     nonce: BN,
-    question: PublicKey
+    question: PublicKey,
+    spotAmm: PublicKey | null = null,
+    bondMint: PublicKey | null = null
   ) {
-    let [proposal] = getProposalAddr(
+    const proposer = this.provider.publicKey;
+    let [proposal] = getProposalAddr(this.autocrat.programId, proposer, nonce);
+    const [proposalSetup] = getProposalSetupAddr(
       this.autocrat.programId,
-      this.provider.publicKey,
+      proposer,
       nonce
     );
-    const [daoTreasury] = getDaoTreasuryAddr(this.autocrat.programId, dao);
-    const { baseVault, quoteVault, passAmm, failAmm } = this.getProposalPdas(
-      proposal,
-      baseMint,
-      quoteMint,
-      dao
-    );
-
-    const [passLp] = getAmmLpMintAddr(
-      this.ammClient.program.programId,
-      passAmm
-    );
-    const [failLp] = getAmmLpMintAddr(
-      this.ammClient.program.programId,
-      failAmm
-    );
-
-    const passLpVaultAccount = getAssociatedTokenAddressSync(
-      passLp,
-      daoTreasury,
-      true
-    );
-    const failLpVaultAccount = getAssociatedTokenAddressSync(
-      failLp,
-      daoTreasury,
-      true
+    const [proposerRecord] = getProposerRecordAddr(
+      this.autocrat.programId,
+      dao,
+      proposer
     );
+    const [daoTreasury] = getDaoTreasuryAddr(this.autocrat.programId, dao);
+    const { baseVault, quoteVault, passAmm, failAmm, passLp, failLp } =
+      this.getProposalPdas(proposal, baseMint, quoteMint, dao);
 
     return this.autocrat.methods
       .initializeProposal({
         descriptionUrl,
-        instruction,
         passLpTokensToLock,
         failLpTokensToLock,
         nonce,
@@ -611,38 +754,37 @@ export class AutocratClient {
         question,
         proposal,
         dao,
+        proposerRecord,
+        proposalInstructions,
+        proposalSetup,
         baseVault,
         quoteVault,
         passAmm,
         failAmm,
         passLpMint: passLp,
         failLpMint: failLp,
-        passLpUserAccount: getAssociatedTokenAddressSync(
+        passLpUserAccount: getAssociatedTokenAddressSync(passLp, proposer),
+        failLpUserAccount: getAssociatedTokenAddressSync(failLp, proposer),
+        passLpVaultAccount: getAssociatedTokenAddressSync(
           passLp,
-          this.provider.publicKey
-        ),
-        failLpUserAccount: getAssociatedTokenAddressSync(
-          failLp,
-          this.provider.publicKey
-        ),
-        passLpVaultAccount,
-        failLpVaultAccount,
-        proposer: this.provider.publicKey,
-      })
-      .preInstructions([
-        createAssociatedTokenAccountIdempotentInstruction(
-          this.provider.publicKey,
-          passLpVaultAccount,
           daoTreasury,
-          passLp
+          true
         ),
-        createAssociatedTokenAccountIdempotentInstruction(
-          this.provider.publicKey,
-          failLpVaultAccount,
+        failLpVaultAccount: getAssociatedTokenAddressSync(
+          failLp,
           daoTreasury,
-          failLp
+          true
         ),
-      ]);
+        proposer,
+        bondMint,
+        bondUserAccount: bondMint
+          ? getAssociatedTokenAddressSync(bondMint, proposer)
+          : null,
+        bondEscrow: bondMint
+          ? getBondEscrowAddr(this.autocrat.programId, proposal)[0]
+          : null,
+        spotAmm,
+      });
   }
 
   async finalizeProposal(proposal: PublicKey) {
@@ -651,51 +793,45 @@ export class AutocratClient {
 
     return this.finalizeProposalIx(
       proposal,
-      storedProposal.instruction,
+      storedProposal.instructions,
       storedProposal.dao,
       storedDao.tokenMint,
       storedDao.usdcMint,
-      storedProposal.proposer
+      storedProposal.proposer,
+      storedProposal.spotOracleSnapshot?.amm ?? null
     ).rpc();
   }
 
   finalizeProposalIx(
     proposal: PublicKey,
-    instruction: any,
+    instructions: PublicKey,
     dao: PublicKey,
     daoToken: PublicKey,
     usdc: PublicKey,
-    proposer: PublicKey
+    proposer: PublicKey,
+    spotAmm: PublicKey | null = null
   ) {
     let vaultProgramId = this.vaultClient.vaultProgram.programId;
 
     const [daoTreasury] = getDaoTreasuryAddr(this.autocrat.programId, dao);
-    const { question, passAmm, failAmm } = this.getProposalPdas(
-      proposal,
-      daoToken,
-      usdc,
-      dao
-    );
-
-    const [passLp] = getAmmLpMintAddr(
-      this.ammClient.program.programId,
-      passAmm
-    );
-    const [failLp] = getAmmLpMintAddr(
-      this.ammClient.program.programId,
-      failAmm
+    const [proposerRecord] = getProposerRecordAddr(
+      this.autocrat.programId,
+      dao,
+      proposer
     );
+    const { question, passAmm, failAmm, passLp, failLp } =
+      this.getProposalPdas(proposal, daoToken, usdc, dao);
 
     const [vaultEventAuthority] = getEventAuthorityAddr(vaultProgramId);
 
     return this.autocrat.methods.finalizeProposal().accounts({
       proposal,
+      instructions,
       passAmm,
       failAmm,
       dao,
+      proposerRecord,
       question,
-      // baseVault,
-      // quoteVault,
       passLpUserAccount: getAssociatedTokenAddressSync(passLp, proposer),
       failLpUserAccount: getAssociatedTokenAddressSync(failLp, proposer),
       passLpVaultAccount: getAssociatedTokenAddressSync(
@@ -708,43 +844,59 @@ export class AutocratClient {
         daoTreasury,
         true
       ),
+      spotAmm,
       vaultProgram: this.vaultClient.vaultProgram.programId,
       treasury: daoTreasury,
       vaultEventAuthority,
+      payer: this.provider.publicKey,
     });
   }
 
+  // executes the proposal's next instruction. proposals with several
+  // instructions need one call per instruction
   async executeProposal(proposal: PublicKey) {
     let storedProposal = await this.getProposal(proposal);
+    let storedInstructions =
+      await this.autocrat.account.proposalInstructions.fetch(
+        storedProposal.instructions
+      );
 
     return this.executeProposalIx(
       proposal,
       storedProposal.dao,
-      storedProposal.instruction
+      storedProposal.instructions,
+      storedInstructions.instructions[storedProposal.numInstructionsExecuted]
     ).rpc();
   }
 
-  executeProposalIx(proposal: PublicKey, dao: PublicKey, instruction: any) {
+  executeProposalIx(
+    proposal: PublicKey,
+    dao: PublicKey,
+    instructions: PublicKey,
+    instruction?: ProposalInstruction
+  ) {
     const [daoTreasury] = getDaoTreasuryAddr(this.autocrat.programId, dao);
     return this.autocrat.methods
       .executeProposal()
       .accounts({
         proposal,
+        instructions,
         dao,
-        // daoTreasury,
       })
       .remainingAccounts(
-        instruction.accounts
-          .concat({
-            pubkey: instruction.programId,
-            isWritable: false,
-            isSigner: false,
-          })
-          .map((meta: AccountMeta) =>
-            meta.pubkey.equals(daoTreasury)
-              ? { ...meta, isSigner: false }
-              : meta
-          )
+        instruction
+          ? instruction.accounts
+              .concat({
+                pubkey: instruction.programId,
+                isWritable: false,
+                isSigner: false,
+              })
+              .map((meta: AccountMeta) =>
+                meta.pubkey.equals(daoTreasury)
+                  ? { ...meta, isSigner: false }
+                  : meta
+              )
+          : []
       );
   }
 
@@ -224,6 +224,11 @@ export type Amm = {
           isMut: true;
           isSigner: false;
         },
+        {
+          name: "lpMint";
+          isMut: false;
+          isSigner: false;
+        },
         {
           name: "userBaseAccount";
           isMut: true;
@@ -289,6 +294,28 @@ export type Amm = {
         }
       ];
       args: [];
+    },
+    {
+      name: "migrateAmm";
+      accounts: [
+        {
+          name: "amm";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+          docs: ["Pays for the AMM's account to grow to the current layout."];
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
     }
   ];
   accounts: [
@@ -342,6 +369,53 @@ export type Amm = {
           {
             name: "seqNum";
             type: "u64";
+          },
+          {
+            name: "curve";
+            type: {
+              defined: "AmmCurve";
+            };
+          },
+          {
+            name: "cumulativeBaseFeesPerLp";
+            docs: [
+              "Running sums of the fees that each LP token has earned, in Q64.64 form.",
+              "Like the TWAP aggregator, these wrap on overflow, so compare two",
+              "snapshots with `FeeSnapshot::get_fees_earned_since` rather than by",
+              "subtracting them directly."
+            ];
+            type: "u128";
+          },
+          {
+            name: "cumulativeQuoteFeesPerLp";
+            type: "u128";
+          },
+          {
+            name: "quoteVolume";
+            docs: [
+              "The total amount of quote tokens that have been swapped in or out of",
+              "this AMM, so other programs can tell whether a market was actually traded."
+            ];
+            type: "u128";
+          },
+          {
+            name: "quoteLiquidityAggregator";
+            docs: [
+              "Running sum of quote_amount over every slot up to",
+              "`quote_liquidity_updated_slot`. Each change to the reserves first credits",
+              "the reserve from before the change for the slots since the last one, so",
+              "liquidity only counts for the slots that it was actually in the pool.",
+              "Lets clients check that a market actually had liquidity over some",
+              "period, rather than only at the moments they happened to look at it.",
+              "",
+              "Like the TWAP aggregator, this wraps back to 0 on overflow, so compare",
+              "two readings with `get_average_quote_liquidity_since`."
+            ];
+            type: "u128";
+          },
+          {
+            name: "quoteLiquidityUpdatedSlot";
+            type: "u64";
           }
         ];
       };
@@ -431,6 +505,12 @@ export type Amm = {
           {
             name: "twapMaxObservationChangePerUpdate";
             type: "u128";
+          },
+          {
+            name: "curve";
+            type: {
+              defined: "AmmCurve";
+            };
           }
         ];
       };
@@ -538,6 +618,23 @@ export type Amm = {
         ];
       };
     },
+    {
+      name: "FeeSnapshot";
+      docs: ["The state of an AMM's fee accumulators at a point in time."];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "cumulativeBaseFeesPerLp";
+            type: "u128";
+          },
+          {
+            name: "cumulativeQuoteFeesPerLp";
+            type: "u128";
+          }
+        ];
+      };
+    },
     {
       name: "SwapType";
       type: {
@@ -551,6 +648,26 @@ export type Amm = {
           }
         ];
       };
+    },
+    {
+      name: "AmmCurve";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "ConstantProduct";
+          },
+          {
+            name: "StableSwap";
+            fields: [
+              {
+                name: "amplification";
+                type: "u64";
+              }
+            ];
+          }
+        ];
+      };
     }
   ];
   events: [
@@ -580,6 +697,41 @@ export type Amm = {
             defined: "SwapType";
           };
           index: false;
+        },
+        {
+          name: "version";
+          type: "u8";
+          index: false;
+        },
+        {
+          name: "feeAmount";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "preBaseReserves";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "preQuoteReserves";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "preSpotPrice";
+          type: "u128";
+          index: false;
+        },
+        {
+          name: "postSpotPrice";
+          type: "u128";
+          index: false;
+        },
+        {
+          name: "twapObservationRecorded";
+          type: "bool";
+          index: false;
         }
       ];
     },
@@ -701,6 +853,13 @@ export type Amm = {
           name: "vaultAtaQuote";
           type: "publicKey";
           index: false;
+        },
+        {
+          name: "curve";
+          type: {
+            defined: "AmmCurve";
+          };
+          index: false;
         }
       ];
     },
@@ -807,6 +966,31 @@ export type Amm = {
       code: 6017;
       name: "CastingOverflow";
       msg: "Casting has caused an overflow";
+    },
+    {
+      code: 6018;
+      name: "InvalidAmplification";
+      msg: "StableSwap `amplification` must be between `MIN_AMPLIFICATION` and `MAX_AMPLIFICATION`";
+    },
+    {
+      code: 6019;
+      name: "StableSwapDecimalsMismatch";
+      msg: "StableSwap pools need their base and quote mints to have the same decimals";
+    },
+    {
+      code: 6020;
+      name: "StableSwapCalculationError";
+      msg: "StableSwap math overflowed or failed to converge";
+    },
+    {
+      code: 6021;
+      name: "StableSwapInvariantFailed";
+      msg: "D should always be increasing";
+    },
+    {
+      code: 6022;
+      name: "AmmAlreadyMigrated";
+      msg: "This AMM is already in the current layout";
     }
   ];
 };
@@ -1037,6 +1221,11 @@ export const IDL: Amm = {
           isMut: true,
           isSigner: false,
         },
+        {
+          name: "lpMint",
+          isMut: false,
+          isSigner: false,
+        },
         {
           name: "userBaseAccount",
           isMut: true,
@@ -1103,6 +1292,28 @@ export const IDL: Amm = {
       ],
       args: [],
     },
+    {
+      name: "migrateAmm",
+      accounts: [
+        {
+          name: "amm",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+          docs: ["Pays for the AMM's account to grow to the current layout."],
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
   ],
   accounts: [
     {
@@ -1156,6 +1367,53 @@ export const IDL: Amm = {
             name: "seqNum",
             type: "u64",
           },
+          {
+            name: "curve",
+            type: {
+              defined: "AmmCurve",
+            },
+          },
+          {
+            name: "cumulativeBaseFeesPerLp",
+            docs: [
+              "Running sums of the fees that each LP token has earned, in Q64.64 form.",
+              "Like the TWAP aggregator, these wrap on overflow, so compare two",
+              "snapshots with `FeeSnapshot::get_fees_earned_since` rather than by",
+              "subtracting them directly.",
+            ],
+            type: "u128",
+          },
+          {
+            name: "cumulativeQuoteFeesPerLp",
+            type: "u128",
+          },
+          {
+            name: "quoteVolume",
+            docs: [
+              "The total amount of quote tokens that have been swapped in or out of",
+              "this AMM, so other programs can tell whether a market was actually traded.",
+            ],
+            type: "u128",
+          },
+          {
+            name: "quoteLiquidityAggregator",
+            docs: [
+              "Running sum of quote_amount over every slot up to",
+              "`quote_liquidity_updated_slot`. Each change to the reserves first credits",
+              "the reserve from before the change for the slots since the last one, so",
+              "liquidity only counts for the slots that it was actually in the pool.",
+              "Lets clients check that a market actually had liquidity over some",
+              "period, rather than only at the moments they happened to look at it.",
+              "",
+              "Like the TWAP aggregator, this wraps back to 0 on overflow, so compare",
+              "two readings with `get_average_quote_liquidity_since`.",
+            ],
+            type: "u128",
+          },
+          {
+            name: "quoteLiquidityUpdatedSlot",
+            type: "u64",
+          },
         ],
       },
     },
@@ -1245,6 +1503,12 @@ export const IDL: Amm = {
             name: "twapMaxObservationChangePerUpdate",
             type: "u128",
           },
+          {
+            name: "curve",
+            type: {
+              defined: "AmmCurve",
+            },
+          },
         ],
       },
     },
@@ -1351,6 +1615,23 @@ export const IDL: Amm = {
         ],
       },
     },
+    {
+      name: "FeeSnapshot",
+      docs: ["The state of an AMM's fee accumulators at a point in time."],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "cumulativeBaseFeesPerLp",
+            type: "u128",
+          },
+          {
+            name: "cumulativeQuoteFeesPerLp",
+            type: "u128",
+          },
+        ],
+      },
+    },
     {
       name: "SwapType",
       type: {
@@ -1365,6 +1646,26 @@ export const IDL: Amm = {
         ],
       },
     },
+    {
+      name: "AmmCurve",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "ConstantProduct",
+          },
+          {
+            name: "StableSwap",
+            fields: [
+              {
+                name: "amplification",
+                type: "u64",
+              },
+            ],
+          },
+        ],
+      },
+    },
   ],
   events: [
     {
@@ -1394,6 +1695,41 @@ export const IDL: Amm = {
           },
           index: false,
         },
+        {
+          name: "version",
+          type: "u8",
+          index: false,
+        },
+        {
+          name: "feeAmount",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "preBaseReserves",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "preQuoteReserves",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "preSpotPrice",
+          type: "u128",
+          index: false,
+        },
+        {
+          name: "postSpotPrice",
+          type: "u128",
+          index: false,
+        },
+        {
+          name: "twapObservationRecorded",
+          type: "bool",
+          index: false,
+        },
       ],
     },
     {
@@ -1515,6 +1851,13 @@ export const IDL: Amm = {
           type: "publicKey",
           index: false,
         },
+        {
+          name: "curve",
+          type: {
+            defined: "AmmCurve",
+          },
+          index: false,
+        },
       ],
     },
     {
@@ -1621,5 +1964,30 @@ export const IDL: Amm = {
       name: "CastingOverflow",
       msg: "Casting has caused an overflow",
     },
+    {
+      code: 6018,
+      name: "InvalidAmplification",
+      msg: "StableSwap `amplification` must be between `MIN_AMPLIFICATION` and `MAX_AMPLIFICATION`",
+    },
+    {
+      code: 6019,
+      name: "StableSwapDecimalsMismatch",
+      msg: "StableSwap pools need their base and quote mints to have the same decimals",
+    },
+    {
+      code: 6020,
+      name: "StableSwapCalculationError",
+      msg: "StableSwap math overflowed or failed to converge",
+    },
+    {
+      code: 6021,
+      name: "StableSwapInvariantFailed",
+      msg: "D should always be increasing",
+    },
+    {
+      code: 6022,
+      name: "AmmAlreadyMigrated",
+      msg: "This AMM is already in the current layout",
+    },
   ],
 };
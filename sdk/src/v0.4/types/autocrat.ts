@@ -29,6 +29,16 @@ export type Autocrat = {
           name: "usdcMint";
           isMut: false;
           isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
         }
       ];
       args: [
@@ -41,83 +51,233 @@ export type Autocrat = {
       ];
     },
     {
-      name: "initializeProposal";
+      name: "initializeProposalInstructions";
       accounts: [
         {
-          name: "proposal";
+          name: "authority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "proposalInstructions";
+          isMut: true;
+          isSigner: true;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "addProposalInstruction";
+      accounts: [
+        {
+          name: "authority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "proposalInstructions";
+          isMut: true;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "instruction";
+          type: {
+            defined: "ProposalInstruction";
+          };
+        }
+      ];
+    },
+    {
+      name: "initializeProposalSetup";
+      accounts: [
+        {
+          name: "proposalSetup";
           isMut: true;
           isSigner: false;
         },
         {
           name: "dao";
-          isMut: true;
+          isMut: false;
           isSigner: false;
         },
+        {
+          name: "proposer";
+          isMut: true;
+          isSigner: true;
+        },
         {
           name: "question";
-          isMut: false;
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "baseVault";
+          isMut: true;
           isSigner: false;
         },
         {
           name: "quoteVault";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "tokenMint";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "baseVault";
+          name: "usdcMint";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "passAmm";
+          name: "baseVaultUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "quoteVaultUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "vaultProgram";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "passLpMint";
+          name: "vaultEventAuthority";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "failLpMint";
+          name: "tokenProgram";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "failAmm";
+          name: "associatedTokenProgram";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "passLpUserAccount";
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "nonce";
+          type: "u64";
+        }
+      ];
+    },
+    {
+      name: "setupProposalAmm";
+      accounts: [
+        {
+          name: "proposalSetup";
           isMut: true;
           isSigner: false;
         },
         {
-          name: "failLpUserAccount";
+          name: "proposer";
           isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "dao";
+          isMut: false;
           isSigner: false;
         },
         {
-          name: "passLpVaultAccount";
+          name: "treasury";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "baseVault";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "quoteVault";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "amm";
           isMut: true;
           isSigner: false;
         },
         {
-          name: "failLpVaultAccount";
+          name: "lpMint";
           isMut: true;
           isSigner: false;
         },
         {
-          name: "proposer";
+          name: "baseMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "quoteMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultAtaBase";
           isMut: true;
-          isSigner: true;
+          isSigner: false;
+        },
+        {
+          name: "vaultAtaQuote";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "userLpAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "userBaseAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "userQuoteAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treasuryLpAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "ammProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "ammEventAuthority";
+          isMut: false;
+          isSigner: false;
         },
         {
           name: "tokenProgram";
           isMut: false;
           isSigner: false;
         },
+        {
+          name: "associatedTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
         {
           name: "systemProgram";
           isMut: false;
@@ -128,13 +288,13 @@ export type Autocrat = {
         {
           name: "params";
           type: {
-            defined: "InitializeProposalParams";
+            defined: "SetupProposalAmmParams";
           };
         }
       ];
     },
     {
-      name: "finalizeProposal";
+      name: "initializeProposal";
       accounts: [
         {
           name: "proposal";
@@ -142,30 +302,64 @@ export type Autocrat = {
           isSigner: false;
         },
         {
-          name: "passAmm";
+          name: "dao";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "proposerRecord";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "proposalInstructions";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "question";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "failAmm";
+          name: "quoteVault";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "dao";
+          name: "baseVault";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "question";
-          isMut: true;
+          name: "passAmm";
+          isMut: false;
           isSigner: false;
         },
         {
-          name: "treasury";
+          name: "passLpMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "failLpMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "failAmm";
           isMut: false;
           isSigner: false;
         },
+        {
+          name: "proposalSetup";
+          isMut: true;
+          isSigner: false;
+          docs: [
+            "Consumed here, so that the proposal can only use the accounts that",
+            "autocrat created for it."
+          ];
+        },
         {
           name: "passLpUserAccount";
           isMut: true;
@@ -186,26 +380,73 @@ export type Autocrat = {
           isMut: true;
           isSigner: false;
         },
+        {
+          name: "proposer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "bondMint";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+          docs: [
+            "The bond accounts are only needed if the DAO has a `proposal_bond`."
+          ];
+        },
+        {
+          name: "bondUserAccount";
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "bondEscrow";
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "spotAmm";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+          docs: [
+            "Must be the DAO's `spot_amm` if it has one, and omitted otherwise."
+          ];
+        },
         {
           name: "tokenProgram";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "vaultProgram";
+          name: "systemProgram";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "vaultEventAuthority";
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
           isMut: false;
           isSigner: false;
         }
       ];
-      args: [];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: "InitializeProposalParams";
+          };
+        }
+      ];
     },
     {
-      name: "executeProposal";
+      name: "seedProposalMarket";
       accounts: [
         {
           name: "proposal";
@@ -216,754 +457,6912 @@ export type Autocrat = {
           name: "dao";
           isMut: false;
           isSigner: false;
-        }
-      ];
-      args: [];
-    },
-    {
-      name: "updateDao";
-      accounts: [
-        {
-          name: "dao";
-          isMut: true;
-          isSigner: false;
         },
         {
           name: "treasury";
           isMut: false;
-          isSigner: true;
-        }
-      ];
-      args: [
+          isSigner: false;
+        },
         {
-          name: "daoParams";
-          type: {
-            defined: "UpdateDaoParams";
-          };
+          name: "question";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "baseVault";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "quoteVault";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "baseVaultUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "quoteVaultUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treasuryBaseAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treasuryQuoteAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "amm";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "lpMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "ammVaultAtaBase";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "ammVaultAtaQuote";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treasuryLpAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "vaultProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultEventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "ammProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "ammEventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
         }
       ];
-    }
-  ];
-  accounts: [
+      args: [];
+    },
+    {
+      name: "settleTreasuryPositions";
+      accounts: [
+        {
+          name: "proposal";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "dao";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "question";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "baseVault";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "quoteVault";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "baseVaultUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "quoteVaultUnderlyingTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treasuryBaseAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treasuryQuoteAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "amm";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "lpMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "ammVaultAtaBase";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "ammVaultAtaQuote";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treasuryLpAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "vaultProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultEventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "ammProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "ammEventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "finalizeProposal";
+      accounts: [
+        {
+          name: "proposal";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "instructions";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "passAmm";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "failAmm";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "dao";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "proposerRecord";
+          isMut: true;
+          isSigner: false;
+          docs: [
+            "Created here for proposals that were made before proposer records",
+            "existed."
+          ];
+        },
+        {
+          name: "question";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "passLpUserAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "failLpUserAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "passLpVaultAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "failLpVaultAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "spotAmm";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+          docs: ["Only needed if the proposal has a `spot_oracle_snapshot`."];
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultEventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "cancelProposal";
+      accounts: [
+        {
+          name: "proposal";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "proposer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "passAmm";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "failAmm";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "dao";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "proposerRecord";
+          isMut: true;
+          isSigner: false;
+          docs: [
+            "Created here for proposals that were made before proposer records",
+            "existed."
+          ];
+        },
+        {
+          name: "question";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "passLpUserAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "failLpUserAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "passLpVaultAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "failLpVaultAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultEventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "executeProposal";
+      accounts: [
+        {
+          name: "proposal";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "instructions";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "dao";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "settleProposalBond";
+      accounts: [
+        {
+          name: "proposal";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "dao";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "bondEscrow";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "proposer";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "proposerBondAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "treasuryBondAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "expireProposal";
+      accounts: [
+        {
+          name: "proposal";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "dao";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "vetoProposal";
+      accounts: [
+        {
+          name: "proposal";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "dao";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vetoAuthority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "initializeMultiOptionProposal";
+      accounts: [
+        {
+          name: "proposal";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "dao";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "proposerRecord";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "question";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "quoteVault";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "baseVault";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "proposer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "spotAmm";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+          docs: [
+            "Must be the DAO's `spot_amm` if it has one, and omitted otherwise."
+          ];
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: "InitializeMultiOptionProposalParams";
+          };
+        }
+      ];
+    },
+    {
+      name: "finalizeMultiOptionProposal";
+      accounts: [
+        {
+          name: "proposal";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "dao";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "proposerRecord";
+          isMut: true;
+          isSigner: false;
+          docs: [
+            "Created here for proposals that were made before proposer records",
+            "existed."
+          ];
+        },
+        {
+          name: "question";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultEventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "spotAmm";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+          docs: ["Only needed if the proposal has a `spot_oracle_snapshot`."];
+        },
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "cancelMultiOptionProposal";
+      accounts: [
+        {
+          name: "proposal";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "proposer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "dao";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "proposerRecord";
+          isMut: true;
+          isSigner: false;
+          docs: [
+            "Created here for proposals that were made before proposer records",
+            "existed."
+          ];
+        },
+        {
+          name: "question";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vaultEventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "executeMultiOptionProposal";
+      accounts: [
+        {
+          name: "proposal";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "instructions";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "dao";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "expireMultiOptionProposal";
+      accounts: [
+        {
+          name: "proposal";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "dao";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "vetoMultiOptionProposal";
+      accounts: [
+        {
+          name: "proposal";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "dao";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "vetoAuthority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "eventAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "migrateProposal";
+      accounts: [
+        {
+          name: "proposal";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "dao";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "proposalInstructions";
+          isMut: true;
+          isSigner: true;
+          docs: [
+            "Receives the proposal's instruction, which older proposals stored inline."
+          ];
+        },
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+          docs: [
+            "Pays for the proposal's account to grow to the current layout."
+          ];
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "migrateDao";
+      accounts: [
+        {
+          name: "dao";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+          docs: ["Pays for the DAO's account to grow to the current layout."];
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "updateDao";
+      accounts: [
+        {
+          name: "dao";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "daoParams";
+          type: {
+            defined: "UpdateDaoParams";
+          };
+        }
+      ];
+    },
+    {
+      name: "addProposalCategory";
+      accounts: [
+        {
+          name: "dao";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "category";
+          type: {
+            defined: "ProposalCategory";
+          };
+        }
+      ];
+    },
+    {
+      name: "removeProposalCategory";
+      accounts: [
+        {
+          name: "dao";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "programId";
+          type: "publicKey";
+        },
+        {
+          name: "dataPrefix";
+          type: "bytes";
+        }
+      ];
+    },
+    {
+      name: "setSpendingLimit";
+      accounts: [
+        {
+          name: "dao";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: "SetSpendingLimitParams";
+          };
+        }
+      ];
+    },
+    {
+      name: "removeSpendingLimit";
+      accounts: [
+        {
+          name: "dao";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "mint";
+          type: "publicKey";
+        }
+      ];
+    }
+  ];
+  accounts: [
+    {
+      name: "dao";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "treasuryPdaBump";
+            type: "u8";
+          },
+          {
+            name: "treasury";
+            type: "publicKey";
+          },
+          {
+            name: "tokenMint";
+            type: "publicKey";
+          },
+          {
+            name: "usdcMint";
+            type: "publicKey";
+          },
+          {
+            name: "proposalCount";
+            type: "u32";
+          },
+          {
+            name: "passThresholdBps";
+            type: "u16";
+          },
+          {
+            name: "slotsPerProposal";
+            type: "u64";
+          },
+          {
+            name: "twapInitialObservation";
+            docs: [
+              "For manipulation-resistance the TWAP is a time-weighted average observation,",
+              "where observation tries to approximate price but can only move by",
+              "`twap_max_observation_change_per_update` per update. Because it can only move",
+              "a little bit per update, you need to check that it has a good initial observation.",
+              "Otherwise, an attacker could create a very high initial observation in the pass",
+              "market and a very low one in the fail market to force the proposal to pass.",
+              "",
+              "We recommend setting an initial observation around the spot price of the token,",
+              "and max observation change per update around 2% the spot price of the token.",
+              "For example, if the spot price of META is $400, we'd recommend setting an initial",
+              "observation of 400 (converted into the AMM prices) and a max observation change per",
+              "update of 8 (also converted into the AMM prices). Observations can be updated once",
+              "a minute, so 2% allows the proposal market to reach double the spot price or 0",
+              "in 50 minutes."
+            ];
+            type: "u128";
+          },
+          {
+            name: "twapMaxObservationChangePerUpdate";
+            type: "u128";
+          },
+          {
+            name: "minQuoteFutarchicLiquidity";
+            docs: [
+              "As an anti-spam measure and to help liquidity, you need to lock up some liquidity",
+              "in both futarchic markets in order to create a proposal.",
+              "",
+              "For example, for META, we can use a `min_quote_futarchic_liquidity` of",
+              "5000 * 1_000_000 (5000 USDC) and a `min_base_futarchic_liquidity` of",
+              "10 * 1_000_000_000 (10 META)."
+            ];
+            type: "u64";
+          },
+          {
+            name: "minBaseFutarchicLiquidity";
+            type: "u64";
+          },
+          {
+            name: "minAverageQuoteLiquidity";
+            docs: [
+              "Locking liquidity only proves that a market had liquidity when the proposal",
+              "was created. To pass, both markets also need to have had a time-weighted",
+              "average quote reserve of at least this much. 0 disables the check."
+            ];
+            type: "u64";
+          },
+          {
+            name: "executionDelaySlots";
+            docs: [
+              "How long a passed proposal has to wait before it can be executed. This",
+              "gives the `veto_authority` time to step in if the markets got it wrong."
+            ];
+            type: "u64";
+          },
+          {
+            name: "vetoAuthority";
+            docs: [
+              "Can veto passed proposals before they're executed, as a last line of",
+              "defense against market manipulation. This could be a multisig or the",
+              "treasury of another futarchy."
+            ];
+            type: {
+              option: "publicKey";
+            };
+          },
+          {
+            name: "executionWindowSlots";
+            docs: [
+              "How long a passed proposal can be executed for once its execution delay",
+              "is over. After that, its instructions are considered stale and anyone can",
+              "mark it as expired."
+            ];
+            type: "u64";
+          },
+          {
+            name: "decisionRule";
+            type: {
+              defined: "DecisionRule";
+            };
+          },
+          {
+            name: "proposalCategories";
+            docs: [
+              "At most `MAX_PROPOSAL_CATEGORIES`. Instructions that aren't in any",
+              "category use the DAO's own threshold, duration and liquidity."
+            ];
+            type: {
+              vec: {
+                defined: "ProposalCategory";
+              };
+            };
+          },
+          {
+            name: "proposalBond";
+            docs: [
+              "Multi-option proposals can't be bonded, so DAOs that set this can only",
+              "create regular proposals."
+            ];
+            type: {
+              option: {
+                defined: "ProposalBond";
+              };
+            };
+          },
+          {
+            name: "activeProposalCount";
+            docs: [
+              "The number of proposals, of either kind, that haven't been finalized or",
+              "cancelled yet."
+            ];
+            type: "u32";
+          },
+          {
+            name: "maxActiveProposals";
+            docs: [
+              "Caps on active proposals, so that the DAO's attention, and its traders'",
+              "capital, isn't spread across too many markets at once. `None` means",
+              "no cap."
+            ];
+            type: {
+              option: "u32";
+            };
+          },
+          {
+            name: "maxActiveProposalsPerProposer";
+            type: {
+              option: "u32";
+            };
+          },
+          {
+            name: "spotAmm";
+            docs: [
+              "A market for the DAO's token against USDC, such as a META/USDC `Amm`,",
+              "whose TWAP over each proposal is recorded when it's finalized. If this",
+              "is set, proposals need to pass it in when they're created and finalized."
+            ];
+            type: {
+              option: "publicKey";
+            };
+          },
+          {
+            name: "treasuryLiquidity";
+            docs: [
+              "If set, anyone can make the treasury seed each market of a regular",
+              "proposal with this much liquidity through `seed_proposal_market`."
+            ];
+            type: {
+              option: {
+                defined: "TreasuryLiquidity";
+              };
+            };
+          },
+          {
+            name: "spendingLimits";
+            docs: ["At most `MAX_SPENDING_LIMITS`, at most one per mint."];
+            type: {
+              vec: {
+                defined: "SpendingLimit";
+              };
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "multiOptionProposal";
+      docs: [
+        'A proposal with more than two outcomes, such as "which of these three',
+        'vendors should we hire?". Each outcome of the question has its own market',
+        "and its own instructions, and the option whose market has the highest TWAP",
+        "wins, as long as it beats the status quo under the DAO's decision rule."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "number";
+            type: "u32";
+          },
+          {
+            name: "proposer";
+            type: "publicKey";
+          },
+          {
+            name: "descriptionUrl";
+            type: "string";
+          },
+          {
+            name: "slotEnqueued";
+            type: "u64";
+          },
+          {
+            name: "slotFinalized";
+            docs: ["0 until the proposal is finalized."];
+            type: "u64";
+          },
+          {
+            name: "state";
+            docs: ["`Passed` if an option other than the status quo won."];
+            type: {
+              defined: "ProposalState";
+            };
+          },
+          {
+            name: "options";
+            docs: [
+              "In the same order as the question's outcomes. Option 0 is the status quo,",
+              "which wins if no other option beats it."
+            ];
+            type: {
+              vec: {
+                defined: "ProposalOption";
+              };
+            };
+          },
+          {
+            name: "winningOption";
+            docs: ["`None` until the proposal is finalized."];
+            type: {
+              option: "u8";
+            };
+          },
+          {
+            name: "numInstructionsExecuted";
+            type: "u16";
+          },
+          {
+            name: "passThresholdBps";
+            docs: [
+              "The strictest requirements of any of the options' categories."
+            ];
+            type: "u16";
+          },
+          {
+            name: "slotsPerProposal";
+            type: "u64";
+          },
+          {
+            name: "baseVault";
+            type: "publicKey";
+          },
+          {
+            name: "quoteVault";
+            type: "publicKey";
+          },
+          {
+            name: "dao";
+            type: "publicKey";
+          },
+          {
+            name: "question";
+            type: "publicKey";
+          },
+          {
+            name: "nonce";
+            type: "u64";
+          },
+          {
+            name: "pdaBump";
+            type: "u8";
+          },
+          {
+            name: "daoSnapshot";
+            type: {
+              defined: "DaoSnapshot";
+            };
+          },
+          {
+            name: "spotOracleSnapshot";
+            docs: [
+              "Taken if the DAO had a `spot_amm` when this proposal was created."
+            ];
+            type: {
+              option: {
+                defined: "SpotOracleSnapshot";
+              };
+            };
+          },
+          {
+            name: "spotTwap";
+            docs: [
+              "The spot market's TWAP over this proposal, recorded when it's finalized."
+            ];
+            type: {
+              option: "u128";
+            };
+          },
+          {
+            name: "spendingLimits";
+            docs: [
+              "The DAO's capped `spending_limits` when this proposal was created,",
+              "which its instructions are checked against."
+            ];
+            type: {
+              vec: {
+                defined: "SpendingLimit";
+              };
+            };
+          },
+          {
+            name: "treasuryOutflows";
+            docs: [
+              "How much of each limited mint this proposal has sent out of the",
+              "treasury. See `SpendingLimit`."
+            ];
+            type: {
+              vec: {
+                defined: "TreasuryOutflow";
+              };
+            };
+          },
+          {
+            name: "decision";
+            docs: [
+              "Set when the proposal is finalized, unless it was cancelled."
+            ];
+            type: {
+              option: {
+                defined: "MultiOptionDecision";
+              };
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "proposalInstructions";
+      docs: [
+        "The instructions of a proposal, stored separately so that a proposer can",
+        "build up a payload that's too big for one transaction. The proposer allocates",
+        "this account with whatever space they need, appends instructions to it with",
+        "`add_proposal_instruction`, and then passes it into `initialize_proposal`,",
+        "which freezes it."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "authority";
+            type: "publicKey";
+          },
+          {
+            name: "frozen";
+            type: "bool";
+          },
+          {
+            name: "instructions";
+            type: {
+              vec: {
+                defined: "ProposalInstruction";
+              };
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "proposalSetup";
+      docs: [
+        "Tracks a proposer's progress through creating the accounts that a proposal",
+        "needs, since they don't all fit in one transaction. Only the proposer can",
+        "advance it, and every account it creates is a canonical PDA derived from the",
+        "proposal's address, so nobody can swap in their own question, vaults or",
+        "markets halfway through.",
+        "",
+        "1. `initialize_proposal_setup` creates this account, the question and both",
+        "conditional vaults.",
+        "2. `setup_proposal_amm` creates the pass market and adds the proposer's",
+        "liquidity to it.",
+        "3. `setup_proposal_amm` does the same for the fail market.",
+        "",
+        "After that, the proposer can call `initialize_proposal` with the same nonce,",
+        "which checks the proposal's accounts against this one and closes it."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "proposer";
+            type: "publicKey";
+          },
+          {
+            name: "dao";
+            type: "publicKey";
+          },
+          {
+            name: "nonce";
+            type: "u64";
+          },
+          {
+            name: "proposal";
+            docs: [
+              "The address that the proposal will have once it's initialized."
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "stage";
+            type: {
+              defined: "ProposalSetupStage";
+            };
+          },
+          {
+            name: "question";
+            type: "publicKey";
+          },
+          {
+            name: "baseVault";
+            type: "publicKey";
+          },
+          {
+            name: "quoteVault";
+            type: "publicKey";
+          },
+          {
+            name: "passAmm";
+            type: "publicKey";
+          },
+          {
+            name: "failAmm";
+            type: "publicKey";
+          },
+          {
+            name: "pdaBump";
+            type: "u8";
+          }
+        ];
+      };
+    },
+    {
+      name: "proposal";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "version";
+            docs: [
+              "`PROPOSAL_VERSION` for proposals created or migrated by this version of",
+              "autocrat. See `migrate_proposal`. This is always the first byte after",
+              "the discriminator, so clients can filter on it."
+            ];
+            type: "u8";
+          },
+          {
+            name: "number";
+            type: "u32";
+          },
+          {
+            name: "proposer";
+            type: "publicKey";
+          },
+          {
+            name: "descriptionUrl";
+            type: "string";
+          },
+          {
+            name: "slotEnqueued";
+            type: "u64";
+          },
+          {
+            name: "slotFinalized";
+            docs: ["0 until the proposal is finalized."];
+            type: "u64";
+          },
+          {
+            name: "state";
+            type: {
+              defined: "ProposalState";
+            };
+          },
+          {
+            name: "instructions";
+            docs: [
+              "The `ProposalInstructions` account holding the instructions to execute,",
+              "in order, if this proposal passes."
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "numInstructionsExecuted";
+            type: "u16";
+          },
+          {
+            name: "passThresholdBps";
+            docs: [
+              "Taken from the proposal's category when it's initialized, so that",
+              "later changes to the DAO don't affect it."
+            ];
+            type: "u16";
+          },
+          {
+            name: "slotsPerProposal";
+            type: "u64";
+          },
+          {
+            name: "passAmm";
+            type: "publicKey";
+          },
+          {
+            name: "failAmm";
+            type: "publicKey";
+          },
+          {
+            name: "baseVault";
+            type: "publicKey";
+          },
+          {
+            name: "quoteVault";
+            type: "publicKey";
+          },
+          {
+            name: "dao";
+            type: "publicKey";
+          },
+          {
+            name: "passLpTokensLocked";
+            type: "u64";
+          },
+          {
+            name: "failLpTokensLocked";
+            type: "u64";
+          },
+          {
+            name: "nonce";
+            docs: [
+              "We need to include a per-proposer nonce to prevent some weird proposal",
+              "front-running edge cases. Using a `u64` means that proposers are unlikely",
+              "to run into collisions, even if they generate nonces randomly - I've run",
+              "the math :D"
+            ];
+            type: "u64";
+          },
+          {
+            name: "pdaBump";
+            type: "u8";
+          },
+          {
+            name: "question";
+            type: "publicKey";
+          },
+          {
+            name: "bond";
+            docs: [
+              "The DAO's bond when this proposal was created, if it had one. The bond",
+              'is held in a token account with the seeds `[b"bond", proposal]` until',
+              "it's settled with `settle_proposal_bond`."
+            ];
+            type: {
+              option: {
+                defined: "ProposalBond";
+              };
+            };
+          },
+          {
+            name: "bondSettled";
+            type: "bool";
+          },
+          {
+            name: "spotOracleSnapshot";
+            docs: [
+              "Taken if the DAO had a `spot_amm` when this proposal was created."
+            ];
+            type: {
+              option: {
+                defined: "SpotOracleSnapshot";
+              };
+            };
+          },
+          {
+            name: "spotTwap";
+            docs: [
+              "The spot market's TWAP over this proposal, recorded when it's finalized."
+            ];
+            type: {
+              option: "u128";
+            };
+          },
+          {
+            name: "marketSnapshots";
+            docs: [
+              "Taken of each market when this proposal was created, indexed by outcome."
+            ];
+            type: {
+              array: [
+                {
+                  defined: "MarketSnapshot";
+                },
+                2
+              ];
+            };
+          },
+          {
+            name: "quoteVolumes";
+            docs: [
+              "How much quote was swapped in each market over this proposal, indexed",
+              "by outcome. Recorded when the proposal is finalized or cancelled, so",
+              "that the bond can be settled without trusting the markets afterwards."
+            ];
+            type: {
+              array: ["u128", 2];
+            };
+          },
+          {
+            name: "treasuryLpTokens";
+            docs: [
+              "The LP tokens that the treasury got for seeding each market, indexed by",
+              "outcome. These are held in the same accounts as the proposer's locked LP.",
+              "Set back to 0 once `settle_treasury_positions` has removed them."
+            ];
+            type: {
+              array: ["u64", 2];
+            };
+          },
+          {
+            name: "treasuryMaxPriceDeviationBps";
+            docs: [
+              "The DAO's `max_price_deviation_bps` when the treasury last seeded one of",
+              "this proposal's markets, which also bounds the price that",
+              "`settle_treasury_positions` removes the treasury's liquidity at."
+            ];
+            type: "u16";
+          },
+          {
+            name: "decision";
+            docs: [
+              "Set when the proposal is finalized, unless it was cancelled."
+            ];
+            type: {
+              option: {
+                defined: "ProposalDecision";
+              };
+            };
+          },
+          {
+            name: "daoSnapshot";
+            type: {
+              defined: "DaoSnapshot";
+            };
+          },
+          {
+            name: "spendingLimits";
+            docs: [
+              "The DAO's capped `spending_limits` when this proposal was created,",
+              "which its instructions are checked against."
+            ];
+            type: {
+              vec: {
+                defined: "SpendingLimit";
+              };
+            };
+          },
+          {
+            name: "treasuryOutflows";
+            docs: [
+              "How much of each limited mint this proposal has sent out of the",
+              "treasury. See `SpendingLimit`."
+            ];
+            type: {
+              vec: {
+                defined: "TreasuryOutflow";
+              };
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "proposerRecord";
+      docs: [
+        "Tracks how many proposals a proposer has open in a DAO, so that the DAO can",
+        "cap it with `max_active_proposals_per_proposer`."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "dao";
+            type: "publicKey";
+          },
+          {
+            name: "proposer";
+            type: "publicKey";
+          },
+          {
+            name: "activeProposalCount";
+            type: "u32";
+          },
+          {
+            name: "pdaBump";
+            type: "u8";
+          }
+        ];
+      };
+    }
+  ];
+  types: [
+    {
+      name: "CommonFields";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "slot";
+            type: "u64";
+          },
+          {
+            name: "unixTimestamp";
+            type: "i64";
+          }
+        ];
+      };
+    },
+    {
+      name: "DaoConfig";
+      docs: [
+        "The parts of a DAO's config that decide how its proposals are traded and",
+        "finalized."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "passThresholdBps";
+            type: "u16";
+          },
+          {
+            name: "slotsPerProposal";
+            type: "u64";
+          },
+          {
+            name: "twapInitialObservation";
+            type: "u128";
+          },
+          {
+            name: "twapMaxObservationChangePerUpdate";
+            type: "u128";
+          },
+          {
+            name: "minQuoteFutarchicLiquidity";
+            type: "u64";
+          },
+          {
+            name: "minBaseFutarchicLiquidity";
+            type: "u64";
+          },
+          {
+            name: "minAverageQuoteLiquidity";
+            type: "u64";
+          },
+          {
+            name: "executionDelaySlots";
+            type: "u64";
+          },
+          {
+            name: "vetoAuthority";
+            type: {
+              option: "publicKey";
+            };
+          },
+          {
+            name: "executionWindowSlots";
+            type: "u64";
+          },
+          {
+            name: "decisionRule";
+            type: {
+              defined: "DecisionRule";
+            };
+          },
+          {
+            name: "proposalBond";
+            type: {
+              option: {
+                defined: "ProposalBond";
+              };
+            };
+          },
+          {
+            name: "maxActiveProposals";
+            type: {
+              option: "u32";
+            };
+          },
+          {
+            name: "maxActiveProposalsPerProposer";
+            type: {
+              option: "u32";
+            };
+          },
+          {
+            name: "spotAmm";
+            type: {
+              option: "publicKey";
+            };
+          },
+          {
+            name: "treasuryLiquidity";
+            type: {
+              option: {
+                defined: "TreasuryLiquidity";
+              };
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "InitializeDaoParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "twapInitialObservation";
+            type: "u128";
+          },
+          {
+            name: "twapMaxObservationChangePerUpdate";
+            type: "u128";
+          },
+          {
+            name: "minQuoteFutarchicLiquidity";
+            type: "u64";
+          },
+          {
+            name: "minBaseFutarchicLiquidity";
+            type: "u64";
+          },
+          {
+            name: "passThresholdBps";
+            type: {
+              option: "u16";
+            };
+          },
+          {
+            name: "slotsPerProposal";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "minAverageQuoteLiquidity";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "executionDelaySlots";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "vetoAuthority";
+            type: {
+              option: "publicKey";
+            };
+          },
+          {
+            name: "executionWindowSlots";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "decisionRule";
+            type: {
+              option: {
+                defined: "DecisionRule";
+              };
+            };
+          },
+          {
+            name: "proposalBond";
+            type: {
+              option: {
+                defined: "ProposalBond";
+              };
+            };
+          },
+          {
+            name: "maxActiveProposals";
+            type: {
+              option: "u32";
+            };
+          },
+          {
+            name: "maxActiveProposalsPerProposer";
+            type: {
+              option: "u32";
+            };
+          },
+          {
+            name: "spotAmm";
+            type: {
+              option: "publicKey";
+            };
+          },
+          {
+            name: "treasuryLiquidity";
+            type: {
+              option: {
+                defined: "TreasuryLiquidity";
+              };
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "InitializeMultiOptionProposalParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "descriptionUrl";
+            type: "string";
+          },
+          {
+            name: "lpTokensToLock";
+            docs: [
+              "How many LP tokens to lock in each option's market, including the status",
+              "quo's, in outcome order."
+            ];
+            type: {
+              vec: "u64";
+            };
+          },
+          {
+            name: "nonce";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "InitializeProposalParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "descriptionUrl";
+            type: "string";
+          },
+          {
+            name: "passLpTokensToLock";
+            type: "u64";
+          },
+          {
+            name: "failLpTokensToLock";
+            type: "u64";
+          },
+          {
+            name: "nonce";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "SetSpendingLimitParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "mint";
+            type: "publicKey";
+          },
+          {
+            name: "maxPerProposal";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "maxPerWindow";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "windowSlots";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "SetupProposalAmmParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "quoteAmount";
+            type: "u64";
+          },
+          {
+            name: "maxBaseAmount";
+            type: "u64";
+          },
+          {
+            name: "minLpTokens";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "UpdateDaoParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "passThresholdBps";
+            type: {
+              option: "u16";
+            };
+          },
+          {
+            name: "slotsPerProposal";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "twapInitialObservation";
+            type: {
+              option: "u128";
+            };
+          },
+          {
+            name: "twapMaxObservationChangePerUpdate";
+            type: {
+              option: "u128";
+            };
+          },
+          {
+            name: "minQuoteFutarchicLiquidity";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "minBaseFutarchicLiquidity";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "minAverageQuoteLiquidity";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "executionDelaySlots";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "vetoAuthority";
+            docs: ["`Some(None)` removes the veto authority."];
+            type: {
+              option: {
+                option: "publicKey";
+              };
+            };
+          },
+          {
+            name: "executionWindowSlots";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "decisionRule";
+            type: {
+              option: {
+                defined: "DecisionRule";
+              };
+            };
+          },
+          {
+            name: "proposalBond";
+            docs: ["`Some(None)` removes the bond."];
+            type: {
+              option: {
+                option: {
+                  defined: "ProposalBond";
+                };
+              };
+            };
+          },
+          {
+            name: "maxActiveProposals";
+            docs: ["`Some(None)` removes the cap."];
+            type: {
+              option: {
+                option: "u32";
+              };
+            };
+          },
+          {
+            name: "maxActiveProposalsPerProposer";
+            docs: ["`Some(None)` removes the cap."];
+            type: {
+              option: {
+                option: "u32";
+              };
+            };
+          },
+          {
+            name: "spotAmm";
+            docs: ["`Some(None)` removes the spot market."];
+            type: {
+              option: {
+                option: "publicKey";
+              };
+            };
+          },
+          {
+            name: "treasuryLiquidity";
+            docs: ["`Some(None)` stops the treasury from seeding new markets."];
+            type: {
+              option: {
+                option: {
+                  defined: "TreasuryLiquidity";
+                };
+              };
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "ProposalBond";
+      docs: [
+        "A deposit that proposers put up when they create a proposal, so that spam",
+        "costs more than the rent and the time value of the locked LP."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "mint";
+            docs: ["Either the DAO's token or USDC."];
+            type: "publicKey";
+          },
+          {
+            name: "amount";
+            type: "u64";
+          },
+          {
+            name: "refundQuoteVolume";
+            docs: [
+              "The bond is returned if the proposal passes or if at least this much",
+              "quote was traded in each of its markets. Otherwise, it goes to the",
+              "treasury. Liquidity that the proposer or the treasury adds doesn't",
+              "count, since it isn't traded. Proposers can still trade against",
+              "themselves, but every swap pays a fee to the market's other LPs, so",
+              "this should be high enough that faking it costs more than the bond."
+            ];
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "TreasuryLiquidity";
+      docs: [
+        "How much liquidity the treasury adds to each of a proposal's markets, so",
+        "that proposers don't need to bring all of it themselves."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "quoteAmount";
+            type: "u64";
+          },
+          {
+            name: "maxBaseAmount";
+            docs: [
+              "The most of the DAO's token the treasury adds along with",
+              "`quote_amount`. How much is actually added depends on the market's price."
+            ];
+            type: "u64";
+          },
+          {
+            name: "maxPriceDeviationBps";
+            docs: [
+              "How far, in bps, a market's price can be from its TWAP oracle's last",
+              "observation for the treasury to seed it. The observation can only move",
+              "by `twap_max_observation_change_per_update` each update, so it's much",
+              "harder to push around than the price."
+            ];
+            type: "u16";
+          }
+        ];
+      };
+    },
+    {
+      name: "DaoV0";
+      docs: [
+        "The layout of DAOs created before any of the fields after",
+        "`min_base_futarchic_liquidity` were added. These can be brought up to date",
+        "with `migrate_dao`."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "treasuryPdaBump";
+            type: "u8";
+          },
+          {
+            name: "treasury";
+            type: "publicKey";
+          },
+          {
+            name: "tokenMint";
+            type: "publicKey";
+          },
+          {
+            name: "usdcMint";
+            type: "publicKey";
+          },
+          {
+            name: "proposalCount";
+            type: "u32";
+          },
+          {
+            name: "passThresholdBps";
+            type: "u16";
+          },
+          {
+            name: "slotsPerProposal";
+            type: "u64";
+          },
+          {
+            name: "twapInitialObservation";
+            type: "u128";
+          },
+          {
+            name: "twapMaxObservationChangePerUpdate";
+            type: "u128";
+          },
+          {
+            name: "minQuoteFutarchicLiquidity";
+            type: "u64";
+          },
+          {
+            name: "minBaseFutarchicLiquidity";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "DaoSnapshot";
+      docs: [
+        "The parts of a DAO's config that decide how a proposal is finalized and",
+        "executed. Proposals copy these from their DAO when they're created, so that",
+        "`update_dao` can't change the rules for proposals that are already trading.",
+        "Proposals already store their threshold and duration separately, since",
+        "those depend on their category."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "decisionRule";
+            type: {
+              defined: "DecisionRule";
+            };
+          },
+          {
+            name: "minAverageQuoteLiquidity";
+            type: "u64";
+          },
+          {
+            name: "executionDelaySlots";
+            type: "u64";
+          },
+          {
+            name: "executionWindowSlots";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "ProposalOption";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "amm";
+            type: "publicKey";
+          },
+          {
+            name: "lpTokensLocked";
+            type: "u64";
+          },
+          {
+            name: "instructions";
+            docs: [
+              "The `ProposalInstructions` to execute if this option wins. `None` for",
+              "the status quo."
+            ];
+            type: {
+              option: "publicKey";
+            };
+          },
+          {
+            name: "proposalType";
+            docs: [
+              "What the option's instructions do, which can change the threshold that",
+              "it has to beat the status quo by. Recorded when the proposal is",
+              "created, since its instructions are frozen from then on."
+            ];
+            type: {
+              defined: "ProposalType";
+            };
+          },
+          {
+            name: "marketSnapshot";
+            docs: [
+              "Taken of the option's market when the proposal was created."
+            ];
+            type: {
+              defined: "MarketSnapshot";
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "MultiOptionDecision";
+      docs: [
+        "The inputs that `finalize_multi_option_proposal` decided a proposal with,",
+        "like `ProposalDecision`."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "optionTwaps";
+            docs: ["In the same order as the proposal's options."];
+            type: {
+              vec: "u128";
+            };
+          },
+          {
+            name: "marketsHadLiquidity";
+            docs: [
+              "If this is false, the status quo won regardless of the TWAPs."
+            ];
+            type: "bool";
+          }
+        ];
+      };
+    },
+    {
+      name: "ProposalCategory";
+      docs: [
+        "Lets a DAO give some proposals more or less scrutiny than others. For",
+        "example, a DAO could require proposals that mint its token to run longer",
+        "and clear a higher threshold than proposals that make small grants.",
+        "",
+        "An instruction is in a category if it calls `program_id` and its data",
+        "starts with the first `data_prefix_len` bytes of `data_prefix`. This can be",
+        "used to match an Anchor discriminator or an SPL token instruction tag."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "programId";
+            type: "publicKey";
+          },
+          {
+            name: "dataPrefix";
+            type: {
+              array: ["u8", 8];
+            };
+          },
+          {
+            name: "dataPrefixLen";
+            type: "u8";
+          },
+          {
+            name: "requirements";
+            type: {
+              defined: "ProposalRequirements";
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "ProposalRequirements";
+      docs: ["What a proposal needs to satisfy to be created and to pass."];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "passThresholdBps";
+            type: "u16";
+          },
+          {
+            name: "slotsPerProposal";
+            type: "u64";
+          },
+          {
+            name: "minQuoteFutarchicLiquidity";
+            type: "u64";
+          },
+          {
+            name: "minBaseFutarchicLiquidity";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "ProposalAccount";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "pubkey";
+            type: "publicKey";
+          },
+          {
+            name: "isSigner";
+            type: "bool";
+          },
+          {
+            name: "isWritable";
+            type: "bool";
+          }
+        ];
+      };
+    },
+    {
+      name: "ProposalInstruction";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "programId";
+            type: "publicKey";
+          },
+          {
+            name: "accounts";
+            type: {
+              vec: {
+                defined: "ProposalAccount";
+              };
+            };
+          },
+          {
+            name: "data";
+            type: "bytes";
+          }
+        ];
+      };
+    },
+    {
+      name: "SpotOracleSnapshot";
+      docs: [
+        "The state of the DAO's spot market's oracle when a proposal was created, so",
+        "that its TWAP over the proposal can be computed when it's finalized."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "amm";
+            type: "publicKey";
+          },
+          {
+            name: "aggregator";
+            type: "u128";
+          },
+          {
+            name: "lastUpdatedSlot";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "MarketSnapshot";
+      docs: [
+        "The state of one of a proposal's markets when the proposal was created, so",
+        "that what happened in the market over the proposal can be measured when",
+        "it's finalized."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "quoteLiquidityAggregator";
+            type: "u128";
+          },
+          {
+            name: "quoteVolume";
+            type: "u128";
+          }
+        ];
+      };
+    },
+    {
+      name: "ProposalDecision";
+      docs: [
+        "The inputs that `finalize_proposal` decided a proposal with, kept so that",
+        "anyone can audit the decision from chain state. Together with the",
+        "proposal's `pass_threshold_bps`, `spot_twap` and `dao_snapshot`, these are",
+        "everything that `DaoSnapshot::proposal_passes` looked at."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "passMarketTwap";
+            type: "u128";
+          },
+          {
+            name: "failMarketTwap";
+            type: "u128";
+          },
+          {
+            name: "proposalType";
+            type: {
+              defined: "ProposalType";
+            };
+          },
+          {
+            name: "marketsHadLiquidity";
+            docs: [
+              "If this is false, the proposal failed regardless of its TWAPs."
+            ];
+            type: "bool";
+          }
+        ];
+      };
+    },
+    {
+      name: "ProposalV0";
+      docs: [
+        "The layout of proposals created before proposals were versioned, when every",
+        "proposal held exactly one instruction. These can be brought up to date",
+        "with `migrate_proposal`."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "number";
+            type: "u32";
+          },
+          {
+            name: "proposer";
+            type: "publicKey";
+          },
+          {
+            name: "descriptionUrl";
+            type: "string";
+          },
+          {
+            name: "slotEnqueued";
+            type: "u64";
+          },
+          {
+            name: "state";
+            type: {
+              defined: "ProposalState";
+            };
+          },
+          {
+            name: "instruction";
+            type: {
+              defined: "ProposalInstruction";
+            };
+          },
+          {
+            name: "passAmm";
+            type: "publicKey";
+          },
+          {
+            name: "failAmm";
+            type: "publicKey";
+          },
+          {
+            name: "baseVault";
+            type: "publicKey";
+          },
+          {
+            name: "quoteVault";
+            type: "publicKey";
+          },
+          {
+            name: "dao";
+            type: "publicKey";
+          },
+          {
+            name: "passLpTokensLocked";
+            type: "u64";
+          },
+          {
+            name: "failLpTokensLocked";
+            type: "u64";
+          },
+          {
+            name: "nonce";
+            type: "u64";
+          },
+          {
+            name: "pdaBump";
+            type: "u8";
+          },
+          {
+            name: "question";
+            type: "publicKey";
+          }
+        ];
+      };
+    },
+    {
+      name: "SpendingLimit";
+      docs: [
+        "Caps how much of one mint the treasury can send out through proposals.",
+        "Outflows are measured as the decrease in the balances of the treasury's",
+        "token accounts for this mint across each executed instruction.",
+        "",
+        "Proposals snapshot the DAO's limits when they're created and are checked",
+        "against those, so a proposal can't loosen its own limits by changing the",
+        "DAO's. The window is shared by every proposal, so it's always tracked on the",
+        "DAO, and the stricter of the snapshot's and the DAO's `max_per_window`",
+        "applies."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "mint";
+            type: "publicKey";
+          },
+          {
+            name: "maxPerProposal";
+            docs: ["The most that any one proposal can send out."];
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "maxPerWindow";
+            docs: [
+              "The most that all proposals combined can send out in any `window_slots`",
+              "slots."
+            ];
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "windowSlots";
+            type: "u64";
+          },
+          {
+            name: "windowOutflows";
+            docs: [
+              "The outflows in the window, split into `SPENDING_WINDOW_BUCKETS`",
+              "buckets so that it can roll. Bucket number `n` is stored at index",
+              "`n % SPENDING_WINDOW_BUCKETS`, and `latest_bucket` is the newest bucket",
+              "number. Updated by autocrat as proposals are executed."
+            ];
+            type: {
+              array: ["u64", 8];
+            };
+          },
+          {
+            name: "latestBucket";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "TreasuryOutflow";
+      docs: [
+        "How much of a mint a proposal has sent out of the treasury so far."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "mint";
+            type: "publicKey";
+          },
+          {
+            name: "amount";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "DecisionRule";
+      docs: [
+        "How a DAO decides whether a proposal passed, given the TWAPs of its pass",
+        "and fail markets."
+      ];
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Relative";
+          },
+          {
+            name: "DefaultPass";
+            fields: [
+              {
+                name: "failThresholdBps";
+                type: "u16";
+              }
+            ];
+          },
+          {
+            name: "Absolute";
+            fields: [
+              {
+                name: "threshold";
+                type: "u128";
+              }
+            ];
+          },
+          {
+            name: "RelativeAboveSpot";
+          },
+          {
+            name: "ByProposalType";
+            fields: [
+              {
+                name: "parameterChangeThresholdBps";
+                type: "u16";
+              },
+              {
+                name: "treasurySpendThresholdBps";
+                type: "u16";
+              },
+              {
+                name: "securityChangeThresholdBps";
+                type: "u16";
+              }
+            ];
+          }
+        ];
+      };
+    },
+    {
+      name: "ProposalType";
+      docs: [
+        "What a proposal's instructions do, which decides the threshold that it",
+        "needs under `DecisionRule::ByProposalType`."
+      ];
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Standard";
+          },
+          {
+            name: "ParameterChange";
+          },
+          {
+            name: "TreasurySpend";
+          },
+          {
+            name: "SecurityChange";
+          }
+        ];
+      };
+    },
+    {
+      name: "ProposalSetupStage";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "CreatePassAmm";
+          },
+          {
+            name: "CreateFailAmm";
+          },
+          {
+            name: "Complete";
+          }
+        ];
+      };
+    },
+    {
+      name: "ProposalState";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Pending";
+          },
+          {
+            name: "Passed";
+          },
+          {
+            name: "Failed";
+          },
+          {
+            name: "Executed";
+          },
+          {
+            name: "Cancelled";
+          },
+          {
+            name: "Vetoed";
+          },
+          {
+            name: "Expired";
+          }
+        ];
+      };
+    }
+  ];
+  events: [
+    {
+      name: "InitializeDaoEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "dao";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "tokenMint";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "usdcMint";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "treasury";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "config";
+          type: {
+            defined: "DaoConfig";
+          };
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "UpdateDaoEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "dao";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "config";
+          type: {
+            defined: "DaoConfig";
+          };
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "InitializeProposalEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "proposal";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "dao";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "proposer";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "number";
+          type: "u32";
+          index: false;
+        },
+        {
+          name: "descriptionUrl";
+          type: "string";
+          index: false;
+        },
+        {
+          name: "instructions";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "question";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "baseVault";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "quoteVault";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "passAmm";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "failAmm";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "passLpTokensLocked";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "failLpTokensLocked";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "passThresholdBps";
+          type: "u16";
+          index: false;
+        },
+        {
+          name: "slotsPerProposal";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "nonce";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "FinalizeProposalEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "proposal";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "dao";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "passMarketTwap";
+          type: "u128";
+          index: false;
+        },
+        {
+          name: "failMarketTwap";
+          type: "u128";
+          index: false;
+        },
+        {
+          name: "passThresholdBps";
+          type: "u16";
+          index: false;
+        },
+        {
+          name: "decisionRule";
+          type: {
+            defined: "DecisionRule";
+          };
+          index: false;
+        },
+        {
+          name: "spotTwap";
+          type: {
+            option: "u128";
+          };
+          index: false;
+        },
+        {
+          name: "marketsHadLiquidity";
+          type: "bool";
+          index: false;
+        },
+        {
+          name: "state";
+          type: {
+            defined: "ProposalState";
+          };
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "InitializeMultiOptionProposalEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "proposal";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "dao";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "proposer";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "number";
+          type: "u32";
+          index: false;
+        },
+        {
+          name: "descriptionUrl";
+          type: "string";
+          index: false;
+        },
+        {
+          name: "question";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "baseVault";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "quoteVault";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "options";
+          type: {
+            vec: {
+              defined: "ProposalOption";
+            };
+          };
+          index: false;
+        },
+        {
+          name: "passThresholdBps";
+          type: "u16";
+          index: false;
+        },
+        {
+          name: "slotsPerProposal";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "nonce";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "FinalizeMultiOptionProposalEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "proposal";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "dao";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "optionTwaps";
+          type: {
+            vec: "u128";
+          };
+          index: false;
+        },
+        {
+          name: "passThresholdBps";
+          type: "u16";
+          index: false;
+        },
+        {
+          name: "decisionRule";
+          type: {
+            defined: "DecisionRule";
+          };
+          index: false;
+        },
+        {
+          name: "spotTwap";
+          type: {
+            option: "u128";
+          };
+          index: false;
+        },
+        {
+          name: "marketsHadLiquidity";
+          type: "bool";
+          index: false;
+        },
+        {
+          name: "winningOption";
+          type: "u8";
+          index: false;
+        },
+        {
+          name: "state";
+          type: {
+            defined: "ProposalState";
+          };
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "SeedProposalMarketEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "proposal";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "dao";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "amm";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "lpTokens";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "SettleTreasuryPositionsEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "proposal";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "dao";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "amm";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "lpTokensBurned";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "SettleProposalBondEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "proposal";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "dao";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "proposer";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "bondMint";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "amount";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "refunded";
+          type: "bool";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "ExecuteProposalEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "proposal";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "dao";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "instructionIndex";
+          type: {
+            option: "u16";
+          };
+          index: false;
+        },
+        {
+          name: "state";
+          type: {
+            defined: "ProposalState";
+          };
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "CancelProposalEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "proposal";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "dao";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "proposer";
+          type: "publicKey";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "VetoProposalEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "proposal";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "dao";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "vetoAuthority";
+          type: "publicKey";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "ExpireProposalEvent";
+      fields: [
+        {
+          name: "common";
+          type: {
+            defined: "CommonFields";
+          };
+          index: false;
+        },
+        {
+          name: "proposal";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "dao";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "numInstructionsExecuted";
+          type: "u16";
+          index: false;
+        }
+      ];
+    }
+  ];
+  errors: [
+    {
+      code: 6000;
+      name: "AmmTooOld";
+      msg: "Amms must have been created within 5 minutes (counted in slots) of proposal initialization";
+    },
+    {
+      code: 6001;
+      name: "InvalidInitialObservation";
+      msg: "An amm has an `initial_observation` that doesn't match the `dao`'s config";
+    },
+    {
+      code: 6002;
+      name: "InvalidMaxObservationChange";
+      msg: "An amm has a `max_observation_change_per_update` that doesn't match the `dao`'s config";
+    },
+    {
+      code: 6003;
+      name: "InvalidSettlementAuthority";
+      msg: "One of the vaults has an invalid `settlement_authority`";
+    },
+    {
+      code: 6004;
+      name: "ProposalTooYoung";
+      msg: "Proposal is too young to be executed or rejected";
+    },
+    {
+      code: 6005;
+      name: "MarketsTooYoung";
+      msg: "Markets too young for proposal to be finalized. TWAP might need to be cranked";
+    },
+    {
+      code: 6006;
+      name: "ProposalAlreadyFinalized";
+      msg: "This proposal has already been finalized";
+    },
+    {
+      code: 6007;
+      name: "InvalidVaultNonce";
+      msg: "A conditional vault has an invalid nonce. A nonce should encode the proposal number";
+    },
+    {
+      code: 6008;
+      name: "ProposalNotPassed";
+      msg: "This proposal can't be executed because it isn't in the passed state";
+    },
+    {
+      code: 6009;
+      name: "InsufficientLpTokenBalance";
+      msg: "The proposer has fewer pass or fail LP tokens than they requested to lock";
+    },
+    {
+      code: 6010;
+      name: "InsufficientLpTokenLock";
+      msg: "The LP tokens passed in have less liquidity than the proposal's `min_quote_futarchic_liquidity` or `min_base_futachic_liquidity`";
+    },
+    {
+      code: 6011;
+      name: "ProposalInstructionsFrozen";
+      msg: "This `ProposalInstructions` account has already been used by a proposal and can't be changed";
+    },
+    {
+      code: 6012;
+      name: "CancellationWindowElapsed";
+      msg: "Proposals can only be cancelled within `PROPOSAL_CANCELLATION_WINDOW_SLOTS` of being initialized";
+    },
+    {
+      code: 6013;
+      name: "ExecutionDelayNotElapsed";
+      msg: "This proposal can't be executed until `execution_delay_slots` after it was finalized";
+    },
+    {
+      code: 6014;
+      name: "InvalidVetoAuthority";
+      msg: "The signer isn't this DAO's `veto_authority`";
+    },
+    {
+      code: 6015;
+      name: "ExecutionWindowElapsed";
+      msg: "This proposal can't be executed because its `execution_window_slots` have passed";
+    },
+    {
+      code: 6016;
+      name: "ExecutionWindowNotElapsed";
+      msg: "This proposal can still be executed, so it can't be marked as expired";
+    },
+    {
+      code: 6017;
+      name: "InvalidNumOptions";
+      msg: "Multi-option proposals need between 2 and `MAX_PROPOSAL_OPTIONS` options, one for each of the question's outcomes";
+    },
+    {
+      code: 6018;
+      name: "InvalidOptionAccounts";
+      msg: "The remaining accounts don't match the proposal's options";
+    },
+    {
+      code: 6019;
+      name: "TooManyProposalCategories";
+      msg: "This DAO already has `MAX_PROPOSAL_CATEGORIES` proposal categories";
+    },
+    {
+      code: 6020;
+      name: "InvalidDataPrefixLen";
+      msg: "A proposal category's `data_prefix_len` can be at most 8";
+    },
+    {
+      code: 6021;
+      name: "InvalidBondMint";
+      msg: "A proposal bond has to be in the DAO's token or USDC";
+    },
+    {
+      code: 6022;
+      name: "MissingBondAccounts";
+      msg: "This DAO has a proposal bond, so the bond accounts need to be passed in";
+    },
+    {
+      code: 6023;
+      name: "NoBondToSettle";
+      msg: "This proposal has no bond, or its bond has already been settled";
+    },
+    {
+      code: 6024;
+      name: "ProposalNotFinalized";
+      msg: "A proposal's bond can only be settled once the proposal is no longer pending";
+    },
+    {
+      code: 6025;
+      name: "TooManyActiveProposals";
+      msg: "The DAO or this proposer already has the maximum number of active proposals";
+    },
+    {
+      code: 6026;
+      name: "ProposalSetupComplete";
+      msg: "This proposal setup has already created both markets";
+    },
+    {
+      code: 6027;
+      name: "InvalidSetupMint";
+      msg: "A market's mints don't match the conditional tokens of the proposal's vaults";
+    },
+    {
+      code: 6028;
+      name: "NonCanonicalQuestion";
+      msg: "A proposal's question must use the proposal's address as its ID and have the canonical number of outcomes";
+    },
+    {
+      code: 6029;
+      name: "MissingSpotAmm";
+      msg: "This DAO has a spot market, which needs to be passed in";
+    },
+    {
+      code: 6030;
+      name: "InvalidSpotAmm";
+      msg: "This isn't the DAO's spot market";
+    },
+    {
+      code: 6031;
+      name: "TreasuryLiquidityDisabled";
+      msg: "This DAO doesn't seed proposal markets from its treasury";
+    },
+    {
+      code: 6032;
+      name: "MarketAlreadySeeded";
+      msg: "The treasury has already seeded this market";
+    },
+    {
+      code: 6033;
+      name: "InvalidSeedMarket";
+      msg: "This market isn't one of the proposal's";
+    },
+    {
+      code: 6034;
+      name: "MarketPriceOutOfRange";
+      msg: "The market's price is too far from its TWAP oracle's last observation to seed it";
+    },
+    {
+      code: 6035;
+      name: "NoTreasuryPositionToSettle";
+      msg: "The treasury has no liquidity in this market to settle";
+    },
+    {
+      code: 6036;
+      name: "ProposalAlreadyMigrated";
+      msg: "This proposal is already in the current layout";
+    },
+    {
+      code: 6037;
+      name: "InvalidThresholdBps";
+      msg: "Thresholds in basis points can't be more than 10,000";
+    },
+    {
+      code: 6038;
+      name: "InvalidSlotsPerProposal";
+      msg: "Proposals need to last at least a day's worth of slots";
+    },
+    {
+      code: 6039;
+      name: "InvalidTwapInitialObservation";
+      msg: "The TWAP's initial observation can't be 0";
+    },
+    {
+      code: 6040;
+      name: "InvalidMaxObservationChangePerUpdate";
+      msg: "The TWAP's max observation change per update can't be 0, or the TWAP could never move";
+    },
+    {
+      code: 6041;
+      name: "InvalidExecutionWindow";
+      msg: "The execution window can't be 0, or passed proposals could never be executed";
+    },
+    {
+      code: 6042;
+      name: "InvalidMaxActiveProposals";
+      msg: "A cap on active proposals can't be 0, or no proposals could be created";
+    },
+    {
+      code: 6043;
+      name: "InvalidTreasuryLiquidity";
+      msg: "The treasury needs to add a non-zero amount of both tokens";
+    },
+    {
+      code: 6044;
+      name: "InvalidSpendingLimit";
+      msg: "A spending limit needs a non-zero window";
+    },
+    {
+      code: 6045;
+      name: "TooManySpendingLimits";
+      msg: "This DAO already has the maximum number of spending limits";
+    },
+    {
+      code: 6046;
+      name: "SpendingLimitExceeded";
+      msg: "This instruction would send more out of the treasury than the DAO's spending limits allow";
+    },
+    {
+      code: 6047;
+      name: "TreasuryTokenAccountAuthorityChanged";
+      msg: "Proposals can't change the owner or delegate of the treasury's limited token accounts";
+    },
+    {
+      code: 6048;
+      name: "DaoAlreadyMigrated";
+      msg: "This DAO is already in the current layout";
+    },
+    {
+      code: 6049;
+      name: "UnsupportedByMultiOptionProposals";
+      msg: "Multi-option proposals don't support proposal bonds or treasury liquidity";
+    },
+    {
+      code: 6050;
+      name: "ProposalSetupIncomplete";
+      msg: "A proposal can only be initialized once its setup has created both markets";
+    },
+    {
+      code: 6051;
+      name: "InvalidAbsoluteThreshold";
+      msg: "An absolute threshold can be at most the DAO's initial TWAP observation";
+    },
+    {
+      code: 6052;
+      name: "SpotAmmRequired";
+      msg: "RelativeAboveSpot needs the DAO to have a spot market";
+    },
+    {
+      code: 6053;
+      name: "CannotMigratePendingProposal";
+      msg: "Only proposals that have already been finalized can be migrated";
+    }
+  ];
+};
+
+export const IDL: Autocrat = {
+  version: "0.4.0",
+  name: "autocrat",
+  instructions: [
+    {
+      name: "initializeDao",
+      accounts: [
+        {
+          name: "dao",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "usdcMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "InitializeDaoParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "initializeProposalInstructions",
+      accounts: [
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "proposalInstructions",
+          isMut: true,
+          isSigner: true,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "addProposalInstruction",
+      accounts: [
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "proposalInstructions",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "instruction",
+          type: {
+            defined: "ProposalInstruction",
+          },
+        },
+      ],
+    },
+    {
+      name: "initializeProposalSetup",
+      accounts: [
+        {
+          name: "proposalSetup",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "dao",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "proposer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "question",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "baseVault",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "quoteVault",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "usdcMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "baseVaultUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "quoteVaultUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vaultEventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "associatedTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "nonce",
+          type: "u64",
+        },
+      ],
+    },
+    {
+      name: "setupProposalAmm",
+      accounts: [
+        {
+          name: "proposalSetup",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "proposer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "dao",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "baseVault",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "quoteVault",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "amm",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "lpMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "baseMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "quoteMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vaultAtaBase",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultAtaQuote",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userLpAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userBaseAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userQuoteAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasuryLpAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "ammProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "ammEventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "associatedTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "SetupProposalAmmParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "initializeProposal",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "dao",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "proposerRecord",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "proposalInstructions",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "question",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "quoteVault",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "baseVault",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "passAmm",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "passLpMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "failLpMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "failAmm",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "proposalSetup",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "Consumed here, so that the proposal can only use the accounts that",
+            "autocrat created for it.",
+          ],
+        },
+        {
+          name: "passLpUserAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "failLpUserAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "passLpVaultAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "failLpVaultAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "proposer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "bondMint",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+          docs: [
+            "The bond accounts are only needed if the DAO has a `proposal_bond`.",
+          ],
+        },
+        {
+          name: "bondUserAccount",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "bondEscrow",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "spotAmm",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+          docs: [
+            "Must be the DAO's `spot_amm` if it has one, and omitted otherwise.",
+          ],
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "InitializeProposalParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "seedProposalMarket",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "dao",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "question",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "baseVault",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "quoteVault",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "baseVaultUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "quoteVaultUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasuryBaseAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasuryQuoteAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "amm",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "lpMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "ammVaultAtaBase",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "ammVaultAtaQuote",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasuryLpAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vaultEventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "ammProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "ammEventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "settleTreasuryPositions",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "dao",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "question",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "baseVault",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "quoteVault",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "baseVaultUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "quoteVaultUnderlyingTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasuryBaseAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasuryQuoteAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "amm",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "lpMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "ammVaultAtaBase",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "ammVaultAtaQuote",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasuryLpAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "vaultProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vaultEventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "ammProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "ammEventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "finalizeProposal",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "instructions",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "passAmm",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "failAmm",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "dao",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "proposerRecord",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "Created here for proposals that were made before proposer records",
+            "existed.",
+          ],
+        },
+        {
+          name: "question",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "passLpUserAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "failLpUserAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "passLpVaultAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "failLpVaultAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "spotAmm",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+          docs: ["Only needed if the proposal has a `spot_oracle_snapshot`."],
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vaultProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vaultEventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "cancelProposal",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "proposer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "passAmm",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "failAmm",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "dao",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "proposerRecord",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "Created here for proposals that were made before proposer records",
+            "existed.",
+          ],
+        },
+        {
+          name: "question",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "passLpUserAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "failLpUserAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "passLpVaultAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "failLpVaultAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vaultProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vaultEventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "executeProposal",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "instructions",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "dao",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "settleProposalBond",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "dao",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "bondEscrow",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "proposer",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "proposerBondAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "treasuryBondAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "expireProposal",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "dao",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "vetoProposal",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "dao",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vetoAuthority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "initializeMultiOptionProposal",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "dao",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "proposerRecord",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "question",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "quoteVault",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "baseVault",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "proposer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "spotAmm",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+          docs: [
+            "Must be the DAO's `spot_amm` if it has one, and omitted otherwise.",
+          ],
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "InitializeMultiOptionProposalParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "finalizeMultiOptionProposal",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "dao",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "proposerRecord",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "Created here for proposals that were made before proposer records",
+            "existed.",
+          ],
+        },
+        {
+          name: "question",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vaultProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vaultEventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "spotAmm",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+          docs: ["Only needed if the proposal has a `spot_oracle_snapshot`."],
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "cancelMultiOptionProposal",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "proposer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "dao",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "proposerRecord",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "Created here for proposals that were made before proposer records",
+            "existed.",
+          ],
+        },
+        {
+          name: "question",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vaultProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vaultEventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "executeMultiOptionProposal",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "instructions",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "dao",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "expireMultiOptionProposal",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "dao",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "vetoMultiOptionProposal",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "dao",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "vetoAuthority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "eventAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "migrateProposal",
+      accounts: [
+        {
+          name: "proposal",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "dao",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "proposalInstructions",
+          isMut: true,
+          isSigner: true,
+          docs: [
+            "Receives the proposal's instruction, which older proposals stored inline.",
+          ],
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+          docs: [
+            "Pays for the proposal's account to grow to the current layout.",
+          ],
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "migrateDao",
+      accounts: [
+        {
+          name: "dao",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+          docs: ["Pays for the DAO's account to grow to the current layout."],
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "updateDao",
+      accounts: [
+        {
+          name: "dao",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: false,
+          isSigner: true,
+        },
+      ],
+      args: [
+        {
+          name: "daoParams",
+          type: {
+            defined: "UpdateDaoParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "addProposalCategory",
+      accounts: [
+        {
+          name: "dao",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: false,
+          isSigner: true,
+        },
+      ],
+      args: [
+        {
+          name: "category",
+          type: {
+            defined: "ProposalCategory",
+          },
+        },
+      ],
+    },
+    {
+      name: "removeProposalCategory",
+      accounts: [
+        {
+          name: "dao",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: false,
+          isSigner: true,
+        },
+      ],
+      args: [
+        {
+          name: "programId",
+          type: "publicKey",
+        },
+        {
+          name: "dataPrefix",
+          type: "bytes",
+        },
+      ],
+    },
+    {
+      name: "setSpendingLimit",
+      accounts: [
+        {
+          name: "dao",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: false,
+          isSigner: true,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "SetSpendingLimitParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "removeSpendingLimit",
+      accounts: [
+        {
+          name: "dao",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: false,
+          isSigner: true,
+        },
+      ],
+      args: [
+        {
+          name: "mint",
+          type: "publicKey",
+        },
+      ],
+    },
+  ],
+  accounts: [
+    {
+      name: "dao",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "treasuryPdaBump",
+            type: "u8",
+          },
+          {
+            name: "treasury",
+            type: "publicKey",
+          },
+          {
+            name: "tokenMint",
+            type: "publicKey",
+          },
+          {
+            name: "usdcMint",
+            type: "publicKey",
+          },
+          {
+            name: "proposalCount",
+            type: "u32",
+          },
+          {
+            name: "passThresholdBps",
+            type: "u16",
+          },
+          {
+            name: "slotsPerProposal",
+            type: "u64",
+          },
+          {
+            name: "twapInitialObservation",
+            docs: [
+              "For manipulation-resistance the TWAP is a time-weighted average observation,",
+              "where observation tries to approximate price but can only move by",
+              "`twap_max_observation_change_per_update` per update. Because it can only move",
+              "a little bit per update, you need to check that it has a good initial observation.",
+              "Otherwise, an attacker could create a very high initial observation in the pass",
+              "market and a very low one in the fail market to force the proposal to pass.",
+              "",
+              "We recommend setting an initial observation around the spot price of the token,",
+              "and max observation change per update around 2% the spot price of the token.",
+              "For example, if the spot price of META is $400, we'd recommend setting an initial",
+              "observation of 400 (converted into the AMM prices) and a max observation change per",
+              "update of 8 (also converted into the AMM prices). Observations can be updated once",
+              "a minute, so 2% allows the proposal market to reach double the spot price or 0",
+              "in 50 minutes.",
+            ],
+            type: "u128",
+          },
+          {
+            name: "twapMaxObservationChangePerUpdate",
+            type: "u128",
+          },
+          {
+            name: "minQuoteFutarchicLiquidity",
+            docs: [
+              "As an anti-spam measure and to help liquidity, you need to lock up some liquidity",
+              "in both futarchic markets in order to create a proposal.",
+              "",
+              "For example, for META, we can use a `min_quote_futarchic_liquidity` of",
+              "5000 * 1_000_000 (5000 USDC) and a `min_base_futarchic_liquidity` of",
+              "10 * 1_000_000_000 (10 META).",
+            ],
+            type: "u64",
+          },
+          {
+            name: "minBaseFutarchicLiquidity",
+            type: "u64",
+          },
+          {
+            name: "minAverageQuoteLiquidity",
+            docs: [
+              "Locking liquidity only proves that a market had liquidity when the proposal",
+              "was created. To pass, both markets also need to have had a time-weighted",
+              "average quote reserve of at least this much. 0 disables the check.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "executionDelaySlots",
+            docs: [
+              "How long a passed proposal has to wait before it can be executed. This",
+              "gives the `veto_authority` time to step in if the markets got it wrong.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "vetoAuthority",
+            docs: [
+              "Can veto passed proposals before they're executed, as a last line of",
+              "defense against market manipulation. This could be a multisig or the",
+              "treasury of another futarchy.",
+            ],
+            type: {
+              option: "publicKey",
+            },
+          },
+          {
+            name: "executionWindowSlots",
+            docs: [
+              "How long a passed proposal can be executed for once its execution delay",
+              "is over. After that, its instructions are considered stale and anyone can",
+              "mark it as expired.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "decisionRule",
+            type: {
+              defined: "DecisionRule",
+            },
+          },
+          {
+            name: "proposalCategories",
+            docs: [
+              "At most `MAX_PROPOSAL_CATEGORIES`. Instructions that aren't in any",
+              "category use the DAO's own threshold, duration and liquidity.",
+            ],
+            type: {
+              vec: {
+                defined: "ProposalCategory",
+              },
+            },
+          },
+          {
+            name: "proposalBond",
+            docs: [
+              "Multi-option proposals can't be bonded, so DAOs that set this can only",
+              "create regular proposals.",
+            ],
+            type: {
+              option: {
+                defined: "ProposalBond",
+              },
+            },
+          },
+          {
+            name: "activeProposalCount",
+            docs: [
+              "The number of proposals, of either kind, that haven't been finalized or",
+              "cancelled yet.",
+            ],
+            type: "u32",
+          },
+          {
+            name: "maxActiveProposals",
+            docs: [
+              "Caps on active proposals, so that the DAO's attention, and its traders'",
+              "capital, isn't spread across too many markets at once. `None` means",
+              "no cap.",
+            ],
+            type: {
+              option: "u32",
+            },
+          },
+          {
+            name: "maxActiveProposalsPerProposer",
+            type: {
+              option: "u32",
+            },
+          },
+          {
+            name: "spotAmm",
+            docs: [
+              "A market for the DAO's token against USDC, such as a META/USDC `Amm`,",
+              "whose TWAP over each proposal is recorded when it's finalized. If this",
+              "is set, proposals need to pass it in when they're created and finalized.",
+            ],
+            type: {
+              option: "publicKey",
+            },
+          },
+          {
+            name: "treasuryLiquidity",
+            docs: [
+              "If set, anyone can make the treasury seed each market of a regular",
+              "proposal with this much liquidity through `seed_proposal_market`.",
+            ],
+            type: {
+              option: {
+                defined: "TreasuryLiquidity",
+              },
+            },
+          },
+          {
+            name: "spendingLimits",
+            docs: ["At most `MAX_SPENDING_LIMITS`, at most one per mint."],
+            type: {
+              vec: {
+                defined: "SpendingLimit",
+              },
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "multiOptionProposal",
+      docs: [
+        'A proposal with more than two outcomes, such as "which of these three',
+        'vendors should we hire?". Each outcome of the question has its own market',
+        "and its own instructions, and the option whose market has the highest TWAP",
+        "wins, as long as it beats the status quo under the DAO's decision rule.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "number",
+            type: "u32",
+          },
+          {
+            name: "proposer",
+            type: "publicKey",
+          },
+          {
+            name: "descriptionUrl",
+            type: "string",
+          },
+          {
+            name: "slotEnqueued",
+            type: "u64",
+          },
+          {
+            name: "slotFinalized",
+            docs: ["0 until the proposal is finalized."],
+            type: "u64",
+          },
+          {
+            name: "state",
+            docs: ["`Passed` if an option other than the status quo won."],
+            type: {
+              defined: "ProposalState",
+            },
+          },
+          {
+            name: "options",
+            docs: [
+              "In the same order as the question's outcomes. Option 0 is the status quo,",
+              "which wins if no other option beats it.",
+            ],
+            type: {
+              vec: {
+                defined: "ProposalOption",
+              },
+            },
+          },
+          {
+            name: "winningOption",
+            docs: ["`None` until the proposal is finalized."],
+            type: {
+              option: "u8",
+            },
+          },
+          {
+            name: "numInstructionsExecuted",
+            type: "u16",
+          },
+          {
+            name: "passThresholdBps",
+            docs: [
+              "The strictest requirements of any of the options' categories.",
+            ],
+            type: "u16",
+          },
+          {
+            name: "slotsPerProposal",
+            type: "u64",
+          },
+          {
+            name: "baseVault",
+            type: "publicKey",
+          },
+          {
+            name: "quoteVault",
+            type: "publicKey",
+          },
+          {
+            name: "dao",
+            type: "publicKey",
+          },
+          {
+            name: "question",
+            type: "publicKey",
+          },
+          {
+            name: "nonce",
+            type: "u64",
+          },
+          {
+            name: "pdaBump",
+            type: "u8",
+          },
+          {
+            name: "daoSnapshot",
+            type: {
+              defined: "DaoSnapshot",
+            },
+          },
+          {
+            name: "spotOracleSnapshot",
+            docs: [
+              "Taken if the DAO had a `spot_amm` when this proposal was created.",
+            ],
+            type: {
+              option: {
+                defined: "SpotOracleSnapshot",
+              },
+            },
+          },
+          {
+            name: "spotTwap",
+            docs: [
+              "The spot market's TWAP over this proposal, recorded when it's finalized.",
+            ],
+            type: {
+              option: "u128",
+            },
+          },
+          {
+            name: "spendingLimits",
+            docs: [
+              "The DAO's capped `spending_limits` when this proposal was created,",
+              "which its instructions are checked against.",
+            ],
+            type: {
+              vec: {
+                defined: "SpendingLimit",
+              },
+            },
+          },
+          {
+            name: "treasuryOutflows",
+            docs: [
+              "How much of each limited mint this proposal has sent out of the",
+              "treasury. See `SpendingLimit`.",
+            ],
+            type: {
+              vec: {
+                defined: "TreasuryOutflow",
+              },
+            },
+          },
+          {
+            name: "decision",
+            docs: [
+              "Set when the proposal is finalized, unless it was cancelled.",
+            ],
+            type: {
+              option: {
+                defined: "MultiOptionDecision",
+              },
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "proposalInstructions",
+      docs: [
+        "The instructions of a proposal, stored separately so that a proposer can",
+        "build up a payload that's too big for one transaction. The proposer allocates",
+        "this account with whatever space they need, appends instructions to it with",
+        "`add_proposal_instruction`, and then passes it into `initialize_proposal`,",
+        "which freezes it.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "authority",
+            type: "publicKey",
+          },
+          {
+            name: "frozen",
+            type: "bool",
+          },
+          {
+            name: "instructions",
+            type: {
+              vec: {
+                defined: "ProposalInstruction",
+              },
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "proposalSetup",
+      docs: [
+        "Tracks a proposer's progress through creating the accounts that a proposal",
+        "needs, since they don't all fit in one transaction. Only the proposer can",
+        "advance it, and every account it creates is a canonical PDA derived from the",
+        "proposal's address, so nobody can swap in their own question, vaults or",
+        "markets halfway through.",
+        "",
+        "1. `initialize_proposal_setup` creates this account, the question and both",
+        "conditional vaults.",
+        "2. `setup_proposal_amm` creates the pass market and adds the proposer's",
+        "liquidity to it.",
+        "3. `setup_proposal_amm` does the same for the fail market.",
+        "",
+        "After that, the proposer can call `initialize_proposal` with the same nonce,",
+        "which checks the proposal's accounts against this one and closes it.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "proposer",
+            type: "publicKey",
+          },
+          {
+            name: "dao",
+            type: "publicKey",
+          },
+          {
+            name: "nonce",
+            type: "u64",
+          },
+          {
+            name: "proposal",
+            docs: [
+              "The address that the proposal will have once it's initialized.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "stage",
+            type: {
+              defined: "ProposalSetupStage",
+            },
+          },
+          {
+            name: "question",
+            type: "publicKey",
+          },
+          {
+            name: "baseVault",
+            type: "publicKey",
+          },
+          {
+            name: "quoteVault",
+            type: "publicKey",
+          },
+          {
+            name: "passAmm",
+            type: "publicKey",
+          },
+          {
+            name: "failAmm",
+            type: "publicKey",
+          },
+          {
+            name: "pdaBump",
+            type: "u8",
+          },
+        ],
+      },
+    },
+    {
+      name: "proposal",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "version",
+            docs: [
+              "`PROPOSAL_VERSION` for proposals created or migrated by this version of",
+              "autocrat. See `migrate_proposal`. This is always the first byte after",
+              "the discriminator, so clients can filter on it.",
+            ],
+            type: "u8",
+          },
+          {
+            name: "number",
+            type: "u32",
+          },
+          {
+            name: "proposer",
+            type: "publicKey",
+          },
+          {
+            name: "descriptionUrl",
+            type: "string",
+          },
+          {
+            name: "slotEnqueued",
+            type: "u64",
+          },
+          {
+            name: "slotFinalized",
+            docs: ["0 until the proposal is finalized."],
+            type: "u64",
+          },
+          {
+            name: "state",
+            type: {
+              defined: "ProposalState",
+            },
+          },
+          {
+            name: "instructions",
+            docs: [
+              "The `ProposalInstructions` account holding the instructions to execute,",
+              "in order, if this proposal passes.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "numInstructionsExecuted",
+            type: "u16",
+          },
+          {
+            name: "passThresholdBps",
+            docs: [
+              "Taken from the proposal's category when it's initialized, so that",
+              "later changes to the DAO don't affect it.",
+            ],
+            type: "u16",
+          },
+          {
+            name: "slotsPerProposal",
+            type: "u64",
+          },
+          {
+            name: "passAmm",
+            type: "publicKey",
+          },
+          {
+            name: "failAmm",
+            type: "publicKey",
+          },
+          {
+            name: "baseVault",
+            type: "publicKey",
+          },
+          {
+            name: "quoteVault",
+            type: "publicKey",
+          },
+          {
+            name: "dao",
+            type: "publicKey",
+          },
+          {
+            name: "passLpTokensLocked",
+            type: "u64",
+          },
+          {
+            name: "failLpTokensLocked",
+            type: "u64",
+          },
+          {
+            name: "nonce",
+            docs: [
+              "We need to include a per-proposer nonce to prevent some weird proposal",
+              "front-running edge cases. Using a `u64` means that proposers are unlikely",
+              "to run into collisions, even if they generate nonces randomly - I've run",
+              "the math :D",
+            ],
+            type: "u64",
+          },
+          {
+            name: "pdaBump",
+            type: "u8",
+          },
+          {
+            name: "question",
+            type: "publicKey",
+          },
+          {
+            name: "bond",
+            docs: [
+              "The DAO's bond when this proposal was created, if it had one. The bond",
+              'is held in a token account with the seeds `[b"bond", proposal]` until',
+              "it's settled with `settle_proposal_bond`.",
+            ],
+            type: {
+              option: {
+                defined: "ProposalBond",
+              },
+            },
+          },
+          {
+            name: "bondSettled",
+            type: "bool",
+          },
+          {
+            name: "spotOracleSnapshot",
+            docs: [
+              "Taken if the DAO had a `spot_amm` when this proposal was created.",
+            ],
+            type: {
+              option: {
+                defined: "SpotOracleSnapshot",
+              },
+            },
+          },
+          {
+            name: "spotTwap",
+            docs: [
+              "The spot market's TWAP over this proposal, recorded when it's finalized.",
+            ],
+            type: {
+              option: "u128",
+            },
+          },
+          {
+            name: "marketSnapshots",
+            docs: [
+              "Taken of each market when this proposal was created, indexed by outcome.",
+            ],
+            type: {
+              array: [
+                {
+                  defined: "MarketSnapshot",
+                },
+                2,
+              ],
+            },
+          },
+          {
+            name: "quoteVolumes",
+            docs: [
+              "How much quote was swapped in each market over this proposal, indexed",
+              "by outcome. Recorded when the proposal is finalized or cancelled, so",
+              "that the bond can be settled without trusting the markets afterwards.",
+            ],
+            type: {
+              array: ["u128", 2],
+            },
+          },
+          {
+            name: "treasuryLpTokens",
+            docs: [
+              "The LP tokens that the treasury got for seeding each market, indexed by",
+              "outcome. These are held in the same accounts as the proposer's locked LP.",
+              "Set back to 0 once `settle_treasury_positions` has removed them.",
+            ],
+            type: {
+              array: ["u64", 2],
+            },
+          },
+          {
+            name: "treasuryMaxPriceDeviationBps",
+            docs: [
+              "The DAO's `max_price_deviation_bps` when the treasury last seeded one of",
+              "this proposal's markets, which also bounds the price that",
+              "`settle_treasury_positions` removes the treasury's liquidity at.",
+            ],
+            type: "u16",
+          },
+          {
+            name: "decision",
+            docs: [
+              "Set when the proposal is finalized, unless it was cancelled.",
+            ],
+            type: {
+              option: {
+                defined: "ProposalDecision",
+              },
+            },
+          },
+          {
+            name: "daoSnapshot",
+            type: {
+              defined: "DaoSnapshot",
+            },
+          },
+          {
+            name: "spendingLimits",
+            docs: [
+              "The DAO's capped `spending_limits` when this proposal was created,",
+              "which its instructions are checked against.",
+            ],
+            type: {
+              vec: {
+                defined: "SpendingLimit",
+              },
+            },
+          },
+          {
+            name: "treasuryOutflows",
+            docs: [
+              "How much of each limited mint this proposal has sent out of the",
+              "treasury. See `SpendingLimit`.",
+            ],
+            type: {
+              vec: {
+                defined: "TreasuryOutflow",
+              },
+            },
+          },
+        ],
+      },
+    },
     {
-      name: "dao";
+      name: "proposerRecord",
+      docs: [
+        "Tracks how many proposals a proposer has open in a DAO, so that the DAO can",
+        "cap it with `max_active_proposals_per_proposer`.",
+      ],
       type: {
-        kind: "struct";
+        kind: "struct",
         fields: [
           {
-            name: "treasuryPdaBump";
-            type: "u8";
+            name: "dao",
+            type: "publicKey",
           },
           {
-            name: "treasury";
-            type: "publicKey";
+            name: "proposer",
+            type: "publicKey",
           },
           {
-            name: "tokenMint";
-            type: "publicKey";
+            name: "activeProposalCount",
+            type: "u32",
           },
           {
-            name: "usdcMint";
-            type: "publicKey";
+            name: "pdaBump",
+            type: "u8",
           },
+        ],
+      },
+    },
+  ],
+  types: [
+    {
+      name: "CommonFields",
+      type: {
+        kind: "struct",
+        fields: [
           {
-            name: "proposalCount";
-            type: "u32";
+            name: "slot",
+            type: "u64",
           },
           {
-            name: "passThresholdBps";
-            type: "u16";
+            name: "unixTimestamp",
+            type: "i64",
           },
+        ],
+      },
+    },
+    {
+      name: "DaoConfig",
+      docs: [
+        "The parts of a DAO's config that decide how its proposals are traded and",
+        "finalized.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
           {
-            name: "slotsPerProposal";
-            type: "u64";
+            name: "passThresholdBps",
+            type: "u16",
           },
           {
-            name: "twapInitialObservation";
-            docs: [
-              "For manipulation-resistance the TWAP is a time-weighted average observation,",
-              "where observation tries to approximate price but can only move by",
-              "`twap_max_observation_change_per_update` per update. Because it can only move",
-              "a little bit per update, you need to check that it has a good initial observation.",
-              "Otherwise, an attacker could create a very high initial observation in the pass",
-              "market and a very low one in the fail market to force the proposal to pass.",
-              "",
-              "We recommend setting an initial observation around the spot price of the token,",
-              "and max observation change per update around 2% the spot price of the token.",
-              "For example, if the spot price of META is $400, we'd recommend setting an initial",
-              "observation of 400 (converted into the AMM prices) and a max observation change per",
-              "update of 8 (also converted into the AMM prices). Observations can be updated once",
-              "a minute, so 2% allows the proposal market to reach double the spot price or 0",
-              "in 50 minutes."
-            ];
-            type: "u128";
+            name: "slotsPerProposal",
+            type: "u64",
           },
           {
-            name: "twapMaxObservationChangePerUpdate";
-            type: "u128";
+            name: "twapInitialObservation",
+            type: "u128",
           },
           {
-            name: "minQuoteFutarchicLiquidity";
-            docs: [
-              "As an anti-spam measure and to help liquidity, you need to lock up some liquidity",
-              "in both futarchic markets in order to create a proposal.",
-              "",
-              "For example, for META, we can use a `min_quote_futarchic_liquidity` of",
-              "5000 * 1_000_000 (5000 USDC) and a `min_base_futarchic_liquidity` of",
-              "10 * 1_000_000_000 (10 META)."
-            ];
-            type: "u64";
+            name: "twapMaxObservationChangePerUpdate",
+            type: "u128",
+          },
+          {
+            name: "minQuoteFutarchicLiquidity",
+            type: "u64",
+          },
+          {
+            name: "minBaseFutarchicLiquidity",
+            type: "u64",
+          },
+          {
+            name: "minAverageQuoteLiquidity",
+            type: "u64",
+          },
+          {
+            name: "executionDelaySlots",
+            type: "u64",
+          },
+          {
+            name: "vetoAuthority",
+            type: {
+              option: "publicKey",
+            },
+          },
+          {
+            name: "executionWindowSlots",
+            type: "u64",
+          },
+          {
+            name: "decisionRule",
+            type: {
+              defined: "DecisionRule",
+            },
+          },
+          {
+            name: "proposalBond",
+            type: {
+              option: {
+                defined: "ProposalBond",
+              },
+            },
+          },
+          {
+            name: "maxActiveProposals",
+            type: {
+              option: "u32",
+            },
+          },
+          {
+            name: "maxActiveProposalsPerProposer",
+            type: {
+              option: "u32",
+            },
           },
           {
-            name: "minBaseFutarchicLiquidity";
-            type: "u64";
-          }
-        ];
-      };
+            name: "spotAmm",
+            type: {
+              option: "publicKey",
+            },
+          },
+          {
+            name: "treasuryLiquidity",
+            type: {
+              option: {
+                defined: "TreasuryLiquidity",
+              },
+            },
+          },
+        ],
+      },
     },
     {
-      name: "proposal";
+      name: "InitializeDaoParams",
       type: {
-        kind: "struct";
+        kind: "struct",
         fields: [
           {
-            name: "number";
-            type: "u32";
+            name: "twapInitialObservation",
+            type: "u128",
           },
           {
-            name: "proposer";
-            type: "publicKey";
+            name: "twapMaxObservationChangePerUpdate",
+            type: "u128",
           },
           {
-            name: "descriptionUrl";
-            type: "string";
+            name: "minQuoteFutarchicLiquidity",
+            type: "u64",
           },
           {
-            name: "slotEnqueued";
-            type: "u64";
+            name: "minBaseFutarchicLiquidity",
+            type: "u64",
           },
           {
-            name: "state";
+            name: "passThresholdBps",
             type: {
-              defined: "ProposalState";
-            };
+              option: "u16",
+            },
           },
           {
-            name: "instruction";
+            name: "slotsPerProposal",
             type: {
-              defined: "ProposalInstruction";
-            };
+              option: "u64",
+            },
           },
           {
-            name: "passAmm";
-            type: "publicKey";
+            name: "minAverageQuoteLiquidity",
+            type: {
+              option: "u64",
+            },
           },
           {
-            name: "failAmm";
-            type: "publicKey";
+            name: "executionDelaySlots",
+            type: {
+              option: "u64",
+            },
           },
           {
-            name: "baseVault";
-            type: "publicKey";
+            name: "vetoAuthority",
+            type: {
+              option: "publicKey",
+            },
           },
           {
-            name: "quoteVault";
-            type: "publicKey";
+            name: "executionWindowSlots",
+            type: {
+              option: "u64",
+            },
           },
           {
-            name: "dao";
-            type: "publicKey";
+            name: "decisionRule",
+            type: {
+              option: {
+                defined: "DecisionRule",
+              },
+            },
           },
           {
-            name: "passLpTokensLocked";
-            type: "u64";
+            name: "proposalBond",
+            type: {
+              option: {
+                defined: "ProposalBond",
+              },
+            },
           },
           {
-            name: "failLpTokensLocked";
-            type: "u64";
+            name: "maxActiveProposals",
+            type: {
+              option: "u32",
+            },
           },
           {
-            name: "nonce";
-            docs: [
-              "We need to include a per-proposer nonce to prevent some weird proposal",
-              "front-running edge cases. Using a `u64` means that proposers are unlikely",
-              "to run into collisions, even if they generate nonces randomly - I've run",
-              "the math :D"
-            ];
-            type: "u64";
+            name: "maxActiveProposalsPerProposer",
+            type: {
+              option: "u32",
+            },
           },
           {
-            name: "pdaBump";
-            type: "u8";
+            name: "spotAmm",
+            type: {
+              option: "publicKey",
+            },
           },
           {
-            name: "question";
-            type: "publicKey";
-          }
-        ];
-      };
-    }
-  ];
-  types: [
+            name: "treasuryLiquidity",
+            type: {
+              option: {
+                defined: "TreasuryLiquidity",
+              },
+            },
+          },
+        ],
+      },
+    },
     {
-      name: "InitializeDaoParams";
+      name: "InitializeMultiOptionProposalParams",
       type: {
-        kind: "struct";
+        kind: "struct",
         fields: [
           {
-            name: "twapInitialObservation";
-            type: "u128";
+            name: "descriptionUrl",
+            type: "string",
           },
           {
-            name: "twapMaxObservationChangePerUpdate";
-            type: "u128";
+            name: "lpTokensToLock",
+            docs: [
+              "How many LP tokens to lock in each option's market, including the status",
+              "quo's, in outcome order.",
+            ],
+            type: {
+              vec: "u64",
+            },
           },
           {
-            name: "minQuoteFutarchicLiquidity";
-            type: "u64";
+            name: "nonce",
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "InitializeProposalParams",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "descriptionUrl",
+            type: "string",
           },
           {
-            name: "minBaseFutarchicLiquidity";
-            type: "u64";
+            name: "passLpTokensToLock",
+            type: "u64",
           },
           {
-            name: "passThresholdBps";
-            type: {
-              option: "u16";
-            };
+            name: "failLpTokensToLock",
+            type: "u64",
           },
           {
-            name: "slotsPerProposal";
-            type: {
-              option: "u64";
-            };
-          }
-        ];
-      };
+            name: "nonce",
+            type: "u64",
+          },
+        ],
+      },
     },
     {
-      name: "InitializeProposalParams";
+      name: "SetSpendingLimitParams",
       type: {
-        kind: "struct";
+        kind: "struct",
         fields: [
           {
-            name: "descriptionUrl";
-            type: "string";
+            name: "mint",
+            type: "publicKey",
           },
           {
-            name: "instruction";
+            name: "maxPerProposal",
             type: {
-              defined: "ProposalInstruction";
-            };
+              option: "u64",
+            },
           },
           {
-            name: "passLpTokensToLock";
-            type: "u64";
+            name: "maxPerWindow",
+            type: {
+              option: "u64",
+            },
           },
           {
-            name: "failLpTokensToLock";
-            type: "u64";
+            name: "windowSlots",
+            type: "u64",
           },
+        ],
+      },
+    },
+    {
+      name: "SetupProposalAmmParams",
+      type: {
+        kind: "struct",
+        fields: [
           {
-            name: "nonce";
-            type: "u64";
-          }
-        ];
-      };
+            name: "quoteAmount",
+            type: "u64",
+          },
+          {
+            name: "maxBaseAmount",
+            type: "u64",
+          },
+          {
+            name: "minLpTokens",
+            type: "u64",
+          },
+        ],
+      },
     },
     {
-      name: "UpdateDaoParams";
+      name: "UpdateDaoParams",
       type: {
-        kind: "struct";
+        kind: "struct",
         fields: [
           {
-            name: "passThresholdBps";
+            name: "passThresholdBps",
             type: {
-              option: "u16";
-            };
+              option: "u16",
+            },
           },
           {
-            name: "slotsPerProposal";
+            name: "slotsPerProposal",
             type: {
-              option: "u64";
-            };
+              option: "u64",
+            },
           },
           {
-            name: "twapInitialObservation";
+            name: "twapInitialObservation",
             type: {
-              option: "u128";
-            };
+              option: "u128",
+            },
           },
           {
-            name: "twapMaxObservationChangePerUpdate";
+            name: "twapMaxObservationChangePerUpdate",
             type: {
-              option: "u128";
-            };
+              option: "u128",
+            },
           },
           {
-            name: "minQuoteFutarchicLiquidity";
+            name: "minQuoteFutarchicLiquidity",
             type: {
-              option: "u64";
-            };
+              option: "u64",
+            },
           },
           {
-            name: "minBaseFutarchicLiquidity";
+            name: "minBaseFutarchicLiquidity",
             type: {
-              option: "u64";
-            };
-          }
-        ];
-      };
-    },
-    {
-      name: "ProposalAccount";
-      type: {
-        kind: "struct";
-        fields: [
+              option: "u64",
+            },
+          },
           {
-            name: "pubkey";
-            type: "publicKey";
+            name: "minAverageQuoteLiquidity",
+            type: {
+              option: "u64",
+            },
           },
           {
-            name: "isSigner";
-            type: "bool";
+            name: "executionDelaySlots",
+            type: {
+              option: "u64",
+            },
           },
           {
-            name: "isWritable";
-            type: "bool";
-          }
-        ];
-      };
-    },
-    {
-      name: "ProposalInstruction";
-      type: {
-        kind: "struct";
-        fields: [
+            name: "vetoAuthority",
+            docs: ["`Some(None)` removes the veto authority."],
+            type: {
+              option: {
+                option: "publicKey",
+              },
+            },
+          },
           {
-            name: "programId";
-            type: "publicKey";
+            name: "executionWindowSlots",
+            type: {
+              option: "u64",
+            },
           },
           {
-            name: "accounts";
+            name: "decisionRule",
             type: {
-              vec: {
-                defined: "ProposalAccount";
-              };
-            };
+              option: {
+                defined: "DecisionRule",
+              },
+            },
           },
           {
-            name: "data";
-            type: "bytes";
-          }
-        ];
-      };
-    },
-    {
-      name: "ProposalState";
-      type: {
-        kind: "enum";
-        variants: [
+            name: "proposalBond",
+            docs: ["`Some(None)` removes the bond."],
+            type: {
+              option: {
+                option: {
+                  defined: "ProposalBond",
+                },
+              },
+            },
+          },
           {
-            name: "Pending";
+            name: "maxActiveProposals",
+            docs: ["`Some(None)` removes the cap."],
+            type: {
+              option: {
+                option: "u32",
+              },
+            },
           },
           {
-            name: "Passed";
+            name: "maxActiveProposalsPerProposer",
+            docs: ["`Some(None)` removes the cap."],
+            type: {
+              option: {
+                option: "u32",
+              },
+            },
           },
           {
-            name: "Failed";
+            name: "spotAmm",
+            docs: ["`Some(None)` removes the spot market."],
+            type: {
+              option: {
+                option: "publicKey",
+              },
+            },
           },
           {
-            name: "Executed";
-          }
-        ];
-      };
-    }
-  ];
-  errors: [
-    {
-      code: 6000;
-      name: "AmmTooOld";
-      msg: "Amms must have been created within 5 minutes (counted in slots) of proposal initialization";
-    },
-    {
-      code: 6001;
-      name: "InvalidInitialObservation";
-      msg: "An amm has an `initial_observation` that doesn't match the `dao`'s config";
-    },
-    {
-      code: 6002;
-      name: "InvalidMaxObservationChange";
-      msg: "An amm has a `max_observation_change_per_update` that doesn't match the `dao`'s config";
-    },
-    {
-      code: 6003;
-      name: "InvalidSettlementAuthority";
-      msg: "One of the vaults has an invalid `settlement_authority`";
-    },
-    {
-      code: 6004;
-      name: "ProposalTooYoung";
-      msg: "Proposal is too young to be executed or rejected";
-    },
-    {
-      code: 6005;
-      name: "MarketsTooYoung";
-      msg: "Markets too young for proposal to be finalized. TWAP might need to be cranked";
-    },
-    {
-      code: 6006;
-      name: "ProposalAlreadyFinalized";
-      msg: "This proposal has already been finalized";
-    },
-    {
-      code: 6007;
-      name: "InvalidVaultNonce";
-      msg: "A conditional vault has an invalid nonce. A nonce should encode the proposal number";
-    },
-    {
-      code: 6008;
-      name: "ProposalNotPassed";
-      msg: "This proposal can't be executed because it isn't in the passed state";
-    },
-    {
-      code: 6009;
-      name: "InsufficientLpTokenBalance";
-      msg: "The proposer has fewer pass or fail LP tokens than they requested to lock";
-    },
-    {
-      code: 6010;
-      name: "InsufficientLpTokenLock";
-      msg: "The LP tokens passed in have less liquidity than the DAO's `min_quote_futarchic_liquidity` or `min_base_futachic_liquidity`";
-    }
-  ];
-};
-
-export const IDL: Autocrat = {
-  version: "0.4.0",
-  name: "autocrat",
-  instructions: [
-    {
-      name: "initializeDao",
-      accounts: [
-        {
-          name: "dao",
-          isMut: true,
-          isSigner: true,
-        },
-        {
-          name: "payer",
-          isMut: true,
-          isSigner: true,
-        },
-        {
-          name: "systemProgram",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "tokenMint",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "usdcMint",
-          isMut: false,
-          isSigner: false,
-        },
-      ],
-      args: [
-        {
-          name: "params",
-          type: {
-            defined: "InitializeDaoParams",
+            name: "treasuryLiquidity",
+            docs: ["`Some(None)` stops the treasury from seeding new markets."],
+            type: {
+              option: {
+                option: {
+                  defined: "TreasuryLiquidity",
+                },
+              },
+            },
           },
-        },
-      ],
+        ],
+      },
     },
     {
-      name: "initializeProposal",
-      accounts: [
-        {
-          name: "proposal",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "dao",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "question",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "quoteVault",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "baseVault",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "passAmm",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "passLpMint",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "failLpMint",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "failAmm",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "passLpUserAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "failLpUserAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "passLpVaultAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "failLpVaultAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "proposer",
-          isMut: true,
-          isSigner: true,
-        },
-        {
-          name: "tokenProgram",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "systemProgram",
-          isMut: false,
-          isSigner: false,
-        },
+      name: "ProposalBond",
+      docs: [
+        "A deposit that proposers put up when they create a proposal, so that spam",
+        "costs more than the rent and the time value of the locked LP.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "mint",
+            docs: ["Either the DAO's token or USDC."],
+            type: "publicKey",
+          },
+          {
+            name: "amount",
+            type: "u64",
+          },
+          {
+            name: "refundQuoteVolume",
+            docs: [
+              "The bond is returned if the proposal passes or if at least this much",
+              "quote was traded in each of its markets. Otherwise, it goes to the",
+              "treasury. Liquidity that the proposer or the treasury adds doesn't",
+              "count, since it isn't traded. Proposers can still trade against",
+              "themselves, but every swap pays a fee to the market's other LPs, so",
+              "this should be high enough that faking it costs more than the bond.",
+            ],
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "TreasuryLiquidity",
+      docs: [
+        "How much liquidity the treasury adds to each of a proposal's markets, so",
+        "that proposers don't need to bring all of it themselves.",
       ],
-      args: [
-        {
-          name: "params",
-          type: {
-            defined: "InitializeProposalParams",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "quoteAmount",
+            type: "u64",
           },
-        },
+          {
+            name: "maxBaseAmount",
+            docs: [
+              "The most of the DAO's token the treasury adds along with",
+              "`quote_amount`. How much is actually added depends on the market's price.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "maxPriceDeviationBps",
+            docs: [
+              "How far, in bps, a market's price can be from its TWAP oracle's last",
+              "observation for the treasury to seed it. The observation can only move",
+              "by `twap_max_observation_change_per_update` each update, so it's much",
+              "harder to push around than the price.",
+            ],
+            type: "u16",
+          },
+        ],
+      },
+    },
+    {
+      name: "DaoV0",
+      docs: [
+        "The layout of DAOs created before any of the fields after",
+        "`min_base_futarchic_liquidity` were added. These can be brought up to date",
+        "with `migrate_dao`.",
       ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "treasuryPdaBump",
+            type: "u8",
+          },
+          {
+            name: "treasury",
+            type: "publicKey",
+          },
+          {
+            name: "tokenMint",
+            type: "publicKey",
+          },
+          {
+            name: "usdcMint",
+            type: "publicKey",
+          },
+          {
+            name: "proposalCount",
+            type: "u32",
+          },
+          {
+            name: "passThresholdBps",
+            type: "u16",
+          },
+          {
+            name: "slotsPerProposal",
+            type: "u64",
+          },
+          {
+            name: "twapInitialObservation",
+            type: "u128",
+          },
+          {
+            name: "twapMaxObservationChangePerUpdate",
+            type: "u128",
+          },
+          {
+            name: "minQuoteFutarchicLiquidity",
+            type: "u64",
+          },
+          {
+            name: "minBaseFutarchicLiquidity",
+            type: "u64",
+          },
+        ],
+      },
     },
     {
-      name: "finalizeProposal",
-      accounts: [
-        {
-          name: "proposal",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "passAmm",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "failAmm",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "dao",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "question",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "treasury",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "passLpUserAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "failLpUserAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "passLpVaultAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "failLpVaultAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "tokenProgram",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "vaultProgram",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "vaultEventAuthority",
-          isMut: false,
-          isSigner: false,
-        },
+      name: "DaoSnapshot",
+      docs: [
+        "The parts of a DAO's config that decide how a proposal is finalized and",
+        "executed. Proposals copy these from their DAO when they're created, so that",
+        "`update_dao` can't change the rules for proposals that are already trading.",
+        "Proposals already store their threshold and duration separately, since",
+        "those depend on their category.",
       ],
-      args: [],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "decisionRule",
+            type: {
+              defined: "DecisionRule",
+            },
+          },
+          {
+            name: "minAverageQuoteLiquidity",
+            type: "u64",
+          },
+          {
+            name: "executionDelaySlots",
+            type: "u64",
+          },
+          {
+            name: "executionWindowSlots",
+            type: "u64",
+          },
+        ],
+      },
     },
     {
-      name: "executeProposal",
-      accounts: [
-        {
-          name: "proposal",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "dao",
-          isMut: false,
-          isSigner: false,
-        },
+      name: "ProposalOption",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "amm",
+            type: "publicKey",
+          },
+          {
+            name: "lpTokensLocked",
+            type: "u64",
+          },
+          {
+            name: "instructions",
+            docs: [
+              "The `ProposalInstructions` to execute if this option wins. `None` for",
+              "the status quo.",
+            ],
+            type: {
+              option: "publicKey",
+            },
+          },
+          {
+            name: "proposalType",
+            docs: [
+              "What the option's instructions do, which can change the threshold that",
+              "it has to beat the status quo by. Recorded when the proposal is",
+              "created, since its instructions are frozen from then on.",
+            ],
+            type: {
+              defined: "ProposalType",
+            },
+          },
+          {
+            name: "marketSnapshot",
+            docs: [
+              "Taken of the option's market when the proposal was created.",
+            ],
+            type: {
+              defined: "MarketSnapshot",
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "MultiOptionDecision",
+      docs: [
+        "The inputs that `finalize_multi_option_proposal` decided a proposal with,",
+        "like `ProposalDecision`.",
       ],
-      args: [],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "optionTwaps",
+            docs: ["In the same order as the proposal's options."],
+            type: {
+              vec: "u128",
+            },
+          },
+          {
+            name: "marketsHadLiquidity",
+            docs: [
+              "If this is false, the status quo won regardless of the TWAPs.",
+            ],
+            type: "bool",
+          },
+        ],
+      },
     },
     {
-      name: "updateDao",
-      accounts: [
-        {
-          name: "dao",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "treasury",
-          isMut: false,
-          isSigner: true,
-        },
+      name: "ProposalCategory",
+      docs: [
+        "Lets a DAO give some proposals more or less scrutiny than others. For",
+        "example, a DAO could require proposals that mint its token to run longer",
+        "and clear a higher threshold than proposals that make small grants.",
+        "",
+        "An instruction is in a category if it calls `program_id` and its data",
+        "starts with the first `data_prefix_len` bytes of `data_prefix`. This can be",
+        "used to match an Anchor discriminator or an SPL token instruction tag.",
       ],
-      args: [
-        {
-          name: "daoParams",
-          type: {
-            defined: "UpdateDaoParams",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "programId",
+            type: "publicKey",
+          },
+          {
+            name: "dataPrefix",
+            type: {
+              array: ["u8", 8],
+            },
           },
-        },
-      ],
+          {
+            name: "dataPrefixLen",
+            type: "u8",
+          },
+          {
+            name: "requirements",
+            type: {
+              defined: "ProposalRequirements",
+            },
+          },
+        ],
+      },
     },
-  ],
-  accounts: [
     {
-      name: "dao",
+      name: "ProposalRequirements",
+      docs: ["What a proposal needs to satisfy to be created and to pass."],
       type: {
         kind: "struct",
         fields: [
           {
-            name: "treasuryPdaBump",
-            type: "u8",
+            name: "passThresholdBps",
+            type: "u16",
           },
           {
-            name: "treasury",
-            type: "publicKey",
+            name: "slotsPerProposal",
+            type: "u64",
           },
           {
-            name: "tokenMint",
+            name: "minQuoteFutarchicLiquidity",
+            type: "u64",
+          },
+          {
+            name: "minBaseFutarchicLiquidity",
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "ProposalAccount",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "pubkey",
             type: "publicKey",
           },
           {
-            name: "usdcMint",
+            name: "isSigner",
+            type: "bool",
+          },
+          {
+            name: "isWritable",
+            type: "bool",
+          },
+        ],
+      },
+    },
+    {
+      name: "ProposalInstruction",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "programId",
             type: "publicKey",
           },
           {
-            name: "proposalCount",
-            type: "u32",
+            name: "accounts",
+            type: {
+              vec: {
+                defined: "ProposalAccount",
+              },
+            },
           },
           {
-            name: "passThresholdBps",
-            type: "u16",
+            name: "data",
+            type: "bytes",
+          },
+        ],
+      },
+    },
+    {
+      name: "SpotOracleSnapshot",
+      docs: [
+        "The state of the DAO's spot market's oracle when a proposal was created, so",
+        "that its TWAP over the proposal can be computed when it's finalized.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "amm",
+            type: "publicKey",
           },
           {
-            name: "slotsPerProposal",
+            name: "aggregator",
+            type: "u128",
+          },
+          {
+            name: "lastUpdatedSlot",
             type: "u64",
           },
+        ],
+      },
+    },
+    {
+      name: "MarketSnapshot",
+      docs: [
+        "The state of one of a proposal's markets when the proposal was created, so",
+        "that what happened in the market over the proposal can be measured when",
+        "it's finalized.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
           {
-            name: "twapInitialObservation",
-            docs: [
-              "For manipulation-resistance the TWAP is a time-weighted average observation,",
-              "where observation tries to approximate price but can only move by",
-              "`twap_max_observation_change_per_update` per update. Because it can only move",
-              "a little bit per update, you need to check that it has a good initial observation.",
-              "Otherwise, an attacker could create a very high initial observation in the pass",
-              "market and a very low one in the fail market to force the proposal to pass.",
-              "",
-              "We recommend setting an initial observation around the spot price of the token,",
-              "and max observation change per update around 2% the spot price of the token.",
-              "For example, if the spot price of META is $400, we'd recommend setting an initial",
-              "observation of 400 (converted into the AMM prices) and a max observation change per",
-              "update of 8 (also converted into the AMM prices). Observations can be updated once",
-              "a minute, so 2% allows the proposal market to reach double the spot price or 0",
-              "in 50 minutes.",
-            ],
+            name: "quoteLiquidityAggregator",
             type: "u128",
           },
           {
-            name: "twapMaxObservationChangePerUpdate",
+            name: "quoteVolume",
             type: "u128",
           },
+        ],
+      },
+    },
+    {
+      name: "ProposalDecision",
+      docs: [
+        "The inputs that `finalize_proposal` decided a proposal with, kept so that",
+        "anyone can audit the decision from chain state. Together with the",
+        "proposal's `pass_threshold_bps`, `spot_twap` and `dao_snapshot`, these are",
+        "everything that `DaoSnapshot::proposal_passes` looked at.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
           {
-            name: "minQuoteFutarchicLiquidity",
-            docs: [
-              "As an anti-spam measure and to help liquidity, you need to lock up some liquidity",
-              "in both futarchic markets in order to create a proposal.",
-              "",
-              "For example, for META, we can use a `min_quote_futarchic_liquidity` of",
-              "5000 * 1_000_000 (5000 USDC) and a `min_base_futarchic_liquidity` of",
-              "10 * 1_000_000_000 (10 META).",
-            ],
-            type: "u64",
+            name: "passMarketTwap",
+            type: "u128",
           },
           {
-            name: "minBaseFutarchicLiquidity",
-            type: "u64",
+            name: "failMarketTwap",
+            type: "u128",
+          },
+          {
+            name: "proposalType",
+            type: {
+              defined: "ProposalType",
+            },
+          },
+          {
+            name: "marketsHadLiquidity",
+            docs: [
+              "If this is false, the proposal failed regardless of its TWAPs.",
+            ],
+            type: "bool",
           },
         ],
       },
     },
     {
-      name: "proposal",
+      name: "ProposalV0",
+      docs: [
+        "The layout of proposals created before proposals were versioned, when every",
+        "proposal held exactly one instruction. These can be brought up to date",
+        "with `migrate_proposal`.",
+      ],
       type: {
         kind: "struct",
         fields: [
@@ -1025,200 +7424,804 @@ export const IDL: Autocrat = {
           },
           {
             name: "nonce",
+            type: "u64",
+          },
+          {
+            name: "pdaBump",
+            type: "u8",
+          },
+          {
+            name: "question",
+            type: "publicKey",
+          },
+        ],
+      },
+    },
+    {
+      name: "SpendingLimit",
+      docs: [
+        "Caps how much of one mint the treasury can send out through proposals.",
+        "Outflows are measured as the decrease in the balances of the treasury's",
+        "token accounts for this mint across each executed instruction.",
+        "",
+        "Proposals snapshot the DAO's limits when they're created and are checked",
+        "against those, so a proposal can't loosen its own limits by changing the",
+        "DAO's. The window is shared by every proposal, so it's always tracked on the",
+        "DAO, and the stricter of the snapshot's and the DAO's `max_per_window`",
+        "applies.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "mint",
+            type: "publicKey",
+          },
+          {
+            name: "maxPerProposal",
+            docs: ["The most that any one proposal can send out."],
+            type: {
+              option: "u64",
+            },
+          },
+          {
+            name: "maxPerWindow",
             docs: [
-              "We need to include a per-proposer nonce to prevent some weird proposal",
-              "front-running edge cases. Using a `u64` means that proposers are unlikely",
-              "to run into collisions, even if they generate nonces randomly - I've run",
-              "the math :D",
+              "The most that all proposals combined can send out in any `window_slots`",
+              "slots.",
+            ],
+            type: {
+              option: "u64",
+            },
+          },
+          {
+            name: "windowSlots",
+            type: "u64",
+          },
+          {
+            name: "windowOutflows",
+            docs: [
+              "The outflows in the window, split into `SPENDING_WINDOW_BUCKETS`",
+              "buckets so that it can roll. Bucket number `n` is stored at index",
+              "`n % SPENDING_WINDOW_BUCKETS`, and `latest_bucket` is the newest bucket",
+              "number. Updated by autocrat as proposals are executed.",
             ],
+            type: {
+              array: ["u64", 8],
+            },
+          },
+          {
+            name: "latestBucket",
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "TreasuryOutflow",
+      docs: [
+        "How much of a mint a proposal has sent out of the treasury so far.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "mint",
+            type: "publicKey",
+          },
+          {
+            name: "amount",
             type: "u64",
           },
+        ],
+      },
+    },
+    {
+      name: "DecisionRule",
+      docs: [
+        "How a DAO decides whether a proposal passed, given the TWAPs of its pass",
+        "and fail markets.",
+      ],
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Relative",
+          },
+          {
+            name: "DefaultPass",
+            fields: [
+              {
+                name: "failThresholdBps",
+                type: "u16",
+              },
+            ],
+          },
+          {
+            name: "Absolute",
+            fields: [
+              {
+                name: "threshold",
+                type: "u128",
+              },
+            ],
+          },
+          {
+            name: "RelativeAboveSpot",
+          },
+          {
+            name: "ByProposalType",
+            fields: [
+              {
+                name: "parameterChangeThresholdBps",
+                type: "u16",
+              },
+              {
+                name: "treasurySpendThresholdBps",
+                type: "u16",
+              },
+              {
+                name: "securityChangeThresholdBps",
+                type: "u16",
+              },
+            ],
+          },
+        ],
+      },
+    },
+    {
+      name: "ProposalType",
+      docs: [
+        "What a proposal's instructions do, which decides the threshold that it",
+        "needs under `DecisionRule::ByProposalType`.",
+      ],
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Standard",
+          },
+          {
+            name: "ParameterChange",
+          },
+          {
+            name: "TreasurySpend",
+          },
+          {
+            name: "SecurityChange",
+          },
+        ],
+      },
+    },
+    {
+      name: "ProposalSetupStage",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "CreatePassAmm",
+          },
           {
-            name: "pdaBump",
-            type: "u8",
+            name: "CreateFailAmm",
           },
           {
-            name: "question",
-            type: "publicKey",
+            name: "Complete",
           },
         ],
       },
     },
-  ],
-  types: [
     {
-      name: "InitializeDaoParams",
+      name: "ProposalState",
       type: {
-        kind: "struct",
-        fields: [
+        kind: "enum",
+        variants: [
           {
-            name: "twapInitialObservation",
-            type: "u128",
+            name: "Pending",
           },
           {
-            name: "twapMaxObservationChangePerUpdate",
-            type: "u128",
+            name: "Passed",
           },
           {
-            name: "minQuoteFutarchicLiquidity",
-            type: "u64",
+            name: "Failed",
           },
           {
-            name: "minBaseFutarchicLiquidity",
-            type: "u64",
+            name: "Executed",
           },
           {
-            name: "passThresholdBps",
-            type: {
-              option: "u16",
-            },
+            name: "Cancelled",
           },
           {
-            name: "slotsPerProposal",
-            type: {
-              option: "u64",
-            },
+            name: "Vetoed",
+          },
+          {
+            name: "Expired",
           },
         ],
       },
     },
+  ],
+  events: [
     {
-      name: "InitializeProposalParams",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "descriptionUrl",
-            type: "string",
+      name: "InitializeDaoEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
           },
-          {
-            name: "instruction",
-            type: {
-              defined: "ProposalInstruction",
-            },
+          index: false,
+        },
+        {
+          name: "dao",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "tokenMint",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "usdcMint",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "treasury",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "config",
+          type: {
+            defined: "DaoConfig",
           },
-          {
-            name: "passLpTokensToLock",
-            type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "UpdateDaoEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
           },
-          {
-            name: "failLpTokensToLock",
-            type: "u64",
+          index: false,
+        },
+        {
+          name: "dao",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "config",
+          type: {
+            defined: "DaoConfig",
           },
-          {
-            name: "nonce",
-            type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "InitializeProposalEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
           },
-        ],
-      },
+          index: false,
+        },
+        {
+          name: "proposal",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "dao",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "proposer",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "number",
+          type: "u32",
+          index: false,
+        },
+        {
+          name: "descriptionUrl",
+          type: "string",
+          index: false,
+        },
+        {
+          name: "instructions",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "question",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "baseVault",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "quoteVault",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "passAmm",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "failAmm",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "passLpTokensLocked",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "failLpTokensLocked",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "passThresholdBps",
+          type: "u16",
+          index: false,
+        },
+        {
+          name: "slotsPerProposal",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "nonce",
+          type: "u64",
+          index: false,
+        },
+      ],
     },
     {
-      name: "UpdateDaoParams",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "passThresholdBps",
-            type: {
-              option: "u16",
-            },
+      name: "FinalizeProposalEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
           },
-          {
-            name: "slotsPerProposal",
-            type: {
-              option: "u64",
-            },
+          index: false,
+        },
+        {
+          name: "proposal",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "dao",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "passMarketTwap",
+          type: "u128",
+          index: false,
+        },
+        {
+          name: "failMarketTwap",
+          type: "u128",
+          index: false,
+        },
+        {
+          name: "passThresholdBps",
+          type: "u16",
+          index: false,
+        },
+        {
+          name: "decisionRule",
+          type: {
+            defined: "DecisionRule",
           },
-          {
-            name: "twapInitialObservation",
-            type: {
-              option: "u128",
-            },
+          index: false,
+        },
+        {
+          name: "spotTwap",
+          type: {
+            option: "u128",
           },
-          {
-            name: "twapMaxObservationChangePerUpdate",
-            type: {
-              option: "u128",
-            },
+          index: false,
+        },
+        {
+          name: "marketsHadLiquidity",
+          type: "bool",
+          index: false,
+        },
+        {
+          name: "state",
+          type: {
+            defined: "ProposalState",
           },
-          {
-            name: "minQuoteFutarchicLiquidity",
-            type: {
-              option: "u64",
-            },
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "InitializeMultiOptionProposalEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
           },
-          {
-            name: "minBaseFutarchicLiquidity",
-            type: {
-              option: "u64",
+          index: false,
+        },
+        {
+          name: "proposal",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "dao",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "proposer",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "number",
+          type: "u32",
+          index: false,
+        },
+        {
+          name: "descriptionUrl",
+          type: "string",
+          index: false,
+        },
+        {
+          name: "question",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "baseVault",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "quoteVault",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "options",
+          type: {
+            vec: {
+              defined: "ProposalOption",
             },
           },
-        ],
-      },
+          index: false,
+        },
+        {
+          name: "passThresholdBps",
+          type: "u16",
+          index: false,
+        },
+        {
+          name: "slotsPerProposal",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "nonce",
+          type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "FinalizeMultiOptionProposalEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
+          },
+          index: false,
+        },
+        {
+          name: "proposal",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "dao",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "optionTwaps",
+          type: {
+            vec: "u128",
+          },
+          index: false,
+        },
+        {
+          name: "passThresholdBps",
+          type: "u16",
+          index: false,
+        },
+        {
+          name: "decisionRule",
+          type: {
+            defined: "DecisionRule",
+          },
+          index: false,
+        },
+        {
+          name: "spotTwap",
+          type: {
+            option: "u128",
+          },
+          index: false,
+        },
+        {
+          name: "marketsHadLiquidity",
+          type: "bool",
+          index: false,
+        },
+        {
+          name: "winningOption",
+          type: "u8",
+          index: false,
+        },
+        {
+          name: "state",
+          type: {
+            defined: "ProposalState",
+          },
+          index: false,
+        },
+      ],
     },
     {
-      name: "ProposalAccount",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "pubkey",
-            type: "publicKey",
+      name: "SeedProposalMarketEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
           },
-          {
-            name: "isSigner",
-            type: "bool",
+          index: false,
+        },
+        {
+          name: "proposal",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "dao",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "amm",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "lpTokens",
+          type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "SettleTreasuryPositionsEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
           },
-          {
-            name: "isWritable",
-            type: "bool",
+          index: false,
+        },
+        {
+          name: "proposal",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "dao",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "amm",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "lpTokensBurned",
+          type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "SettleProposalBondEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
           },
-        ],
-      },
+          index: false,
+        },
+        {
+          name: "proposal",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "dao",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "proposer",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "bondMint",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "amount",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "refunded",
+          type: "bool",
+          index: false,
+        },
+      ],
     },
     {
-      name: "ProposalInstruction",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "programId",
-            type: "publicKey",
+      name: "ExecuteProposalEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
           },
-          {
-            name: "accounts",
-            type: {
-              vec: {
-                defined: "ProposalAccount",
-              },
-            },
+          index: false,
+        },
+        {
+          name: "proposal",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "dao",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "instructionIndex",
+          type: {
+            option: "u16",
           },
-          {
-            name: "data",
-            type: "bytes",
+          index: false,
+        },
+        {
+          name: "state",
+          type: {
+            defined: "ProposalState",
           },
-        ],
-      },
+          index: false,
+        },
+      ],
     },
     {
-      name: "ProposalState",
-      type: {
-        kind: "enum",
-        variants: [
-          {
-            name: "Pending",
-          },
-          {
-            name: "Passed",
+      name: "CancelProposalEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
           },
-          {
-            name: "Failed",
+          index: false,
+        },
+        {
+          name: "proposal",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "dao",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "proposer",
+          type: "publicKey",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "VetoProposalEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
           },
-          {
-            name: "Executed",
+          index: false,
+        },
+        {
+          name: "proposal",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "dao",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "vetoAuthority",
+          type: "publicKey",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "ExpireProposalEvent",
+      fields: [
+        {
+          name: "common",
+          type: {
+            defined: "CommonFields",
           },
-        ],
-      },
+          index: false,
+        },
+        {
+          name: "proposal",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "dao",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "numInstructionsExecuted",
+          type: "u16",
+          index: false,
+        },
+      ],
     },
   ],
   errors: [
@@ -1275,7 +8278,222 @@ export const IDL: Autocrat = {
     {
       code: 6010,
       name: "InsufficientLpTokenLock",
-      msg: "The LP tokens passed in have less liquidity than the DAO's `min_quote_futarchic_liquidity` or `min_base_futachic_liquidity`",
+      msg: "The LP tokens passed in have less liquidity than the proposal's `min_quote_futarchic_liquidity` or `min_base_futachic_liquidity`",
+    },
+    {
+      code: 6011,
+      name: "ProposalInstructionsFrozen",
+      msg: "This `ProposalInstructions` account has already been used by a proposal and can't be changed",
+    },
+    {
+      code: 6012,
+      name: "CancellationWindowElapsed",
+      msg: "Proposals can only be cancelled within `PROPOSAL_CANCELLATION_WINDOW_SLOTS` of being initialized",
+    },
+    {
+      code: 6013,
+      name: "ExecutionDelayNotElapsed",
+      msg: "This proposal can't be executed until `execution_delay_slots` after it was finalized",
+    },
+    {
+      code: 6014,
+      name: "InvalidVetoAuthority",
+      msg: "The signer isn't this DAO's `veto_authority`",
+    },
+    {
+      code: 6015,
+      name: "ExecutionWindowElapsed",
+      msg: "This proposal can't be executed because its `execution_window_slots` have passed",
+    },
+    {
+      code: 6016,
+      name: "ExecutionWindowNotElapsed",
+      msg: "This proposal can still be executed, so it can't be marked as expired",
+    },
+    {
+      code: 6017,
+      name: "InvalidNumOptions",
+      msg: "Multi-option proposals need between 2 and `MAX_PROPOSAL_OPTIONS` options, one for each of the question's outcomes",
+    },
+    {
+      code: 6018,
+      name: "InvalidOptionAccounts",
+      msg: "The remaining accounts don't match the proposal's options",
+    },
+    {
+      code: 6019,
+      name: "TooManyProposalCategories",
+      msg: "This DAO already has `MAX_PROPOSAL_CATEGORIES` proposal categories",
+    },
+    {
+      code: 6020,
+      name: "InvalidDataPrefixLen",
+      msg: "A proposal category's `data_prefix_len` can be at most 8",
+    },
+    {
+      code: 6021,
+      name: "InvalidBondMint",
+      msg: "A proposal bond has to be in the DAO's token or USDC",
+    },
+    {
+      code: 6022,
+      name: "MissingBondAccounts",
+      msg: "This DAO has a proposal bond, so the bond accounts need to be passed in",
+    },
+    {
+      code: 6023,
+      name: "NoBondToSettle",
+      msg: "This proposal has no bond, or its bond has already been settled",
+    },
+    {
+      code: 6024,
+      name: "ProposalNotFinalized",
+      msg: "A proposal's bond can only be settled once the proposal is no longer pending",
+    },
+    {
+      code: 6025,
+      name: "TooManyActiveProposals",
+      msg: "The DAO or this proposer already has the maximum number of active proposals",
+    },
+    {
+      code: 6026,
+      name: "ProposalSetupComplete",
+      msg: "This proposal setup has already created both markets",
+    },
+    {
+      code: 6027,
+      name: "InvalidSetupMint",
+      msg: "A market's mints don't match the conditional tokens of the proposal's vaults",
+    },
+    {
+      code: 6028,
+      name: "NonCanonicalQuestion",
+      msg: "A proposal's question must use the proposal's address as its ID and have the canonical number of outcomes",
+    },
+    {
+      code: 6029,
+      name: "MissingSpotAmm",
+      msg: "This DAO has a spot market, which needs to be passed in",
+    },
+    {
+      code: 6030,
+      name: "InvalidSpotAmm",
+      msg: "This isn't the DAO's spot market",
+    },
+    {
+      code: 6031,
+      name: "TreasuryLiquidityDisabled",
+      msg: "This DAO doesn't seed proposal markets from its treasury",
+    },
+    {
+      code: 6032,
+      name: "MarketAlreadySeeded",
+      msg: "The treasury has already seeded this market",
+    },
+    {
+      code: 6033,
+      name: "InvalidSeedMarket",
+      msg: "This market isn't one of the proposal's",
+    },
+    {
+      code: 6034,
+      name: "MarketPriceOutOfRange",
+      msg: "The market's price is too far from its TWAP oracle's last observation to seed it",
+    },
+    {
+      code: 6035,
+      name: "NoTreasuryPositionToSettle",
+      msg: "The treasury has no liquidity in this market to settle",
+    },
+    {
+      code: 6036,
+      name: "ProposalAlreadyMigrated",
+      msg: "This proposal is already in the current layout",
+    },
+    {
+      code: 6037,
+      name: "InvalidThresholdBps",
+      msg: "Thresholds in basis points can't be more than 10,000",
+    },
+    {
+      code: 6038,
+      name: "InvalidSlotsPerProposal",
+      msg: "Proposals need to last at least a day's worth of slots",
+    },
+    {
+      code: 6039,
+      name: "InvalidTwapInitialObservation",
+      msg: "The TWAP's initial observation can't be 0",
+    },
+    {
+      code: 6040,
+      name: "InvalidMaxObservationChangePerUpdate",
+      msg: "The TWAP's max observation change per update can't be 0, or the TWAP could never move",
+    },
+    {
+      code: 6041,
+      name: "InvalidExecutionWindow",
+      msg: "The execution window can't be 0, or passed proposals could never be executed",
+    },
+    {
+      code: 6042,
+      name: "InvalidMaxActiveProposals",
+      msg: "A cap on active proposals can't be 0, or no proposals could be created",
+    },
+    {
+      code: 6043,
+      name: "InvalidTreasuryLiquidity",
+      msg: "The treasury needs to add a non-zero amount of both tokens",
+    },
+    {
+      code: 6044,
+      name: "InvalidSpendingLimit",
+      msg: "A spending limit needs a non-zero window",
+    },
+    {
+      code: 6045,
+      name: "TooManySpendingLimits",
+      msg: "This DAO already has the maximum number of spending limits",
+    },
+    {
+      code: 6046,
+      name: "SpendingLimitExceeded",
+      msg: "This instruction would send more out of the treasury than the DAO's spending limits allow",
+    },
+    {
+      code: 6047,
+      name: "TreasuryTokenAccountAuthorityChanged",
+      msg: "Proposals can't change the owner or delegate of the treasury's limited token accounts",
+    },
+    {
+      code: 6048,
+      name: "DaoAlreadyMigrated",
+      msg: "This DAO is already in the current layout",
+    },
+    {
+      code: 6049,
+      name: "UnsupportedByMultiOptionProposals",
+      msg: "Multi-option proposals don't support proposal bonds or treasury liquidity",
+    },
+    {
+      code: 6050,
+      name: "ProposalSetupIncomplete",
+      msg: "A proposal can only be initialized once its setup has created both markets",
+    },
+    {
+      code: 6051,
+      name: "InvalidAbsoluteThreshold",
+      msg: "An absolute threshold can be at most the DAO's initial TWAP observation",
+    },
+    {
+      code: 6052,
+      name: "SpotAmmRequired",
+      msg: "RelativeAboveSpot needs the DAO to have a spot market",
+    },
+    {
+      code: 6053,
+      name: "CannotMigratePendingProposal",
+      msg: "Only proposals that have already been finalized can be migrated",
     },
   ],
 };
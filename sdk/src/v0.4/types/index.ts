@@ -24,10 +24,13 @@ export type InitializeDaoParams =
 export type UpdateDaoParams = IdlTypes<AutocratProgram>["UpdateDaoParams"];
 export type ProposalInstruction =
   IdlTypes<AutocratProgram>["ProposalInstruction"];
+export type SetupProposalAmmParams =
+  IdlTypes<AutocratProgram>["SetupProposalAmmParams"];
 
 export type Dao = IdlAccounts<AutocratProgram>["dao"];
 export type Proposal = IdlAccounts<AutocratProgram>["proposal"];
 export type Amm = IdlAccounts<AmmProgram>["amm"];
+export type AmmCurve = IdlTypes<AmmProgram>["AmmCurve"];
 
 export type SwapEvent = IdlEvents<AmmProgram>["SwapEvent"];
 export type AddLiquidityEvent = IdlEvents<AmmProgram>["AddLiquidityEvent"];
@@ -6,6 +6,8 @@ export const MaxCUs = {
   initializeAmm: 120_000,
   addLiquidity: 120_000,
   initializeProposal: 60_000,
+  initializeProposalSetup: 400_000,
+  setupProposalAmm: 300_000,
 };
 
 export const DEFAULT_CU_PRICE = 1;
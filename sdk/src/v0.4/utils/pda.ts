@@ -161,3 +161,43 @@ export const getAmmLpMintAddr = (
     programId
   );
 };
+
+export const getProposalSetupAddr = (
+  programId: PublicKey,
+  proposer: PublicKey,
+  nonce: BN
+): [PublicKey, number] => {
+  return PublicKey.findProgramAddressSync(
+    [
+      utils.bytes.utf8.encode("proposal_setup"),
+      proposer.toBuffer(),
+      nonce.toArrayLike(Buffer, "le", 8),
+    ],
+    programId
+  );
+};
+
+export const getProposerRecordAddr = (
+  programId: PublicKey,
+  dao: PublicKey,
+  proposer: PublicKey
+): [PublicKey, number] => {
+  return PublicKey.findProgramAddressSync(
+    [
+      utils.bytes.utf8.encode("proposer_record"),
+      dao.toBuffer(),
+      proposer.toBuffer(),
+    ],
+    programId
+  );
+};
+
+export const getBondEscrowAddr = (
+  programId: PublicKey,
+  proposal: PublicKey
+): [PublicKey, number] => {
+  return PublicKey.findProgramAddressSync(
+    [utils.bytes.utf8.encode("bond"), proposal.toBuffer()],
+    programId
+  );
+};
@@ -65,6 +65,53 @@ export default function suite() {
       ammAcc.oracle.initialObservation.eq(expectedInitialObservation)
     );
     assert.equal(ammAcc.seqNum.toString(), "0");
+    assert.deepEqual(ammAcc.curve, { constantProduct: {} });
+  });
+
+  it("creates a stable swap amm", async function () {
+    const USDT = await createMint(
+      this.banksClient,
+      this.payer,
+      this.payer.publicKey,
+      this.payer.publicKey,
+      6
+    );
+
+    let [amm] = getAmmAddr(ammClient.program.programId, USDT, USDC);
+
+    await ammClient.createAmm(
+      Keypair.generate().publicKey,
+      USDT,
+      USDC,
+      1,
+      undefined,
+      { stableSwap: { amplification: new BN(100) } }
+    );
+
+    const ammAcc = await ammClient.getAmm(amm);
+
+    assert.isTrue(ammAcc.curve.stableSwap.amplification.eqn(100));
+  });
+
+  it("fails to create a stable swap amm between mints with different decimals", async function () {
+    let [twapFirstObservationScaled, twapMaxObservationChangePerUpdateScaled] =
+      PriceMath.getAmmPrices(6, 9, 100, 1);
+
+    const callbacks = expectError(
+      "StableSwapDecimalsMismatch",
+      "create AMM succeeded despite mismatched decimals"
+    );
+
+    await ammClient
+      .initializeAmmIx(
+        USDC,
+        META,
+        twapFirstObservationScaled,
+        twapMaxObservationChangePerUpdateScaled,
+        { stableSwap: { amplification: new BN(100) } }
+      )
+      .rpc()
+      .then(callbacks[0], callbacks[1]);
   });
 
   it("fails to create an amm with two identical mints", async function () {
@@ -41,6 +41,7 @@ import {
   getAmmAddr,
   getAmmLpMintAddr,
   getVaultAddr,
+  getProposalSetupAddr,
   AmmMath,
 } from "@metadaoproject/futarchy/v0.4";
 import { PriceMath } from "@metadaoproject/futarchy/v0.4";
@@ -240,19 +241,33 @@ export default function suite() {
       const accounts = [
         {
           pubkey: dao,
-          isSigner: true,
+          isSigner: false,
           isWritable: true,
         },
+        {
+          pubkey: daoTreasury,
+          isSigner: true,
+          isWritable: false,
+        },
       ];
       const data = autocrat.coder.instruction.encode("update_dao", {
         daoParams: {
           passThresholdBps: 500,
-          baseBurnLamports: null,
-          burnDecayPerSlotLamports: null,
           slotsPerProposal: null,
-          marketTakerFee: null,
-          // minQuoteFutarchicLiquidity: new BN(10),
-          // minBaseFutarchicLiquidity: new BN(100),
+          twapInitialObservation: null,
+          twapMaxObservationChangePerUpdate: null,
+          minQuoteFutarchicLiquidity: null,
+          minBaseFutarchicLiquidity: null,
+          minAverageQuoteLiquidity: null,
+          executionDelaySlots: null,
+          vetoAuthority: null,
+          executionWindowSlots: null,
+          decisionRule: null,
+          proposalBond: null,
+          maxActiveProposals: null,
+          maxActiveProposalsPerProposer: null,
+          spotAmm: null,
+          treasuryLiquidity: null,
         },
       });
       const instruction = {
@@ -274,14 +289,30 @@ export default function suite() {
         )
       ).amount;
 
-      await autocratClient.initializeProposal(
+      const proposal = await autocratClient.initializeProposal(
         dao,
         "",
-        instruction,
+        [instruction],
         PriceMath.getChainAmount(5, 9),
         PriceMath.getChainAmount(5000, 6)
       );
 
+      const storedProposal = await autocratClient.getProposal(proposal);
+      const storedInstructions =
+        await autocrat.account.proposalInstructions.fetch(
+          storedProposal.instructions
+        );
+      assert.isTrue(storedInstructions.frozen);
+      assert.equal(storedInstructions.instructions.length, 1);
+
+      // the setup is closed once the proposal is initialized
+      const [proposalSetup] = getProposalSetupAddr(
+        autocrat.programId,
+        payer.publicKey,
+        storedProposal.nonce
+      );
+      assert.isNull(await banksClient.getAccount(proposalSetup));
+
       const postMetaBalance = (
         await getAccount(
           banksClient,
@@ -342,7 +373,7 @@ export default function suite() {
       proposal = await autocratClient.initializeProposal(
         dao,
         "",
-        instruction,
+        [instruction],
         ONE_META.muln(10),
         ONE_USDC.muln(5000)
       );
@@ -566,11 +597,21 @@ export default function suite() {
       const data = autocrat.coder.instruction.encode("update_dao", {
         daoParams: {
           passThresholdBps: 500,
-          slotsPerProposal: new BN(10),
+          slotsPerProposal: new BN(216_000),
           twapInitialObservation: null,
           twapMaxObservationChangePerUpdate: null,
           minQuoteFutarchicLiquidity: new BN(10),
           minBaseFutarchicLiquidity: new BN(100),
+          minAverageQuoteLiquidity: null,
+          executionDelaySlots: null,
+          vetoAuthority: null,
+          executionWindowSlots: null,
+          decisionRule: null,
+          proposalBond: null,
+          maxActiveProposals: null,
+          maxActiveProposalsPerProposer: null,
+          spotAmm: null,
+          treasuryLiquidity: null,
         },
       });
       const instruction = {
@@ -582,7 +623,7 @@ export default function suite() {
       proposal = await autocratClient.initializeProposal(
         dao,
         "",
-        instruction,
+        [instruction],
         ONE_META.muln(10),
         ONE_USDC.muln(6_000)
       );
@@ -703,7 +744,7 @@ export default function suite() {
 
       let afterDao = await autocratClient.getDao(dao);
       assert.equal(afterDao.passThresholdBps, 500);
-      assert.ok(afterDao.slotsPerProposal.eqn(10));
+      assert.ok(afterDao.slotsPerProposal.eqn(216_000));
       assert.equal(afterDao.minQuoteFutarchicLiquidity.toString(), "10");
       assert.equal(afterDao.minBaseFutarchicLiquidity.toString(), "100");
 
@@ -713,7 +754,7 @@ export default function suite() {
       );
 
       await autocratClient
-        .executeProposalIx(proposal, dao, storedProposal.instruction)
+        .executeProposalIx(proposal, dao, storedProposal.instructions)
         .preInstructions([
           // add a pre-instruction so it doesn't think it's already processed it
           anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({
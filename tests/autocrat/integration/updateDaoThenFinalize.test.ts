@@ -0,0 +1,75 @@
+import { BN } from "bn.js";
+import { assert } from "chai";
+import { AutocratClient } from "@metadaoproject/futarchy/v0.4";
+
+import {
+  ONE_USDC,
+  SLOTS_PER_PROPOSAL,
+  buyInMarket,
+  crankProposals,
+  initializeProposal,
+  setupDao,
+  updateDaoInstruction,
+} from "../utils.js";
+
+// passes a proposal that changes the DAO's config while another proposal is
+// trading, and checks that the other proposal is still finalized and executed
+// under the config that it was created with
+export default async function () {
+  const autocratClient: AutocratClient = this.autocratClient;
+  const fixture = await setupDao(this);
+
+  const otherProposal = await initializeProposal(this, fixture.dao);
+  const updateProposal = await initializeProposal(this, fixture.dao, [
+    updateDaoInstruction(autocratClient, fixture, {
+      passThresholdBps: 5_000,
+      slotsPerProposal: new BN(SLOTS_PER_PROPOSAL * 2),
+      executionDelaySlots: new BN(1_000_000),
+    }),
+  ]);
+
+  // both pass markets end up about 20% above their fail markets
+  for (const proposal of [otherProposal, updateProposal]) {
+    await buyInMarket(this, proposal, "pass", ONE_USDC.muln(1_000));
+  }
+  await crankProposals(this, [otherProposal, updateProposal]);
+
+  await autocratClient.finalizeProposal(updateProposal);
+  await autocratClient.executeProposal(updateProposal);
+
+  const storedDao = await autocratClient.getDao(fixture.dao);
+  assert.equal(storedDao.passThresholdBps, 5_000);
+  assert.equal(
+    storedDao.slotsPerProposal.toString(),
+    (SLOTS_PER_PROPOSAL * 2).toString()
+  );
+  assert.equal(storedDao.executionDelaySlots.toString(), "1000000");
+
+  // the new config would need a 50% lead, twice as many slots and a delay
+  // before executing, but the other proposal still runs under the old one
+  await autocratClient.finalizeProposal(otherProposal);
+
+  let storedProposal = await autocratClient.getProposal(otherProposal);
+  assert.exists(storedProposal.state.passed);
+  assert.equal(storedProposal.passThresholdBps, 300);
+  assert.equal(
+    storedProposal.slotsPerProposal.toString(),
+    SLOTS_PER_PROPOSAL.toString()
+  );
+  const { passMarketTwap, failMarketTwap } = storedProposal.decision;
+  assert.isTrue(passMarketTwap.muln(10_000).gt(failMarketTwap.muln(10_300)));
+  assert.isTrue(passMarketTwap.muln(10_000).lt(failMarketTwap.muln(15_000)));
+
+  await autocratClient.executeProposal(otherProposal);
+  storedProposal = await autocratClient.getProposal(otherProposal);
+  assert.exists(storedProposal.state.executed);
+
+  // proposals created afterwards get the new config
+  const laterProposal = await initializeProposal(this, fixture.dao);
+  const storedLaterProposal = await autocratClient.getProposal(laterProposal);
+  assert.equal(storedLaterProposal.passThresholdBps, 5_000);
+  assert.equal(
+    storedLaterProposal.slotsPerProposal.toString(),
+    (SLOTS_PER_PROPOSAL * 2).toString()
+  );
+}
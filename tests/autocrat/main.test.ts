@@ -0,0 +1,22 @@
+import autocrat from "./autocrat.js";
+import proposalSetup from "./unit/proposalSetup.test.js";
+import seedProposalMarket from "./unit/seedProposalMarket.test.js";
+import settleTreasuryPositions from "./unit/settleTreasuryPositions.test.js";
+import settleProposalBond from "./unit/settleProposalBond.test.js";
+import spendingLimits from "./unit/spendingLimits.test.js";
+import multiOptionProposals from "./unit/multiOptionProposals.test.js";
+import updateDaoThenFinalize from "./integration/updateDaoThenFinalize.test.js";
+
+export default function suite() {
+  describe("proposal lifecycle", autocrat);
+  describe("#setup_proposal_amm", proposalSetup);
+  describe("#seed_proposal_market", seedProposalMarket);
+  describe("#settle_treasury_positions", settleTreasuryPositions);
+  describe("#settle_proposal_bond", settleProposalBond);
+  describe("#set_spending_limit", spendingLimits);
+  describe("multi-option proposals", multiOptionProposals);
+  it(
+    "finalizes proposals under the config they were created with",
+    updateDaoThenFinalize
+  );
+}
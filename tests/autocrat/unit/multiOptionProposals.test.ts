@@ -0,0 +1,381 @@
+import { BN } from "bn.js";
+import { assert } from "chai";
+import { getAccount } from "spl-token-bankrun";
+import { ComputeBudgetProgram, PublicKey } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  AutocratClient,
+  ProposalInstruction,
+  getAmmAddr,
+  getAmmLpMintAddr,
+  getConditionalTokenMintAddr,
+  getDaoTreasuryAddr,
+  getEventAuthorityAddr,
+  getProposerRecordAddr,
+  getQuestionAddr,
+  getVaultAddr,
+} from "@metadaoproject/futarchy/v0.4";
+
+import { expectError } from "../../utils.js";
+import {
+  BASE_LIQUIDITY,
+  DaoFixture,
+  ONE_USDC,
+  QUOTE_LIQUIDITY,
+  crankMarkets,
+  setupDao,
+  treasuryAccount,
+  updateDaoInstruction,
+} from "../utils.js";
+
+// the status quo and two options that set different pass thresholds
+const NUM_OPTIONS = 3;
+const OPTION_PASS_THRESHOLDS = [100, 200];
+
+type MultiOptionMarkets = {
+  proposal: PublicKey;
+  nonce: BN;
+  question: PublicKey;
+  baseVault: PublicKey;
+  quoteVault: PublicKey;
+  amms: PublicKey[];
+  lpMints: PublicKey[];
+};
+
+export default function suite() {
+  let autocratClient: AutocratClient;
+  let fixture: DaoFixture;
+
+  // creates a multi-option proposal's question, vaults and markets, and adds
+  // the proposer's liquidity to every market
+  async function setupMarkets(
+    ctx: Mocha.Context,
+    { dao, META, USDC }: DaoFixture
+  ): Promise<MultiOptionMarkets> {
+    const nonce = new BN(Math.random() * 2 ** 50);
+    const [proposal] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("multi_option_proposal"),
+        ctx.payer.publicKey.toBuffer(),
+        nonce.toArrayLike(Buffer, "le", 8),
+      ],
+      autocratClient.autocrat.programId
+    );
+
+    const vaultProgram = ctx.vaultClient.vaultProgram.programId;
+
+    // the question is the proposal's, so that only the proposal can resolve it
+    const questionId = proposal.toBytes();
+    const [question] = getQuestionAddr(
+      vaultProgram,
+      questionId,
+      proposal,
+      NUM_OPTIONS
+    );
+    await ctx.vaultClient
+      .initializeQuestionIx(questionId, proposal, NUM_OPTIONS)
+      .rpc();
+
+    const [baseVault] = getVaultAddr(vaultProgram, question, META);
+    const [quoteVault] = getVaultAddr(vaultProgram, question, USDC);
+    await ctx.vaultClient.initializeVaultIx(question, META, NUM_OPTIONS).rpc();
+    await ctx.vaultClient.initializeVaultIx(question, USDC, NUM_OPTIONS).rpc();
+
+    await ctx.vaultClient
+      .splitTokensIx(question, baseVault, META, BASE_LIQUIDITY, NUM_OPTIONS)
+      .rpc();
+    await ctx.vaultClient
+      .splitTokensIx(question, quoteVault, USDC, QUOTE_LIQUIDITY, NUM_OPTIONS)
+      .rpc();
+
+    const storedDao = await autocratClient.getDao(dao);
+    const [treasury] = getDaoTreasuryAddr(
+      autocratClient.autocrat.programId,
+      dao
+    );
+
+    const amms: PublicKey[] = [];
+    const lpMints: PublicKey[] = [];
+
+    for (let i = 0; i < NUM_OPTIONS; i++) {
+      const [baseMint] = getConditionalTokenMintAddr(
+        vaultProgram,
+        baseVault,
+        i
+      );
+      const [quoteMint] = getConditionalTokenMintAddr(
+        vaultProgram,
+        quoteVault,
+        i
+      );
+      const [amm] = getAmmAddr(
+        ctx.ammClient.getProgramId(),
+        baseMint,
+        quoteMint
+      );
+      const [lpMint] = getAmmLpMintAddr(ctx.ammClient.getProgramId(), amm);
+
+      await ctx.ammClient
+        .initializeAmmIx(
+          baseMint,
+          quoteMint,
+          storedDao.twapInitialObservation,
+          storedDao.twapMaxObservationChangePerUpdate
+        )
+        .rpc();
+      await ctx.ammClient
+        .addLiquidityIx(
+          amm,
+          baseMint,
+          quoteMint,
+          QUOTE_LIQUIDITY,
+          BASE_LIQUIDITY,
+          new BN(0)
+        )
+        .rpc();
+      await ctx.createTokenAccount(lpMint, treasury);
+
+      amms.push(amm);
+      lpMints.push(lpMint);
+    }
+
+    return { proposal, nonce, question, baseVault, quoteVault, amms, lpMints };
+  }
+
+  function initializeMultiOptionProposalIx(
+    ctx: Mocha.Context,
+    { dao, treasury }: DaoFixture,
+    markets: MultiOptionMarkets,
+    optionInstructions: PublicKey[]
+  ) {
+    return autocratClient.autocrat.methods
+      .initializeMultiOptionProposal({
+        descriptionUrl: "",
+        // the first deposit into a market mints as many LP tokens as quote
+        lpTokensToLock: markets.amms.map(() => QUOTE_LIQUIDITY),
+        nonce: markets.nonce,
+      })
+      .accounts({
+        proposal: markets.proposal,
+        dao,
+        proposerRecord: getProposerRecordAddr(
+          autocratClient.autocrat.programId,
+          dao,
+          ctx.payer.publicKey
+        )[0],
+        question: markets.question,
+        quoteVault: markets.quoteVault,
+        baseVault: markets.baseVault,
+        proposer: ctx.payer.publicKey,
+        spotAmm: null,
+      })
+      .remainingAccounts([
+        ...markets.amms.flatMap((amm, i) => [
+          { pubkey: amm, isSigner: false, isWritable: false },
+          { pubkey: markets.lpMints[i], isSigner: false, isWritable: false },
+          {
+            pubkey: getAssociatedTokenAddressSync(
+              markets.lpMints[i],
+              ctx.payer.publicKey
+            ),
+            isSigner: false,
+            isWritable: true,
+          },
+          {
+            pubkey: treasuryAccount(markets.lpMints[i], treasury),
+            isSigner: false,
+            isWritable: true,
+          },
+        ]),
+        ...optionInstructions.map((pubkey) => ({
+          pubkey,
+          isSigner: false,
+          isWritable: true,
+        })),
+      ])
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+      ]);
+  }
+
+  async function initializeOptionInstructions(
+    daoFixture: DaoFixture
+  ): Promise<PublicKey[]> {
+    const optionInstructions = [];
+    for (const passThresholdBps of OPTION_PASS_THRESHOLDS) {
+      optionInstructions.push(
+        await autocratClient.initializeProposalInstructions([
+          updateDaoInstruction(autocratClient, daoFixture, {
+            passThresholdBps,
+          }),
+        ])
+      );
+    }
+    return optionInstructions;
+  }
+
+  beforeEach(async function () {
+    autocratClient = this.autocratClient;
+    fixture = await setupDao(this);
+  });
+
+  it("executes the option whose market did best", async function () {
+    const markets = await setupMarkets(this, fixture);
+    const optionInstructions = await initializeOptionInstructions(fixture);
+
+    await initializeMultiOptionProposalIx(
+      this,
+      fixture,
+      markets,
+      optionInstructions
+    ).rpc();
+
+    let storedProposal =
+      await autocratClient.autocrat.account.multiOptionProposal.fetch(
+        markets.proposal
+      );
+    assert.exists(storedProposal.state.pending);
+    assert.equal(storedProposal.options.length, NUM_OPTIONS);
+    assert.isNull(storedProposal.options[0].instructions);
+    assert.ok(
+      storedProposal.options[2].instructions.equals(optionInstructions[1])
+    );
+
+    // buy the last option, splitting USDC into every option's conditional USDC
+    await this.vaultClient
+      .splitTokensIx(
+        markets.question,
+        markets.quoteVault,
+        fixture.USDC,
+        ONE_USDC.muln(1_000),
+        NUM_OPTIONS
+      )
+      .rpc();
+    const storedAmm = await this.ammClient.getAmm(markets.amms[2]);
+    await this.ammClient
+      .swapIx(
+        markets.amms[2],
+        storedAmm.baseMint,
+        storedAmm.quoteMint,
+        { buy: {} },
+        ONE_USDC.muln(1_000),
+        new BN(0)
+      )
+      .rpc();
+
+    await crankMarkets(this, markets.amms);
+
+    const vaultProgram = this.vaultClient.vaultProgram.programId;
+    await autocratClient.autocrat.methods
+      .finalizeMultiOptionProposal()
+      .accounts({
+        proposal: markets.proposal,
+        dao: fixture.dao,
+        proposerRecord: getProposerRecordAddr(
+          autocratClient.autocrat.programId,
+          fixture.dao,
+          this.payer.publicKey
+        )[0],
+        question: markets.question,
+        treasury: fixture.treasury,
+        vaultProgram,
+        vaultEventAuthority: getEventAuthorityAddr(vaultProgram)[0],
+        spotAmm: null,
+        payer: this.payer.publicKey,
+      })
+      .remainingAccounts(
+        markets.amms.flatMap((amm, i) => [
+          { pubkey: amm, isSigner: false, isWritable: false },
+          {
+            pubkey: getAssociatedTokenAddressSync(
+              markets.lpMints[i],
+              this.payer.publicKey
+            ),
+            isSigner: false,
+            isWritable: true,
+          },
+          {
+            pubkey: treasuryAccount(markets.lpMints[i], fixture.treasury),
+            isSigner: false,
+            isWritable: true,
+          },
+        ])
+      )
+      .rpc();
+
+    storedProposal =
+      await autocratClient.autocrat.account.multiOptionProposal.fetch(
+        markets.proposal
+      );
+    assert.exists(storedProposal.state.passed);
+    assert.equal(storedProposal.winningOption, 2);
+
+    // the proposer gets back the liquidity that they locked in every market
+    for (const lpMint of markets.lpMints) {
+      const proposerLp = await getAccount(
+        this.banksClient,
+        getAssociatedTokenAddressSync(lpMint, this.payer.publicKey)
+      );
+      assert.equal(proposerLp.amount.toString(), QUOTE_LIQUIDITY.toString());
+    }
+
+    const instruction: ProposalInstruction = updateDaoInstruction(
+      autocratClient,
+      fixture,
+      { passThresholdBps: OPTION_PASS_THRESHOLDS[1] }
+    );
+    await autocratClient.autocrat.methods
+      .executeMultiOptionProposal()
+      .accounts({
+        proposal: markets.proposal,
+        instructions: optionInstructions[1],
+        dao: fixture.dao,
+      })
+      .remainingAccounts(
+        instruction.accounts
+          .concat({
+            pubkey: instruction.programId,
+            isSigner: false,
+            isWritable: false,
+          })
+          .map((meta) =>
+            meta.pubkey.equals(fixture.treasury)
+              ? { ...meta, isSigner: false }
+              : meta
+          )
+      )
+      .rpc();
+
+    const storedDao = await autocratClient.getDao(fixture.dao);
+    assert.equal(storedDao.passThresholdBps, OPTION_PASS_THRESHOLDS[1]);
+  });
+
+  it("doesn't create multi-option proposals for DAOs with bonds", async function () {
+    const bondedFixture = await setupDao(this, ({ USDC }) => ({
+      proposalBond: {
+        mint: USDC,
+        amount: ONE_USDC.muln(100),
+        refundQuoteVolume: ONE_USDC.muln(200),
+      },
+    }));
+
+    const markets = await setupMarkets(this, bondedFixture);
+    const optionInstructions = await initializeOptionInstructions(
+      bondedFixture
+    );
+
+    const callbacks = expectError(
+      "UnsupportedByMultiOptionProposals",
+      "created a multi-option proposal that gets around the DAO's bond"
+    );
+
+    await initializeMultiOptionProposalIx(
+      this,
+      bondedFixture,
+      markets,
+      optionInstructions
+    )
+      .rpc()
+      .then(callbacks[0], callbacks[1]);
+  });
+}
@@ -0,0 +1,158 @@
+import { BN } from "bn.js";
+import { assert } from "chai";
+import { getAccount } from "spl-token-bankrun";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { ComputeBudgetProgram } from "@solana/web3.js";
+import {
+  AutocratClient,
+  getProposalAddr,
+  getProposalSetupAddr,
+} from "@metadaoproject/futarchy/v0.4";
+
+import { expectError } from "../../utils.js";
+import {
+  BASE_LIQUIDITY,
+  DaoFixture,
+  QUOTE_LIQUIDITY,
+  initializeProposal,
+  setupDao,
+  treasuryAccount,
+} from "../utils.js";
+
+export default function suite() {
+  let autocratClient: AutocratClient;
+  let fixture: DaoFixture;
+
+  beforeEach(async function () {
+    autocratClient = this.autocratClient;
+    fixture = await setupDao(this);
+  });
+
+  // sets up a proposal's question and vaults, and splits enough tokens for
+  // both of its markets
+  async function initializeSetup(ctx: Mocha.Context): Promise<BN> {
+    const nonce = new BN(Math.random() * 2 ** 50);
+    const [proposal] = getProposalAddr(
+      autocratClient.autocrat.programId,
+      ctx.payer.publicKey,
+      nonce
+    );
+
+    await autocratClient
+      .initializeProposalSetupIx(fixture.dao, fixture.META, fixture.USDC, nonce)
+      .rpc();
+
+    const { question, baseVault, quoteVault } = autocratClient.getProposalPdas(
+      proposal,
+      fixture.META,
+      fixture.USDC,
+      fixture.dao
+    );
+    await ctx.vaultClient
+      .splitTokensIx(question, baseVault, fixture.META, BASE_LIQUIDITY, 2)
+      .rpc();
+    await ctx.vaultClient
+      .splitTokensIx(question, quoteVault, fixture.USDC, QUOTE_LIQUIDITY, 2)
+      .rpc();
+
+    return nonce;
+  }
+
+  function setupAmm(nonce: BN, outcome: "pass" | "fail") {
+    return autocratClient.setupProposalAmmIx(
+      fixture.dao,
+      fixture.META,
+      fixture.USDC,
+      nonce,
+      outcome,
+      {
+        quoteAmount: QUOTE_LIQUIDITY,
+        maxBaseAmount: BASE_LIQUIDITY,
+        minLpTokens: new BN(0),
+      }
+    );
+  }
+
+  it("creates proposals from the markets that their setup created", async function () {
+    const proposal = await initializeProposal(this, fixture.dao);
+
+    const storedProposal = await autocratClient.getProposal(proposal);
+    const {
+      question,
+      baseVault,
+      quoteVault,
+      passAmm,
+      failAmm,
+      passLp,
+      failLp,
+    } = autocratClient.getProposalPdas(
+      proposal,
+      fixture.META,
+      fixture.USDC,
+      fixture.dao
+    );
+
+    assert.ok(storedProposal.question.equals(question));
+    assert.ok(storedProposal.baseVault.equals(baseVault));
+    assert.ok(storedProposal.quoteVault.equals(quoteVault));
+    assert.ok(storedProposal.passAmm.equals(passAmm));
+    assert.ok(storedProposal.failAmm.equals(failAmm));
+    assert.exists(storedProposal.state.pending);
+
+    // the proposer's liquidity is locked in the treasury until the proposal
+    // is finalized
+    for (const lpMint of [passLp, failLp]) {
+      const lockedLp = await getAccount(
+        this.banksClient,
+        treasuryAccount(lpMint, fixture.treasury)
+      );
+      assert.equal(lockedLp.amount.toString(), QUOTE_LIQUIDITY.toString());
+
+      const proposerLp = await getAccount(
+        this.banksClient,
+        getAssociatedTokenAddressSync(lpMint, this.payer.publicKey)
+      );
+      assert.equal(proposerLp.amount.toString(), "0");
+    }
+
+    const [proposalSetup] = getProposalSetupAddr(
+      autocratClient.autocrat.programId,
+      this.payer.publicKey,
+      storedProposal.nonce
+    );
+    assert.isNull(await this.banksClient.getAccount(proposalSetup));
+  });
+
+  it("sets up the pass market before the fail market", async function () {
+    const nonce = await initializeSetup(this);
+
+    const callbacks = expectError(
+      "InvalidSetupMint",
+      "set up the fail market before the pass market"
+    );
+
+    await setupAmm(nonce, "fail")
+      .rpc()
+      .then(callbacks[0], callbacks[1]);
+  });
+
+  it("doesn't set up more than two markets", async function () {
+    const nonce = await initializeSetup(this);
+
+    await setupAmm(nonce, "pass").rpc();
+    await setupAmm(nonce, "fail").rpc();
+
+    const callbacks = expectError(
+      "ProposalSetupComplete",
+      "set up a third market for a proposal"
+    );
+
+    await setupAmm(nonce, "pass")
+      // otherwise, bankrun thinks that this is the first call again
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitPrice({ microLamports: 1 }),
+      ])
+      .rpc()
+      .then(callbacks[0], callbacks[1]);
+  });
+}
@@ -0,0 +1,193 @@
+import { assert } from "chai";
+import { getAccount, mintToOverride } from "spl-token-bankrun";
+import { ComputeBudgetProgram, PublicKey } from "@solana/web3.js";
+import { AutocratClient } from "@metadaoproject/futarchy/v0.4";
+
+import { expectError } from "../../utils.js";
+import {
+  DaoFixture,
+  ONE_META,
+  ONE_USDC,
+  buyInMarket,
+  createTreasuryConditionalAccounts,
+  initializeProposal,
+  setupDao,
+  treasuryAccount,
+  treasuryPositionIx,
+} from "../utils.js";
+
+export default function suite() {
+  let autocratClient: AutocratClient;
+  let fixture: DaoFixture;
+  let proposal: PublicKey;
+
+  beforeEach(async function () {
+    autocratClient = this.autocratClient;
+    fixture = await setupDao(this, {
+      treasuryLiquidity: {
+        quoteAmount: ONE_USDC.muln(1_000),
+        maxBaseAmount: ONE_META.muln(5),
+        maxPriceDeviationBps: 500,
+      },
+    });
+
+    await mintToOverride(
+      this.context,
+      treasuryAccount(fixture.META, fixture.treasury),
+      100n * 1_000_000_000n
+    );
+    await mintToOverride(
+      this.context,
+      treasuryAccount(fixture.USDC, fixture.treasury),
+      100_000n * 1_000_000n
+    );
+
+    proposal = await initializeProposal(this, fixture.dao);
+    await createTreasuryConditionalAccounts(this, fixture, proposal);
+  });
+
+  it("adds the treasury's liquidity to both markets", async function () {
+    const preTreasuryUsdc = (
+      await getAccount(
+        this.banksClient,
+        treasuryAccount(fixture.USDC, fixture.treasury)
+      )
+    ).amount;
+
+    for (const outcome of ["pass", "fail"] as const) {
+      await (
+        await treasuryPositionIx(
+          this,
+          fixture,
+          proposal,
+          outcome,
+          "seedProposalMarket"
+        )
+      ).rpc();
+    }
+
+    const storedProposal = await autocratClient.getProposal(proposal);
+    const { passLp, failLp } = autocratClient.getProposalPdas(
+      proposal,
+      fixture.META,
+      fixture.USDC,
+      fixture.dao
+    );
+
+    // the treasury's LP is tracked apart from the proposer's, which is held
+    // in the same account
+    for (const [lpMint, treasuryLpTokens, lpTokensLocked] of [
+      [
+        passLp,
+        storedProposal.treasuryLpTokens[1],
+        storedProposal.passLpTokensLocked,
+      ],
+      [
+        failLp,
+        storedProposal.treasuryLpTokens[0],
+        storedProposal.failLpTokensLocked,
+      ],
+    ] as const) {
+      assert.isTrue(treasuryLpTokens.gtn(0));
+
+      const treasuryLp = await getAccount(
+        this.banksClient,
+        treasuryAccount(lpMint, fixture.treasury)
+      );
+      assert.equal(
+        treasuryLp.amount.toString(),
+        lpTokensLocked.add(treasuryLpTokens).toString()
+      );
+    }
+    assert.equal(storedProposal.treasuryMaxPriceDeviationBps, 500);
+
+    // both markets are seeded out of one split
+    const postTreasuryUsdc = (
+      await getAccount(
+        this.banksClient,
+        treasuryAccount(fixture.USDC, fixture.treasury)
+      )
+    ).amount;
+    assert.equal(
+      (preTreasuryUsdc - postTreasuryUsdc).toString(),
+      ONE_USDC.muln(1_000).toString()
+    );
+  });
+
+  it("doesn't seed a market twice", async function () {
+    await (
+      await treasuryPositionIx(
+        this,
+        fixture,
+        proposal,
+        "pass",
+        "seedProposalMarket"
+      )
+    ).rpc();
+
+    const callbacks = expectError(
+      "MarketAlreadySeeded",
+      "seeded the same market twice"
+    );
+
+    await (
+      await treasuryPositionIx(
+        this,
+        fixture,
+        proposal,
+        "pass",
+        "seedProposalMarket"
+      )
+    )
+      // otherwise, bankrun thinks that this is the first call again
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitPrice({ microLamports: 1 }),
+      ])
+      .rpc()
+      .then(callbacks[0], callbacks[1]);
+  });
+
+  it("doesn't seed markets whose price was pushed away from the oracle", async function () {
+    await buyInMarket(this, proposal, "pass", ONE_USDC.muln(1_000));
+
+    const callbacks = expectError(
+      "MarketPriceOutOfRange",
+      "seeded a market at a manipulated price"
+    );
+
+    await (
+      await treasuryPositionIx(
+        this,
+        fixture,
+        proposal,
+        "pass",
+        "seedProposalMarket"
+      )
+    )
+      .rpc()
+      .then(callbacks[0], callbacks[1]);
+  });
+
+  it("doesn't seed markets for DAOs without treasury liquidity", async function () {
+    const otherFixture = await setupDao(this);
+    const otherProposal = await initializeProposal(this, otherFixture.dao);
+    await createTreasuryConditionalAccounts(this, otherFixture, otherProposal);
+
+    const callbacks = expectError(
+      "TreasuryLiquidityDisabled",
+      "seeded a market for a DAO without treasury liquidity"
+    );
+
+    await (
+      await treasuryPositionIx(
+        this,
+        otherFixture,
+        otherProposal,
+        "pass",
+        "seedProposalMarket"
+      )
+    )
+      .rpc()
+      .then(callbacks[0], callbacks[1]);
+  });
+}
@@ -0,0 +1,136 @@
+import { assert } from "chai";
+import { getAccount } from "spl-token-bankrun";
+import { PublicKey } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  AutocratClient,
+  getBondEscrowAddr,
+} from "@metadaoproject/futarchy/v0.4";
+
+import { expectError } from "../../utils.js";
+import {
+  DaoFixture,
+  ONE_USDC,
+  buyInMarket,
+  crankProposals,
+  initializeProposal,
+  setupDao,
+  treasuryAccount,
+} from "../utils.js";
+
+const BOND = ONE_USDC.muln(100);
+
+export default function suite() {
+  let autocratClient: AutocratClient;
+  let fixture: DaoFixture;
+  let proposal: PublicKey;
+  let bondEscrow: PublicKey;
+
+  function settleProposalBondIx(ctx: Mocha.Context) {
+    return autocratClient.autocrat.methods.settleProposalBond().accounts({
+      proposal,
+      dao: fixture.dao,
+      bondEscrow,
+      proposer: ctx.payer.publicKey,
+      proposerBondAccount: getAssociatedTokenAddressSync(
+        fixture.USDC,
+        ctx.payer.publicKey
+      ),
+      treasury: fixture.treasury,
+      treasuryBondAccount: treasuryAccount(fixture.USDC, fixture.treasury),
+    });
+  }
+
+  async function usdcBalance(ctx: Mocha.Context, owner: PublicKey) {
+    return (
+      await getAccount(
+        ctx.banksClient,
+        getAssociatedTokenAddressSync(fixture.USDC, owner, true)
+      )
+    ).amount;
+  }
+
+  beforeEach(async function () {
+    autocratClient = this.autocratClient;
+    fixture = await setupDao(this, ({ USDC }) => ({
+      proposalBond: {
+        mint: USDC,
+        amount: BOND,
+        refundQuoteVolume: ONE_USDC.muln(200),
+      },
+    }));
+
+    proposal = await initializeProposal(this, fixture.dao);
+    [bondEscrow] = getBondEscrowAddr(
+      autocratClient.autocrat.programId,
+      proposal
+    );
+  });
+
+  it("escrows the bond until the proposal is finalized", async function () {
+    const escrow = await getAccount(this.banksClient, bondEscrow);
+    assert.equal(escrow.amount.toString(), BOND.toString());
+
+    const storedProposal = await autocratClient.getProposal(proposal);
+    assert.ok(storedProposal.bond.mint.equals(fixture.USDC));
+    assert.isFalse(storedProposal.bondSettled);
+
+    const callbacks = expectError(
+      "ProposalNotFinalized",
+      "settled the bond of a pending proposal"
+    );
+
+    await settleProposalBondIx(this)
+      .rpc()
+      .then(callbacks[0], callbacks[1]);
+  });
+
+  it("sends the bond of an untraded failed proposal to the treasury", async function () {
+    await crankProposals(this, [proposal]);
+    await autocratClient.finalizeProposal(proposal);
+    assert.exists((await autocratClient.getProposal(proposal)).state.failed);
+
+    const preTreasuryUsdc = await usdcBalance(this, fixture.treasury);
+    const preProposerUsdc = await usdcBalance(this, this.payer.publicKey);
+
+    await settleProposalBondIx(this).rpc();
+
+    assert.equal(
+      (await usdcBalance(this, fixture.treasury)) - preTreasuryUsdc,
+      BigInt(BOND.toString())
+    );
+    assert.equal(
+      await usdcBalance(this, this.payer.publicKey),
+      preProposerUsdc
+    );
+
+    assert.isTrue((await autocratClient.getProposal(proposal)).bondSettled);
+    assert.isNull(await this.banksClient.getAccount(bondEscrow));
+  });
+
+  it("refunds the bond of a failed proposal whose markets were traded", async function () {
+    // the same in both markets, so the proposal still fails
+    await buyInMarket(this, proposal, "pass", ONE_USDC.muln(300));
+    await buyInMarket(this, proposal, "fail", ONE_USDC.muln(300));
+
+    await crankProposals(this, [proposal]);
+    await autocratClient.finalizeProposal(proposal);
+
+    const storedProposal = await autocratClient.getProposal(proposal);
+    assert.exists(storedProposal.state.failed);
+    for (const quoteVolume of storedProposal.quoteVolumes) {
+      assert.isTrue(quoteVolume.gte(ONE_USDC.muln(300)));
+    }
+
+    const preTreasuryUsdc = await usdcBalance(this, fixture.treasury);
+    const preProposerUsdc = await usdcBalance(this, this.payer.publicKey);
+
+    await settleProposalBondIx(this).rpc();
+
+    assert.equal(
+      (await usdcBalance(this, this.payer.publicKey)) - preProposerUsdc,
+      BigInt(BOND.toString())
+    );
+    assert.equal(await usdcBalance(this, fixture.treasury), preTreasuryUsdc);
+  });
+}
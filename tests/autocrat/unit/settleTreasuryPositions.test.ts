@@ -0,0 +1,148 @@
+import { assert } from "chai";
+import { getAccount, mintToOverride } from "spl-token-bankrun";
+import { ComputeBudgetProgram, PublicKey } from "@solana/web3.js";
+import { AutocratClient } from "@metadaoproject/futarchy/v0.4";
+
+import { expectError } from "../../utils.js";
+import {
+  DaoFixture,
+  ONE_META,
+  ONE_USDC,
+  crankProposals,
+  createTreasuryConditionalAccounts,
+  initializeProposal,
+  setupDao,
+  treasuryAccount,
+  treasuryPositionIx,
+} from "../utils.js";
+
+export default function suite() {
+  let autocratClient: AutocratClient;
+  let fixture: DaoFixture;
+  let proposal: PublicKey;
+  let preTreasuryBalances: bigint[];
+
+  async function treasuryBalances(ctx: Mocha.Context): Promise<bigint[]> {
+    const balances = [];
+    for (const mint of [fixture.META, fixture.USDC]) {
+      balances.push(
+        (
+          await getAccount(
+            ctx.banksClient,
+            treasuryAccount(mint, fixture.treasury)
+          )
+        ).amount
+      );
+    }
+    return balances;
+  }
+
+  beforeEach(async function () {
+    autocratClient = this.autocratClient;
+    fixture = await setupDao(this, {
+      treasuryLiquidity: {
+        quoteAmount: ONE_USDC.muln(1_000),
+        maxBaseAmount: ONE_META.muln(5),
+        maxPriceDeviationBps: 500,
+      },
+    });
+
+    await mintToOverride(
+      this.context,
+      treasuryAccount(fixture.META, fixture.treasury),
+      100n * 1_000_000_000n
+    );
+    await mintToOverride(
+      this.context,
+      treasuryAccount(fixture.USDC, fixture.treasury),
+      100_000n * 1_000_000n
+    );
+    preTreasuryBalances = await treasuryBalances(this);
+
+    proposal = await initializeProposal(this, fixture.dao);
+    await createTreasuryConditionalAccounts(this, fixture, proposal);
+
+    for (const outcome of ["pass", "fail"] as const) {
+      await (
+        await treasuryPositionIx(
+          this,
+          fixture,
+          proposal,
+          outcome,
+          "seedProposalMarket"
+        )
+      ).rpc();
+    }
+  });
+
+  it("doesn't settle before the proposal is finalized", async function () {
+    const callbacks = expectError(
+      "ProposalNotFinalized",
+      "settled the treasury's positions in a pending proposal"
+    );
+
+    await (
+      await treasuryPositionIx(
+        this,
+        fixture,
+        proposal,
+        "fail",
+        "settleTreasuryPositions"
+      )
+    )
+      .rpc()
+      .then(callbacks[0], callbacks[1]);
+  });
+
+  it("gives the treasury back its tokens", async function () {
+    // nobody trades, so the proposal fails
+    await crankProposals(this, [proposal]);
+    await autocratClient.finalizeProposal(proposal);
+    assert.exists((await autocratClient.getProposal(proposal)).state.failed);
+
+    for (const outcome of ["fail", "pass"] as const) {
+      await (
+        await treasuryPositionIx(
+          this,
+          fixture,
+          proposal,
+          outcome,
+          "settleTreasuryPositions"
+        )
+      ).rpc();
+    }
+
+    const storedProposal = await autocratClient.getProposal(proposal);
+    assert.equal(storedProposal.treasuryLpTokens[0].toString(), "0");
+    assert.equal(storedProposal.treasuryLpTokens[1].toString(), "0");
+
+    // the fail tokens redeem for what was split, and the pass tokens are
+    // worthless, so all that's lost is the AMM's rounding
+    const postTreasuryBalances = await treasuryBalances(this);
+    for (let i = 0; i < 2; i++) {
+      const lost = preTreasuryBalances[i] - postTreasuryBalances[i];
+      assert.isTrue(lost >= 0n && lost <= 1_000n, `lost ${lost}`);
+    }
+
+    const callbacks = expectError(
+      "NoTreasuryPositionToSettle",
+      "settled the same market twice"
+    );
+
+    await (
+      await treasuryPositionIx(
+        this,
+        fixture,
+        proposal,
+        "fail",
+        "settleTreasuryPositions"
+      )
+    )
+      // otherwise, bankrun thinks that this is the first call again
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitPrice({ microLamports: 1 }),
+      ])
+      .rpc()
+      .then(callbacks[0], callbacks[1]);
+  });
+}
@@ -0,0 +1,138 @@
+import { BN } from "bn.js";
+import { assert } from "chai";
+import { getAccount, mintToOverride } from "spl-token-bankrun";
+import {
+  createTransferInstruction,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import {
+  AutocratClient,
+  ProposalInstruction,
+} from "@metadaoproject/futarchy/v0.4";
+
+import { expectError } from "../../utils.js";
+import {
+  DaoFixture,
+  ONE_USDC,
+  initializeProposal,
+  passProposals,
+  setupDao,
+  treasuryAccount,
+} from "../utils.js";
+
+const MAX_PER_PROPOSAL = ONE_USDC.muln(100);
+
+export default function suite() {
+  let autocratClient: AutocratClient;
+  let fixture: DaoFixture;
+
+  function transferFromTreasury(
+    ctx: Mocha.Context,
+    amount: bigint
+  ): ProposalInstruction {
+    const { programId, keys, data } = createTransferInstruction(
+      treasuryAccount(fixture.USDC, fixture.treasury),
+      getAssociatedTokenAddressSync(fixture.USDC, ctx.payer.publicKey),
+      fixture.treasury,
+      amount
+    );
+
+    return { programId, accounts: keys, data };
+  }
+
+  async function payerUsdc(ctx: Mocha.Context): Promise<bigint> {
+    return (
+      await getAccount(
+        ctx.banksClient,
+        getAssociatedTokenAddressSync(fixture.USDC, ctx.payer.publicKey)
+      )
+    ).amount;
+  }
+
+  beforeEach(async function () {
+    autocratClient = this.autocratClient;
+    fixture = await setupDao(this);
+
+    await mintToOverride(
+      this.context,
+      treasuryAccount(fixture.USDC, fixture.treasury),
+      100_000n * 1_000_000n
+    );
+
+    // spending limits can only be set by the treasury, so through a proposal
+    const setSpendingLimit: ProposalInstruction = {
+      programId: autocratClient.autocrat.programId,
+      accounts: [
+        { pubkey: fixture.dao, isSigner: false, isWritable: true },
+        { pubkey: fixture.treasury, isSigner: true, isWritable: false },
+      ],
+      data: autocratClient.autocrat.coder.instruction.encode(
+        "set_spending_limit",
+        {
+          params: {
+            mint: fixture.USDC,
+            maxPerProposal: MAX_PER_PROPOSAL,
+            maxPerWindow: null,
+            windowSlots: new BN(1_000_000),
+          },
+        }
+      ),
+    };
+
+    const proposal = await initializeProposal(this, fixture.dao, [
+      setSpendingLimit,
+    ]);
+    await passProposals(this, [proposal]);
+    await autocratClient.executeProposal(proposal);
+  });
+
+  it("sets spending limits through proposals", async function () {
+    const storedDao = await autocratClient.getDao(fixture.dao);
+
+    assert.equal(storedDao.spendingLimits.length, 1);
+    assert.ok(storedDao.spendingLimits[0].mint.equals(fixture.USDC));
+    assert.equal(
+      storedDao.spendingLimits[0].maxPerProposal.toString(),
+      MAX_PER_PROPOSAL.toString()
+    );
+  });
+
+  it("executes transfers from the treasury within the limit", async function () {
+    const proposal = await initializeProposal(this, fixture.dao, [
+      transferFromTreasury(this, 50_000_000n),
+    ]);
+    await passProposals(this, [proposal]);
+
+    const prePayerUsdc = await payerUsdc(this);
+
+    await autocratClient.executeProposal(proposal);
+
+    assert.equal((await payerUsdc(this)) - prePayerUsdc, 50_000_000n);
+
+    const storedProposal = await autocratClient.getProposal(proposal);
+    assert.exists(storedProposal.state.executed);
+    assert.equal(
+      storedProposal.treasuryOutflows[0].amount.toString(),
+      "50000000"
+    );
+  });
+
+  it("doesn't execute transfers from the treasury over the limit", async function () {
+    const proposal = await initializeProposal(this, fixture.dao, [
+      transferFromTreasury(this, 500_000_000n),
+    ]);
+    await passProposals(this, [proposal]);
+
+    const storedProposal = await autocratClient.getProposal(proposal);
+    assert.equal(storedProposal.spendingLimits.length, 1);
+
+    const callbacks = expectError(
+      "SpendingLimitExceeded",
+      "transferred more than the spending limit out of the treasury"
+    );
+
+    await autocratClient
+      .executeProposal(proposal)
+      .then(callbacks[0], callbacks[1]);
+  });
+}
@@ -0,0 +1,375 @@
+import { BN } from "bn.js";
+import { ComputeBudgetProgram, Keypair, PublicKey } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  AutocratClient,
+  InitializeDaoParams,
+  ProposalInstruction,
+  UpdateDaoParams,
+  PriceMath,
+  getDaoTreasuryAddr,
+  getEventAuthorityAddr,
+} from "@metadaoproject/futarchy/v0.4";
+
+import { advanceBySlots } from "../utils.js";
+
+export const ONE_META = new BN(1_000_000_000);
+export const ONE_USDC = new BN(1_000_000);
+
+// every DAO here prices META at $400, so markets set up with 10 META and
+// 4,000 USDC start at the DAO's `twap_initial_observation`
+export const META_PRICE = 400;
+export const BASE_LIQUIDITY = ONE_META.muln(10);
+export const QUOTE_LIQUIDITY = ONE_USDC.muln(4_000);
+
+// the shortest that proposals can be
+export const SLOTS_PER_PROPOSAL = 216_000;
+
+export type DaoFixture = {
+  dao: PublicKey;
+  treasury: PublicKey;
+  META: PublicKey;
+  USDC: PublicKey;
+};
+
+// creates fresh META and USDC mints, funds the payer with both, and creates
+// a DAO for them with the treasury's token accounts. `params` can depend on
+// the mints, for settings such as bonds
+export async function setupDao(
+  ctx: Mocha.Context,
+  params:
+    | Partial<InitializeDaoParams>
+    | ((mints: {
+        META: PublicKey;
+        USDC: PublicKey;
+      }) => Partial<InitializeDaoParams>) = {}
+): Promise<DaoFixture> {
+  const autocratClient: AutocratClient = ctx.autocratClient;
+
+  const META = await ctx.createMint(ctx.payer.publicKey, 9);
+  const USDC = await ctx.createMint(ctx.payer.publicKey, 6);
+
+  await ctx.createTokenAccount(META, ctx.payer.publicKey);
+  await ctx.createTokenAccount(USDC, ctx.payer.publicKey);
+
+  await ctx.mintTo(META, ctx.payer.publicKey, ctx.payer, 1_000 * 10 ** 9);
+  await ctx.mintTo(USDC, ctx.payer.publicKey, ctx.payer, 1_000_000 * 10 ** 6);
+
+  const twapInitialObservation = PriceMath.getAmmPrice(META_PRICE, 9, 6);
+  const daoKeypair = Keypair.generate();
+
+  await autocratClient
+    .initializeDaoIx(
+      daoKeypair,
+      META,
+      {
+        twapInitialObservation,
+        twapMaxObservationChangePerUpdate: twapInitialObservation.divn(50),
+        minQuoteFutarchicLiquidity: ONE_USDC.muln(100),
+        minBaseFutarchicLiquidity: ONE_META.divn(10),
+        passThresholdBps: null,
+        slotsPerProposal: new BN(SLOTS_PER_PROPOSAL),
+        minAverageQuoteLiquidity: null,
+        executionDelaySlots: null,
+        vetoAuthority: null,
+        executionWindowSlots: null,
+        decisionRule: null,
+        proposalBond: null,
+        maxActiveProposals: null,
+        maxActiveProposalsPerProposer: null,
+        spotAmm: null,
+        treasuryLiquidity: null,
+        ...(typeof params == "function" ? params({ META, USDC }) : params),
+      },
+      USDC
+    )
+    .preInstructions([
+      ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+    ])
+    .rpc();
+
+  const [treasury] = getDaoTreasuryAddr(
+    autocratClient.autocrat.programId,
+    daoKeypair.publicKey
+  );
+
+  await ctx.createTokenAccount(META, treasury);
+  await ctx.createTokenAccount(USDC, treasury);
+
+  return { dao: daoKeypair.publicKey, treasury, META, USDC };
+}
+
+export function updateDaoInstruction(
+  autocratClient: AutocratClient,
+  { dao, treasury }: DaoFixture,
+  params: Partial<UpdateDaoParams>
+): ProposalInstruction {
+  const daoParams: UpdateDaoParams = {
+    passThresholdBps: null,
+    slotsPerProposal: null,
+    twapInitialObservation: null,
+    twapMaxObservationChangePerUpdate: null,
+    minQuoteFutarchicLiquidity: null,
+    minBaseFutarchicLiquidity: null,
+    minAverageQuoteLiquidity: null,
+    executionDelaySlots: null,
+    vetoAuthority: null,
+    executionWindowSlots: null,
+    decisionRule: null,
+    proposalBond: null,
+    maxActiveProposals: null,
+    maxActiveProposalsPerProposer: null,
+    spotAmm: null,
+    treasuryLiquidity: null,
+    ...params,
+  };
+
+  return {
+    programId: autocratClient.autocrat.programId,
+    accounts: [
+      { pubkey: dao, isSigner: false, isWritable: true },
+      { pubkey: treasury, isSigner: true, isWritable: false },
+    ],
+    data: autocratClient.autocrat.coder.instruction.encode("update_dao", {
+      daoParams,
+    }),
+  };
+}
+
+export async function initializeProposal(
+  ctx: Mocha.Context,
+  dao: PublicKey,
+  instructions: ProposalInstruction[] = []
+): Promise<PublicKey> {
+  const autocratClient: AutocratClient = ctx.autocratClient;
+
+  return autocratClient.initializeProposal(
+    dao,
+    "",
+    instructions,
+    BASE_LIQUIDITY,
+    QUOTE_LIQUIDITY
+  );
+}
+
+// swaps `quoteAmount` of USDC for META in one of a proposal's markets,
+// splitting the USDC first
+export async function buyInMarket(
+  ctx: Mocha.Context,
+  proposal: PublicKey,
+  outcome: "pass" | "fail",
+  quoteAmount: BN
+) {
+  const autocratClient: AutocratClient = ctx.autocratClient;
+
+  const storedProposal = await autocratClient.getProposal(proposal);
+  const storedDao = await autocratClient.getDao(storedProposal.dao);
+  const pdas = autocratClient.getProposalPdas(
+    proposal,
+    storedDao.tokenMint,
+    storedDao.usdcMint,
+    storedProposal.dao
+  );
+
+  await ctx.vaultClient
+    .splitTokensIx(
+      pdas.question,
+      pdas.quoteVault,
+      storedDao.usdcMint,
+      quoteAmount,
+      2
+    )
+    .rpc();
+
+  const [amm, baseMint, quoteMint] =
+    outcome == "pass"
+      ? [pdas.passAmm, pdas.passBaseMint, pdas.passQuoteMint]
+      : [pdas.failAmm, pdas.failBaseMint, pdas.failQuoteMint];
+
+  await ctx.ammClient
+    .swapIx(amm, baseMint, quoteMint, { buy: {} }, quoteAmount, new BN(0))
+    .rpc();
+}
+
+// bankrun rejects transactions that it has already processed, so every
+// crank gets a different priority fee
+let crankNonce = 0;
+
+// advances the clock by `slots` in steps, cranking every market's TWAP after
+// each step
+export async function crankMarkets(
+  ctx: Mocha.Context,
+  amms: PublicKey[],
+  slots: number = SLOTS_PER_PROPOSAL + 10_000,
+  slotsPerCrank: number = 10_000
+) {
+  for (let elapsed = 0; elapsed < slots; elapsed += slotsPerCrank) {
+    await advanceBySlots(ctx.context, BigInt(slotsPerCrank));
+
+    await ctx.ammClient
+      .crankThatTwapIx(amms[0])
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitPrice({
+          microLamports: crankNonce++,
+        }),
+        ...(await Promise.all(
+          amms
+            .slice(1)
+            .map((amm) => ctx.ammClient.crankThatTwapIx(amm).instruction())
+        )),
+      ])
+      .rpc();
+  }
+}
+
+export async function crankProposals(
+  ctx: Mocha.Context,
+  proposals: PublicKey[],
+  slots?: number
+) {
+  const amms: PublicKey[] = [];
+
+  for (const proposal of proposals) {
+    const storedProposal = await ctx.autocratClient.getProposal(proposal);
+    amms.push(storedProposal.passAmm, storedProposal.failAmm);
+  }
+
+  await crankMarkets(ctx, amms, slots);
+}
+
+// buys in the pass markets of `proposals` until they pass, then cranks and
+// finalizes them
+export async function passProposals(
+  ctx: Mocha.Context,
+  proposals: PublicKey[]
+) {
+  const autocratClient: AutocratClient = ctx.autocratClient;
+
+  for (const proposal of proposals) {
+    await buyInMarket(ctx, proposal, "pass", ONE_USDC.muln(1_000));
+  }
+
+  await crankProposals(ctx, proposals);
+
+  for (const proposal of proposals) {
+    await autocratClient.finalizeProposal(proposal);
+  }
+}
+
+export function treasuryAccount(
+  mint: PublicKey,
+  treasury: PublicKey
+): PublicKey {
+  return getAssociatedTokenAddressSync(mint, treasury, true);
+}
+
+// the treasury's conditional token accounts for a proposal's vaults, in the
+// order that `seed_proposal_market` and `settle_treasury_positions` take them
+async function treasuryConditionalAccounts(
+  ctx: Mocha.Context,
+  { treasury, META, USDC, dao }: DaoFixture,
+  proposal: PublicKey
+) {
+  const autocratClient: AutocratClient = ctx.autocratClient;
+  const pdas = autocratClient.getProposalPdas(proposal, META, USDC, dao);
+
+  const accounts = [];
+  for (const mints of [
+    [pdas.failBaseMint, pdas.passBaseMint],
+    [pdas.failQuoteMint, pdas.passQuoteMint],
+  ]) {
+    accounts.push(
+      ...mints.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })),
+      ...mints.map((mint) => ({
+        pubkey: treasuryAccount(mint, treasury),
+        isSigner: false,
+        isWritable: true,
+      }))
+    );
+  }
+
+  return accounts;
+}
+
+// `seed_proposal_market` needs these to exist already
+export async function createTreasuryConditionalAccounts(
+  ctx: Mocha.Context,
+  fixture: DaoFixture,
+  proposal: PublicKey
+) {
+  const { passBaseMint, failBaseMint, passQuoteMint, failQuoteMint } =
+    ctx.autocratClient.getProposalPdas(
+      proposal,
+      fixture.META,
+      fixture.USDC,
+      fixture.dao
+    );
+
+  for (const mint of [
+    failBaseMint,
+    passBaseMint,
+    failQuoteMint,
+    passQuoteMint,
+  ]) {
+    await ctx.createTokenAccount(mint, fixture.treasury);
+  }
+}
+
+// `seed_proposal_market` and `settle_treasury_positions` take the same
+// accounts
+export async function treasuryPositionIx(
+  ctx: Mocha.Context,
+  fixture: DaoFixture,
+  proposal: PublicKey,
+  outcome: "pass" | "fail",
+  instruction: "seedProposalMarket" | "settleTreasuryPositions"
+) {
+  const autocratClient: AutocratClient = ctx.autocratClient;
+  const { dao, treasury, META, USDC } = fixture;
+  const pdas = autocratClient.getProposalPdas(proposal, META, USDC, dao);
+
+  const [amm, lpMint, ammBaseMint, ammQuoteMint] =
+    outcome == "pass"
+      ? [pdas.passAmm, pdas.passLp, pdas.passBaseMint, pdas.passQuoteMint]
+      : [pdas.failAmm, pdas.failLp, pdas.failBaseMint, pdas.failQuoteMint];
+
+  const vaultProgram = autocratClient.vaultClient.vaultProgram.programId;
+  const ammProgram = autocratClient.ammClient.getProgramId();
+
+  return autocratClient.autocrat.methods[instruction]()
+    .accounts({
+      proposal,
+      dao,
+      treasury,
+      question: pdas.question,
+      baseVault: pdas.baseVault,
+      quoteVault: pdas.quoteVault,
+      baseVaultUnderlyingTokenAccount: getAssociatedTokenAddressSync(
+        META,
+        pdas.baseVault,
+        true
+      ),
+      quoteVaultUnderlyingTokenAccount: getAssociatedTokenAddressSync(
+        USDC,
+        pdas.quoteVault,
+        true
+      ),
+      treasuryBaseAccount: treasuryAccount(META, treasury),
+      treasuryQuoteAccount: treasuryAccount(USDC, treasury),
+      amm,
+      lpMint,
+      ammVaultAtaBase: getAssociatedTokenAddressSync(ammBaseMint, amm, true),
+      ammVaultAtaQuote: getAssociatedTokenAddressSync(ammQuoteMint, amm, true),
+      treasuryLpAccount: treasuryAccount(lpMint, treasury),
+      vaultProgram,
+      vaultEventAuthority: getEventAuthorityAddr(vaultProgram)[0],
+      ammProgram,
+      ammEventAuthority: getEventAuthorityAddr(ammProgram)[0],
+    })
+    .remainingAccounts(
+      await treasuryConditionalAccounts(ctx, fixture, proposal)
+    )
+    .preInstructions([
+      ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+    ]);
+}